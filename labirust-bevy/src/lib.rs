@@ -0,0 +1,277 @@
+//! # Labirust Bevy
+//!
+//! This crate contains a [`bevy`] plugin running a labirust [`Algorithm`] inside an app.
+//! The resolution is held by the [`Resolution`] non-send resource and progresses by one tick on each [`FixedUpdate`],
+//! emitting [`ResolutionEvent`]s. With the `render` feature, the maze is drawn as a map of sprites, one [`Tile`] per cell and per wall.
+//!
+//! ```no_run
+//! use bevy::prelude::*;
+//! use labirust::{implementations::DepthFirst, Algorithm, MazeGenerator, SimpleGenerator};
+//! use labirust_bevy::{LabirustPlugin, Resolution};
+//!
+//! let maze = SimpleGenerator::new(40, 20).generate().unwrap();
+//! App::new()
+//!     .add_plugins(LabirustPlugin::default())
+//!     .insert_non_send_resource(Resolution::new(maze, DepthFirst::new().boxed()))
+//!     .run();
+//! ```
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use labirust::{Algorithm, Execution, Maze, Pos};
+
+/// Part of the maze displayed by a [`Tile`], shared with the other labirust frontends.
+pub use labirust::TileKind;
+
+/// Plugin advancing the [`Resolution`] on each [`FixedUpdate`], and drawing it with the `render` feature.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LabirustPlugin {
+    timestep: Option<Duration>,
+    tile_size: f32,
+}
+
+impl LabirustPlugin {
+    /// Set the duration of the fixed timestep of the app, hence the delay between two ticks of the resolution.
+    pub fn with_timestep(self, timestep: Duration) -> Self {
+        Self {
+            timestep: Some(timestep),
+            ..self
+        }
+    }
+
+    /// Set the size in pixels of the side of a [`Tile`].
+    pub fn with_tile_size(self, tile_size: f32) -> Self {
+        Self { tile_size, ..self }
+    }
+}
+
+impl Default for LabirustPlugin {
+    /// Keeps the fixed timestep of the app, with tiles of 16 pixels.
+    fn default() -> Self {
+        Self {
+            timestep: None,
+            tile_size: 16.,
+        }
+    }
+}
+
+impl Plugin for LabirustPlugin {
+    fn build(&self, app: &mut App) {
+        let Self {
+            timestep,
+            tile_size,
+        } = *self;
+        if let Some(timestep) = timestep {
+            app.insert_resource(Time::<Fixed>::from_duration(timestep));
+        }
+        app.insert_resource(TileSize(tile_size))
+            .add_event::<ResolutionEvent>()
+            .add_systems(FixedUpdate, advance_resolution);
+        #[cfg(feature = "render")]
+        app.add_systems(Update, (spawn_tiles, paint_tiles).chain());
+    }
+}
+
+/// Size in pixels of the side of a [`Tile`].
+#[derive(Debug, Clone, Copy, PartialEq, Resource)]
+pub struct TileSize(pub f32);
+
+/// The resolution of a [`Maze`] by an [`Algorithm`], to insert as a non-send resource as algorithms are not required to be [`Send`].
+pub struct Resolution {
+    execution: Execution,
+    state: ResolutionState,
+}
+
+/// Progress of a [`Resolution`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResolutionState {
+    /// The algorithm has not reached the end of the maze yet.
+    Running,
+    /// The algorithm reached the end of the maze.
+    Finished,
+    /// The algorithm gave up or guessed an invalid path.
+    Failed,
+}
+
+impl Resolution {
+    /// Constructor, for a resolution of `maze` by `algorithm` starting on the next [`FixedUpdate`].
+    pub fn new(maze: Maze, algorithm: Box<dyn Algorithm>) -> Self {
+        Self {
+            execution: Execution::new(maze, algorithm),
+            state: ResolutionState::Running,
+        }
+    }
+
+    /// The underlying [`Execution`], giving access to the maze, the current path and the tried positions.
+    pub fn execution(&self) -> &Execution {
+        &self.execution
+    }
+
+    /// Progress of the resolution.
+    pub fn state(&self) -> ResolutionState {
+        self.state
+    }
+}
+
+/// Emitted by the [`LabirustPlugin`] each time the [`Resolution`] progresses.
+#[derive(Debug, Event)]
+pub enum ResolutionEvent {
+    /// A tick was done without reaching the end of the maze.
+    Tick(usize),
+    /// The end of the maze was reached after this number of ticks.
+    Finished(usize),
+    /// The resolution stopped on an error.
+    Failed(labirust::Error),
+}
+
+/// Progress the [`Resolution`] by one tick, if there is one still running.
+pub fn advance_resolution(
+    resolution: Option<NonSendMut<Resolution>>,
+    mut events: EventWriter<ResolutionEvent>,
+) {
+    let Some(mut resolution) = resolution else {
+        return;
+    };
+    if resolution.state != ResolutionState::Running {
+        return;
+    }
+    let Resolution { execution, state } = &mut *resolution;
+    match execution.step() {
+        Ok(false) => {
+            events.send(ResolutionEvent::Tick(execution.tick()));
+        }
+        Ok(true) => {
+            *state = ResolutionState::Finished;
+            events.send(ResolutionEvent::Finished(execution.tick()));
+        }
+        Err(error) => {
+            *state = ResolutionState::Failed;
+            events.send(ResolutionEvent::Failed(error));
+        }
+    }
+}
+
+/// A sprite displaying part of the maze of the [`Resolution`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Component)]
+pub struct Tile {
+    kind: TileKind,
+}
+
+impl Tile {
+    /// Part of the maze displayed by the tile.
+    pub fn kind(&self) -> TileKind {
+        self.kind
+    }
+
+    /// Color of the tile for the current state of `resolution`.
+    pub fn color(&self, resolution: &Resolution) -> Color {
+        let execution = resolution.execution();
+        let maze = execution.maze();
+        let on_path = |position: &Pos| execution.path().contains(position);
+        let tried = |&position: &Pos| execution.tried().contains(position);
+        match self.kind {
+            TileKind::Wall => Color::srgb(0.1, 0.1, 0.1),
+            TileKind::Cell(position) if maze.is_start(position) => Color::srgb(0.2, 0.8, 0.2),
+            TileKind::Cell(position) if maze.is_end(position) => Color::srgb(0.8, 0.2, 0.2),
+            TileKind::Cell(position) if on_path(&position) => Color::srgb(0.9, 0.6, 0.1),
+            TileKind::Opening(a, b) if on_path(&a) && on_path(&b) => Color::srgb(0.9, 0.6, 0.1),
+            TileKind::Cell(position) if tried(&position) => Color::srgb(0.4, 0.5, 0.7),
+            TileKind::Opening(a, b) if tried(&a) && tried(&b) => Color::srgb(0.4, 0.5, 0.7),
+            _ => Color::srgb(0.9, 0.9, 0.9),
+        }
+    }
+}
+
+/// Spawn the [`Tile`]s of the [`Resolution`] once it is inserted, centered on the origin.
+/// Mazes that are neither square nor toroidal are not drawn, with a single warning (c.f. [`Maze::try_tiles`]).
+#[cfg(feature = "render")]
+pub fn spawn_tiles(
+    mut commands: Commands,
+    resolution: Option<NonSend<Resolution>>,
+    tile_size: Res<TileSize>,
+    tiles: Query<(), With<Tile>>,
+    mut warned: Local<bool>,
+) {
+    let Some(resolution) = resolution else {
+        return;
+    };
+    if !tiles.is_empty() {
+        return;
+    }
+    let TileSize(size) = *tile_size;
+    let maze = resolution.execution().maze();
+    let (columns, rows) = match maze.try_tiles() {
+        Ok(tiles) => tiles,
+        Err(error) => {
+            if !std::mem::replace(&mut *warned, true) {
+                warn!("{error}");
+            }
+            return;
+        }
+    };
+    for y in 0..rows {
+        for x in 0..columns {
+            let tile = Tile {
+                kind: TileKind::of(maze, Pos::new(x, y)),
+            };
+            let translation = Vec3::new(
+                (x as f32 - (columns - 1) as f32 / 2.) * size,
+                ((rows - 1) as f32 / 2. - y as f32) * size,
+                0.,
+            );
+            commands.spawn((
+                tile,
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: tile.color(&resolution),
+                        custom_size: Some(Vec2::splat(size)),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(translation),
+                    ..default()
+                },
+            ));
+        }
+    }
+}
+
+/// Update the color of the [`Tile`]s when the [`Resolution`] progresses.
+#[cfg(feature = "render")]
+pub fn paint_tiles(
+    resolution: Option<NonSend<Resolution>>,
+    mut tiles: Query<(&Tile, &mut Sprite)>,
+) {
+    let Some(resolution) = resolution else {
+        return;
+    };
+    if !resolution.is_changed() {
+        return;
+    }
+    for (tile, mut sprite) in &mut tiles {
+        sprite.color = tile.color(&resolution);
+    }
+}
+
+#[test]
+fn resolution() {
+    use labirust::{implementations::BreathFirst, MazeGenerator, SimpleGenerator};
+
+    let maze = SimpleGenerator::new(10, 8).generate().unwrap();
+    let mut app = App::new();
+    app.add_plugins(LabirustPlugin::default())
+        .insert_non_send_resource(Resolution::new(maze, BreathFirst::new().boxed()));
+    let mut ticks = 0;
+    while app.world().non_send_resource::<Resolution>().state() == ResolutionState::Running {
+        app.world_mut().run_schedule(FixedUpdate);
+        ticks += 1;
+    }
+
+    let resolution = app.world().non_send_resource::<Resolution>();
+    assert_eq!(resolution.state(), ResolutionState::Finished);
+    assert_eq!(resolution.execution().tick(), ticks);
+    let events = app.world().resource::<Events<ResolutionEvent>>();
+    let mut reader = events.get_reader();
+    let last = reader.read(events).last();
+    assert!(matches!(last, Some(ResolutionEvent::Finished(t)) if *t == ticks));
+}
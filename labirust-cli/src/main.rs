@@ -1,30 +1,25 @@
-use std::str::FromStr;
+use std::path::PathBuf;
 
-use clap::Parser;
-use labirust::{implementations::*, Algorithm, Executor, SimpleGenerator};
+use clap::{Parser, Subcommand};
+use labirust::{implementations::Registry, Executor, SimpleGenerator};
 
-enum Algorithms {
-    DepthFirst,
-    BreathFirst,
-}
-
-impl FromStr for Algorithms {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "depth-first" => Ok(Self::DepthFirst),
-            "breath-first" => Ok(Self::BreathFirst),
-            _ => Err("No right pattern".into()),
-        }
-    }
-}
+mod serve;
 
 #[derive(Parser)]
+#[clap(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Parameters {
+    #[clap(subcommand)]
+    command: Option<Command>,
+
     /// Algorithm to use in the simulation.
-    /// One of: "depth-first", "breath-first"
-    algorithm: Algorithms,
+    /// One of: "depth-first", "breath-first", "bidirectional", "wall-follower-left", "wall-follower-right", "tremaux", "random-mouse", "ida-star", "pledge", "jump-point", "wavefront", "beam", "fringe", or one registered by a plugin.
+    #[clap(required = true)]
+    algorithm: Option<String>,
+
+    /// Shared library registering additional algorithms, c.f. the `plugins` module of labirust.
+    /// Must be built with the same compiler, version and features of labirust as the cli.
+    #[clap(long = "plugin")]
+    plugins: Vec<PathBuf>,
 
     /// Width of the maze to solve.
     #[clap(short, default_value_t = 40)]
@@ -39,20 +34,55 @@ struct Parameters {
     delay: usize,
 }
 
+#[derive(Subcommand)]
+enum Command {
+    /// Serve maze generation and resolution over HTTP, c.f. the `POST /generate` and `POST /solve` endpoints.
+    Serve {
+        /// Port to listen on.
+        #[clap(long, default_value_t = 8080)]
+        port: u16,
+    },
+}
+
 fn main() {
     let params = Parameters::parse();
 
-    let algorithm: Box<dyn Algorithm> = match params.algorithm {
-        Algorithms::DepthFirst => Box::new(DepthFirst::new()),
-        Algorithms::BreathFirst => Box::new(BreathFirst::new()),
+    if let Some(Command::Serve { port }) = params.command {
+        if let Err(error) = serve::serve(port) {
+            eprintln!("error: {error}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let mut registry = Registry::new();
+    for plugin in &params.plugins {
+        // SAFETY: plugins are provided by the user, who is trusted to build them as documented.
+        if let Err(error) = unsafe { registry.load(plugin) } {
+            eprintln!("error: {}: {error}", plugin.display());
+            std::process::exit(2);
+        }
+    }
+
+    let algorithm = params.algorithm.expect("required argument");
+    let algorithm = match registry.create(&algorithm) {
+        Ok(algorithm) => algorithm,
+        Err(error) => {
+            eprintln!("error: {error}");
+            std::process::exit(2);
+        }
     };
 
-    let mut executor = Executor::build_dyn(algorithm, |b| {
+    let executor = Executor::build_dyn(algorithm, |b| {
         b.generated(Box::new(SimpleGenerator::new(
             params.width as isize,
             params.height as isize,
         )))
+        .delay_ms(params.delay as u64)
     });
 
-    executor.run();
+    if let Err(error) = executor.and_then(|mut executor| executor.run()) {
+        eprintln!("error: {error}");
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,503 @@
+//! ## Serve
+//!
+//! This module contains the HTTP server mode of the CLI, exposing maze generation and resolution as JSON endpoints:
+//! * `POST /generate` with `{ "width", "height", "generator", "seed" }` answers the generated maze.
+//! * `POST /solve` with `{ "maze", "algorithm", "max_ticks" }` answers the path found by the algorithm and its metrics,
+//!   `max_ticks` being at most [`MAX_TICKS`].
+//! * `GET /stream` upgrades to a WebSocket, expecting the same body as `/solve` with an optional `delay_ms` as first message,
+//!   then streams a [`TraceFrame`] per tick as they are computed, ending with either a `finished` or an `error` message.
+//!   Streams run on their own threads, at most [`MAX_STREAMS`] at once, so that they do not hold the workers answering other requests.
+
+use std::{
+    io::{Read, Write},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use labirust::{
+    implementations, AldousBroderGenerator, CellSelection, Error, Execution, Executor,
+    GrowingTreeGenerator, HuntAndKillGenerator, ImperfectGenerator, KruskalGenerator, Maze,
+    MazeGenerator, Renderer, SimpleGenerator, TraceFrame, WilsonGenerator,
+};
+use serde::{Deserialize, Serialize};
+use tiny_http::{Header, Method, Request, Response, Server, StatusCode};
+use tungstenite::{
+    handshake::derive_accept_key,
+    protocol::{Role, WebSocketConfig},
+    Message, WebSocket,
+};
+
+/// Largest number of cells of a maze handled by the server.
+const MAX_CELLS: isize = 1_000_000;
+
+/// Largest number of portals of a maze handled by the server.
+const MAX_PORTALS: usize = 100_000;
+
+/// Largest request body or WebSocket message read by the server, in bytes, enough for a maze of [`MAX_CELLS`] cells.
+const MAX_BODY: usize = 16 * 1024 * 1024;
+
+/// Number of threads handling requests, further requests waiting for one of them to be free.
+const WORKERS: usize = 8;
+
+/// Number of ticks after which a resolution is stopped, unless requested otherwise.
+const DEFAULT_MAX_TICKS: usize = 1_000_000;
+
+/// Largest number of ticks of a resolution, larger requested numbers being lowered to it.
+const MAX_TICKS: usize = 10_000_000;
+
+/// Largest number of ticks streamed over a WebSocket, larger requested numbers being lowered to it.
+const MAX_STREAM_TICKS: usize = 10_000;
+
+/// Longest delay between two frames of a stream, in milliseconds, longer requested delays being shortened to it.
+const MAX_DELAY_MS: u64 = 1_000;
+
+/// Largest number of streams handled at once, further WebSocket upgrades being refused.
+const MAX_STREAMS: usize = 32;
+
+/// Longest wait for the first message of a stream before giving up on it.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Number of streams currently handled.
+static STREAMS: AtomicUsize = AtomicUsize::new(0);
+
+/// Generate a maze of a width and a height, from a seed if there is one.
+type Generate = fn(isize, isize, Option<u64>) -> labirust::Result<Maze>;
+
+/// Generators selectable through the `generator` field, by name.
+const GENERATORS: &[(&str, Generate)] = &[
+    ("simple", |width, height, seed| {
+        generate_with(SimpleGenerator::new(width, height), seed)
+    }),
+    ("kruskal", |width, height, seed| {
+        generate_with(KruskalGenerator::new(width, height), seed)
+    }),
+    ("wilson", |width, height, seed| {
+        generate_with(WilsonGenerator::new(width, height), seed)
+    }),
+    ("aldous-broder", |width, height, seed| {
+        generate_with(AldousBroderGenerator::new(width, height), seed)
+    }),
+    ("hunt-and-kill", |width, height, seed| {
+        generate_with(HuntAndKillGenerator::new(width, height), seed)
+    }),
+    ("growing-tree", |width, height, seed| {
+        let selection = CellSelection::Mixed { newest: 50 };
+        generate_with(GrowingTreeGenerator::new(width, height, selection), seed)
+    }),
+    ("imperfect", |width, height, seed| {
+        generate_with(ImperfectGenerator::new(width, height, 0.1), seed)
+    }),
+];
+
+/// Generate a maze with `generator`, from a seed if there is one.
+fn generate_with(mut generator: impl MazeGenerator, seed: Option<u64>) -> labirust::Result<Maze> {
+    let maze = match seed {
+        Some(seed) => generator.generate_seeded(seed),
+        None => generator.generate(),
+    };
+    Ok(maze?)
+}
+
+#[derive(Deserialize)]
+struct GenerateRequest {
+    width: isize,
+    height: isize,
+    #[serde(default = "default_generator")]
+    generator: String,
+    seed: Option<u64>,
+}
+
+fn default_generator() -> String {
+    GENERATORS[0].0.into()
+}
+
+#[derive(Deserialize)]
+struct SolveRequest {
+    maze: Maze,
+    algorithm: String,
+    max_ticks: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct StreamRequest {
+    #[serde(flatten)]
+    solve: SolveRequest,
+    #[serde(default)]
+    delay_ms: u64,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+/// Messages sent over the WebSocket of `/stream`.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StreamMessage {
+    Frame(TraceFrame),
+    Finished { ticks: usize },
+    Error { error: String },
+}
+
+/// Listen on `port` until the process is stopped, handling requests on a pool of [`WORKERS`] threads.
+pub fn serve(port: u16) -> Result<(), String> {
+    let server = Server::http(("0.0.0.0", port)).map_err(|error| error.to_string())?;
+    println!("listening on port {port}");
+    // accepting waits while every worker is busy and the queue is full
+    let (sender, receiver) = mpsc::sync_channel::<Request>(WORKERS);
+    let receiver = Arc::new(Mutex::new(receiver));
+    for _ in 0..WORKERS {
+        let receiver = Arc::clone(&receiver);
+        thread::spawn(move || loop {
+            let request = match receiver.lock() {
+                Ok(receiver) => receiver.recv(),
+                Err(_) => return,
+            };
+            match request {
+                Ok(request) => respond(request),
+                Err(_) => return,
+            }
+        });
+    }
+    for request in server.incoming_requests() {
+        if sender.send(request).is_err() {
+            return Err("every worker stopped".into());
+        }
+    }
+    Ok(())
+}
+
+/// Answer a request, streaming over a WebSocket for `/stream`.
+fn respond(mut request: Request) {
+    if request.url() == "/stream" {
+        return stream(request);
+    }
+    let mut body = String::new();
+    let read = request
+        .as_reader()
+        .take(MAX_BODY as u64 + 1)
+        .read_to_string(&mut body);
+    let (status, json) = match read {
+        Ok(length) if length > MAX_BODY => failure(
+            413,
+            format!("request bodies are limited to {MAX_BODY} bytes"),
+        ),
+        Ok(_) => handle(request.method(), request.url(), &body),
+        Err(error) => failure(400, error),
+    };
+    let header = Header::from_bytes("Content-Type", "application/json").expect("valid header");
+    let response = Response::from_string(json)
+        .with_status_code(status)
+        .with_header(header);
+    if let Err(error) = request.respond(response) {
+        eprintln!("error: {error}");
+    }
+}
+
+/// One of the [`MAX_STREAMS`] streams handled at once, released when dropped.
+struct StreamSlot;
+
+impl StreamSlot {
+    /// Take a slot, `None` if every one of them is taken.
+    fn acquire() -> Option<Self> {
+        let taken = STREAMS.fetch_update(Ordering::AcqRel, Ordering::Acquire, |streams| {
+            (streams < MAX_STREAMS).then_some(streams + 1)
+        });
+        taken.ok().map(|_| Self)
+    }
+}
+
+impl Drop for StreamSlot {
+    fn drop(&mut self) {
+        STREAMS.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Upgrade the request to a WebSocket, then stream the resolution described by its first message on a thread of its own.
+fn stream(request: Request) {
+    let key = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Sec-WebSocket-Key"))
+        .map(|header| derive_accept_key(header.value.as_bytes()));
+    let slot = StreamSlot::acquire();
+    let (accept, slot) = match (key, slot) {
+        (Some(accept), Some(slot)) => (accept, slot),
+        (key, _) => {
+            let (status, json) = match key {
+                None => failure(400, "expected a WebSocket upgrade"),
+                Some(_) => failure(503, format!("streams are limited to {MAX_STREAMS} at once")),
+            };
+            let response = Response::from_string(json).with_status_code(status);
+            if let Err(error) = request.respond(response) {
+                eprintln!("error: {error}");
+            }
+            return;
+        }
+    };
+    let header = Header::from_bytes("Sec-WebSocket-Accept", accept).expect("valid header");
+    let response = Response::empty(StatusCode(101)).with_header(header);
+    let socket = request.upgrade("websocket", response);
+    let config = WebSocketConfig {
+        max_message_size: Some(MAX_BODY),
+        max_frame_size: Some(MAX_BODY),
+        ..WebSocketConfig::default()
+    };
+    let socket = WebSocket::from_raw_socket(socket, Role::Server, Some(config));
+
+    thread::spawn(move || {
+        let _slot = slot;
+        if let Err(error) = respond_stream(socket, READ_TIMEOUT) {
+            eprintln!("error: {error}");
+        }
+    });
+}
+
+/// Read the resolution to stream from the first message of `socket`, stream it and close the socket.
+/// Gives up when the first message takes longer than `timeout` to arrive,
+/// the socket being closed once the client sends anything or disconnects, as the upgraded socket has no timeout of its own.
+fn respond_stream<S>(socket: WebSocket<S>, timeout: Duration) -> Result<(), String>
+where
+    S: Read + Write + Send + 'static,
+{
+    // nothing is written before the first message, so that the socket can be read on another thread
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut socket = socket;
+        let body = socket.read().and_then(Message::into_text);
+        let _ = sender.send((socket, body));
+    });
+    let (mut socket, body) = receiver
+        .recv_timeout(timeout)
+        .map_err(|_| format!("no request received within {timeout:?}"))?;
+    let body = body.map_err(|error| error.to_string())?;
+    let mut send = |message: StreamMessage| {
+        let json = serde_json::to_string(&message).expect("message serialization");
+        socket
+            .send(Message::Text(json))
+            .map_err(|error| error.to_string())
+    };
+    match parse::<StreamRequest>(&body) {
+        Ok(request) => stream_messages(request, &mut send)?,
+        Err((_, error)) => send(StreamMessage::Error { error })?,
+    }
+    socket.close(None).map_err(|error| error.to_string())
+}
+
+/// Run the requested resolution, handing a message to `send` on each tick, then a last one on completion or failure.
+fn stream_messages<E>(
+    request: StreamRequest,
+    mut send: impl FnMut(StreamMessage) -> Result<(), E>,
+) -> Result<(), E> {
+    let StreamRequest {
+        solve:
+            SolveRequest {
+                maze,
+                algorithm,
+                max_ticks,
+            },
+        delay_ms,
+    } = request;
+    if let Err((_, error)) = check_maze(&maze) {
+        return send(StreamMessage::Error { error });
+    }
+    let algorithm = match implementations::by_name(&algorithm) {
+        Ok(algorithm) => algorithm,
+        Err(error) => {
+            let error = error.to_string();
+            return send(StreamMessage::Error { error });
+        }
+    };
+    let max_ticks = max_ticks.unwrap_or(DEFAULT_MAX_TICKS).min(MAX_STREAM_TICKS);
+    let delay = Duration::from_millis(delay_ms.min(MAX_DELAY_MS));
+    let mut execution = Execution::new(maze, algorithm);
+    loop {
+        if execution.tick() >= max_ticks {
+            let error = Error::Timeout { ticks: max_ticks }.to_string();
+            return send(StreamMessage::Error { error });
+        }
+        match execution.step() {
+            Ok(finished) => {
+                // a maze starting on its end is finished without any frame
+                if let Some(frame) = execution.frame() {
+                    send(StreamMessage::Frame(frame))?;
+                }
+                if finished {
+                    let ticks = execution.tick();
+                    return send(StreamMessage::Finished { ticks });
+                }
+            }
+            Err(error) => {
+                let error = error.to_string();
+                return send(StreamMessage::Error { error });
+            }
+        }
+        thread::sleep(delay);
+    }
+}
+
+/// Answer a request, returns the status code and the JSON body of the response.
+fn handle(method: &Method, url: &str, body: &str) -> (u16, String) {
+    let result = match (method, url) {
+        (Method::Post, "/generate") => parse(body).and_then(generate),
+        (Method::Post, "/solve") => parse(body).and_then(solve),
+        (_, "/generate" | "/solve") => Err((405, "expected a POST request".into())),
+        _ => Err((404, format!("no endpoint at {url}"))),
+    };
+    match result {
+        Ok(json) => (200, json),
+        Err((status, error)) => failure(status, error),
+    }
+}
+
+fn failure(status: u16, error: impl ToString) -> (u16, String) {
+    let error = ErrorResponse {
+        error: error.to_string(),
+    };
+    (
+        status,
+        serde_json::to_string(&error).expect("error serialization"),
+    )
+}
+
+fn parse<'d, T: Deserialize<'d>>(body: &'d str) -> Result<T, (u16, String)> {
+    serde_json::from_str(body).map_err(|error| (400, format!("invalid request: {error}")))
+}
+
+fn too_large() -> (u16, String) {
+    (400, format!("mazes are limited to {MAX_CELLS} cells"))
+}
+
+/// Check that a maze received from a client is small enough and consistent (c.f. [`Maze::validate`]).
+fn check_maze(maze: &Maze) -> Result<(), (u16, String)> {
+    if maze.width().saturating_mul(maze.height()) > MAX_CELLS {
+        return Err(too_large());
+    }
+    if maze.portals().nth(MAX_PORTALS).is_some() {
+        return Err((400, format!("mazes are limited to {MAX_PORTALS} portals")));
+    }
+    maze.validate()
+        .check()
+        .map_err(|error| (400, format!("invalid maze: {error}")))
+}
+
+fn generate(request: GenerateRequest) -> Result<String, (u16, String)> {
+    let GenerateRequest {
+        width,
+        height,
+        generator,
+        seed,
+    } = request;
+    let (_, generate) = GENERATORS
+        .iter()
+        .find(|(name, _)| *name == generator)
+        .ok_or_else(|| (400, format!("unknown generator \"{generator}\"")))?;
+    if width.saturating_mul(height) > MAX_CELLS {
+        return Err(too_large());
+    }
+    let maze = generate(width, height, seed).map_err(|error| (400, error.to_string()))?;
+    Ok(serde_json::to_string(&maze).expect("maze serialization"))
+}
+
+fn solve(request: SolveRequest) -> Result<String, (u16, String)> {
+    let SolveRequest {
+        maze,
+        algorithm,
+        max_ticks,
+    } = request;
+    check_maze(&maze)?;
+    let algorithm =
+        implementations::by_name(&algorithm).map_err(|error| (400, error.to_string()))?;
+    let report = Executor::build_dyn(algorithm, |b| {
+        b.maze(maze.clone())
+            .renderer(Renderer::Headless)
+            .delay_ms(0)
+            .max_ticks(max_ticks.unwrap_or(DEFAULT_MAX_TICKS).min(MAX_TICKS))
+            .metrics(true)
+    })
+    .and_then(|mut executor| executor.run())
+    .map_err(|error| (422, error.to_string()))?;
+    Ok(serde_json::to_string(&report).expect("report serialization"))
+}
+
+#[test]
+fn endpoints() {
+    let body = r#"{ "width": 8, "height": 5, "seed": 3 }"#;
+    let (status, maze) = handle(&Method::Post, "/generate", body);
+    assert_eq!(status, 200);
+    assert_eq!(handle(&Method::Post, "/generate", body).1, maze);
+
+    let body = format!(r#"{{ "maze": {maze}, "algorithm": "breath-first" }}"#);
+    let (status, report) = handle(&Method::Post, "/solve", &body);
+    assert_eq!(status, 200);
+    let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+    assert!(report["ticks"].as_u64().unwrap() > 0);
+    assert!(!report["path"].as_array().unwrap().is_empty());
+
+    let body = format!(r#"{{ "maze": {maze}, "algorithm": "nope" }}"#);
+    assert_eq!(handle(&Method::Post, "/solve", &body).0, 400);
+    assert_eq!(handle(&Method::Get, "/solve", "").0, 405);
+    assert_eq!(handle(&Method::Post, "/nope", "").0, 404);
+    assert_eq!(handle(&Method::Post, "/generate", "{").0, 400);
+
+    // a passage leading out of the maze
+    let mut json: serde_json::Value = serde_json::from_str(&maze).unwrap();
+    json["cells"]["cells"][0] = serde_json::json!(1 | 2 | 4 | 8);
+    let body = format!(r#"{{ "maze": {json}, "algorithm": "breath-first" }}"#);
+    let (status, error) = handle(&Method::Post, "/solve", &body);
+    assert_eq!(status, 400);
+    assert!(error.contains("out of bounds"));
+}
+
+#[test]
+fn streaming() {
+    let (_, maze) = handle(&Method::Post, "/generate", r#"{ "width": 8, "height": 5 }"#);
+    let body = format!(r#"{{ "maze": {maze}, "algorithm": "depth-first" }}"#);
+    let mut messages = Vec::new();
+    let result = stream_messages::<()>(parse(&body).unwrap(), |message| {
+        messages.push(serde_json::to_value(message).unwrap());
+        Ok(())
+    });
+    assert!(result.is_ok());
+    let (last, frames) = messages.split_last().unwrap();
+    assert_eq!(last["type"], "finished");
+    assert_eq!(last["ticks"].as_u64().unwrap() as usize, frames.len());
+    for (tick, frame) in frames.iter().enumerate() {
+        assert_eq!(frame["type"], "frame");
+        assert_eq!(frame["tick"].as_u64().unwrap() as usize, tick);
+    }
+
+    let body = format!(r#"{{ "maze": {maze}, "algorithm": "depth-first", "max_ticks": 1 }}"#);
+    let mut messages = Vec::new();
+    let _ = stream_messages::<()>(parse(&body).unwrap(), |message| {
+        messages.push(serde_json::to_value(message).unwrap());
+        Ok(())
+    });
+    assert_eq!(messages.last().unwrap()["type"], "error");
+}
+
+#[test]
+fn stream_limits() {
+    use std::net::{TcpListener, TcpStream};
+
+    let slots: Vec<_> = (0..MAX_STREAMS)
+        .map_while(|_| StreamSlot::acquire())
+        .collect();
+    assert_eq!(slots.len(), MAX_STREAMS);
+    assert!(StreamSlot::acquire().is_none());
+    drop(slots);
+    assert!(StreamSlot::acquire().is_some());
+
+    // a client upgrading to a WebSocket but never sending its request
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let _client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (server, _) = listener.accept().unwrap();
+    let socket = WebSocket::from_raw_socket(server, Role::Server, None);
+    let error = respond_stream(socket, Duration::from_millis(50)).unwrap_err();
+    assert!(error.starts_with("no request received"));
+}
@@ -0,0 +1,185 @@
+//! # Labirust Godot
+//!
+//! This crate contains a Godot 4 extension exposing labirust to scripts through two classes:
+//! [`LabirustMaze`], generating mazes, and [`LabirustSolver`], solving them one tick at a time so that a scene can animate the resolution.
+//! Positions are exchanged as [`Vector2i`]s.
+
+use godot::prelude::*;
+use labirust::{implementations::by_name, Execution, Maze, MazeGenerator, Pos, SimpleGenerator};
+
+struct LabirustExtension;
+
+#[gdextension]
+unsafe impl ExtensionLibrary for LabirustExtension {}
+
+fn to_vector(position: Pos) -> Vector2i {
+    Vector2i::new(position.x() as i32, position.y() as i32)
+}
+
+fn to_pos(vector: Vector2i) -> Pos {
+    Pos::new(vector.x as isize, vector.y as isize)
+}
+
+fn to_array(positions: impl IntoIterator<Item = Pos>) -> Array<Vector2i> {
+    let mut result = Array::new();
+    for position in positions {
+        result.push(to_vector(position));
+    }
+    result
+}
+
+/// A maze, created by the static `generate` functions.
+#[derive(GodotClass)]
+#[class(no_init, base = RefCounted)]
+pub struct LabirustMaze {
+    maze: Maze,
+}
+
+#[godot_api]
+impl LabirustMaze {
+    /// Generate a maze of `width` by `height` cells, returns null if the size is not positive.
+    #[func]
+    fn generate(width: i64, height: i64) -> Option<Gd<Self>> {
+        Self::from_result(SimpleGenerator::new(width as isize, height as isize).generate())
+    }
+
+    /// Generate a maze of `width` by `height` cells, always the same for a given `seed`.
+    #[func]
+    fn generate_seeded(width: i64, height: i64, seed: i64) -> Option<Gd<Self>> {
+        let mut generator = SimpleGenerator::new(width as isize, height as isize);
+        Self::from_result(generator.generate_seeded(seed as u64))
+    }
+
+    #[func]
+    fn get_width(&self) -> i64 {
+        self.maze.width() as i64
+    }
+
+    #[func]
+    fn get_height(&self) -> i64 {
+        self.maze.height() as i64
+    }
+
+    #[func]
+    fn get_start(&self) -> Vector2i {
+        to_vector(self.maze.start())
+    }
+
+    #[func]
+    fn get_end(&self) -> Vector2i {
+        to_vector(self.maze.end())
+    }
+
+    /// Whether a wall separates two positions, positions out of the maze always being walled.
+    #[func]
+    fn is_walled(&self, a: Vector2i, b: Vector2i) -> bool {
+        let (a, b) = (to_pos(a), to_pos(b));
+        !self.maze.is_inside(a) || !self.maze.is_inside(b) || self.maze.is_walled(a, b)
+    }
+
+    /// Positions directly accessible from `position`, empty if it is out of the maze.
+    #[func]
+    fn paths_from(&self, position: Vector2i) -> Array<Vector2i> {
+        let position = to_pos(position);
+        match self.maze.is_inside(position) {
+            true => to_array(self.maze.paths_from_iter(position)),
+            false => Array::new(),
+        }
+    }
+
+    fn from_result<E: std::fmt::Display>(result: Result<Maze, E>) -> Option<Gd<Self>> {
+        match result {
+            Ok(maze) => Some(Gd::from_object(Self { maze })),
+            Err(error) => {
+                godot_error!("labirust: {error}");
+                None
+            }
+        }
+    }
+}
+
+/// The step-wise resolution of a maze by an algorithm, created by the static `solve` function.
+#[derive(GodotClass)]
+#[class(no_init, base = RefCounted)]
+pub struct LabirustSolver {
+    execution: Execution,
+    failed: bool,
+    base: Base<RefCounted>,
+}
+
+#[godot_api]
+impl LabirustSolver {
+    /// Emitted when the end of the maze is reached.
+    #[signal]
+    fn finished();
+
+    /// Emitted when the algorithm gives up or makes an invalid guess.
+    #[signal]
+    fn failed(message: GString);
+
+    /// Start the resolution of `maze` by the algorithm named `algorithm`, returns null if the name is unknown.
+    #[func]
+    fn solve(maze: Gd<LabirustMaze>, algorithm: GString) -> Option<Gd<Self>> {
+        let algorithm = match by_name(&algorithm.to_string()) {
+            Ok(algorithm) => algorithm,
+            Err(error) => {
+                godot_error!("labirust: {error}");
+                return None;
+            }
+        };
+        let maze = maze.bind().maze.clone();
+        Some(Gd::from_init_fn(|base| Self {
+            execution: Execution::new(maze, algorithm),
+            failed: false,
+            base,
+        }))
+    }
+
+    /// Progress by one tick, returns whether the resolution is over, the end being reached or the algorithm having failed.
+    #[func]
+    fn step(&mut self) -> bool {
+        if self.failed || self.execution.is_finished() {
+            return true;
+        }
+        match self.execution.step() {
+            Ok(true) => {
+                self.base_mut().emit_signal("finished".into(), &[]);
+                true
+            }
+            Ok(false) => false,
+            Err(error) => {
+                self.failed = true;
+                let message = GString::from(error.to_string()).to_variant();
+                self.base_mut().emit_signal("failed".into(), &[message]);
+                true
+            }
+        }
+    }
+
+    #[func]
+    fn get_tick(&self) -> i64 {
+        self.execution.tick() as i64
+    }
+
+    /// The last path guessed by the algorithm.
+    #[func]
+    fn get_path(&self) -> Array<Vector2i> {
+        to_array(self.execution.path().iter().copied())
+    }
+
+    /// Positions that appeared in a guess for the first time on the last tick.
+    #[func]
+    fn get_fresh(&self) -> Array<Vector2i> {
+        to_array(self.execution.fresh().iter().copied())
+    }
+
+    #[func]
+    fn is_finished(&self) -> bool {
+        self.execution.is_finished()
+    }
+
+    #[func]
+    fn has_failed(&self) -> bool {
+        self.failed
+    }
+}
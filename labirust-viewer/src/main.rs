@@ -0,0 +1,232 @@
+use std::{path::PathBuf, thread::JoinHandle};
+
+use eframe::egui;
+use labirust::{
+    implementations, AldousBroderGenerator, CellSelection, GrowingTreeGenerator,
+    HuntAndKillGenerator, ImperfectGenerator, KruskalGenerator, Maze, MazeGenerator,
+    SimpleGenerator, WilsonGenerator,
+};
+
+use replay::Replay;
+
+mod replay;
+
+/// Generate a maze of a width and a height from a seed.
+type Generate = fn(isize, isize, u64) -> labirust::Result<Maze>;
+
+/// Generators selectable in the viewer, by name.
+const GENERATORS: &[(&str, Generate)] = &[
+    ("simple", |width, height, seed| {
+        Ok(SimpleGenerator::new(width, height).generate_seeded(seed)?)
+    }),
+    ("kruskal", |width, height, seed| {
+        Ok(KruskalGenerator::new(width, height).generate_seeded(seed)?)
+    }),
+    ("wilson", |width, height, seed| {
+        Ok(WilsonGenerator::new(width, height).generate_seeded(seed)?)
+    }),
+    ("aldous-broder", |width, height, seed| {
+        Ok(AldousBroderGenerator::new(width, height).generate_seeded(seed)?)
+    }),
+    ("hunt-and-kill", |width, height, seed| {
+        Ok(HuntAndKillGenerator::new(width, height).generate_seeded(seed)?)
+    }),
+    ("growing-tree", |width, height, seed| {
+        let selection = CellSelection::Mixed { newest: 50 };
+        Ok(GrowingTreeGenerator::new(width, height, selection).generate_seeded(seed)?)
+    }),
+    ("imperfect", |width, height, seed| {
+        Ok(ImperfectGenerator::new(width, height, 0.1).generate_seeded(seed)?)
+    }),
+];
+
+struct Viewer {
+    generator: usize,
+    algorithm: usize,
+    width: isize,
+    height: isize,
+    seed: u64,
+    replay: Option<Replay>,
+    recording: Option<JoinHandle<labirust::Result<Replay>>>,
+    tick: usize,
+    playing: bool,
+    export_path: String,
+    status: String,
+}
+
+impl Default for Viewer {
+    fn default() -> Self {
+        Self {
+            generator: 0,
+            algorithm: 0,
+            width: 40,
+            height: 20,
+            seed: 0,
+            replay: None,
+            recording: None,
+            tick: 0,
+            playing: false,
+            export_path: "maze.png".into(),
+            status: String::new(),
+        }
+    }
+}
+
+impl Viewer {
+    /// Generate a maze and record its resolution with the selected generator and algorithm, on a background thread.
+    fn record(&mut self) {
+        let (_, generate) = GENERATORS[self.generator];
+        let entry = &implementations::registry()[self.algorithm];
+        let (width, height, seed) = (self.width, self.height, self.seed);
+        self.recording = Some(std::thread::spawn(move || {
+            generate(width, height, seed).and_then(|maze| Replay::record(maze, entry.create()))
+        }));
+        self.status = "recording...".into();
+    }
+
+    /// Show the recorded replay once the background recording is over.
+    fn poll_recording(&mut self, ctx: &egui::Context) {
+        let Some(recording) = self.recording.take_if(|recording| recording.is_finished()) else {
+            if self.recording.is_some() {
+                ctx.request_repaint();
+            }
+            return;
+        };
+        let replay = recording.join().expect("the recording thread panicked");
+        match replay {
+            Ok(replay) => {
+                self.status = format!("solved in {} ticks", replay.len());
+                self.replay = Some(replay);
+                self.tick = 0;
+                self.playing = true;
+            }
+            Err(error) => {
+                self.status = format!("error: {error}");
+                self.replay = None;
+            }
+        }
+    }
+
+    fn export(&mut self) {
+        let Some(replay) = &self.replay else {
+            return;
+        };
+        let path = PathBuf::from(&self.export_path);
+        self.status = match replay.export(self.tick, 8, &path) {
+            Ok(()) => format!("exported to {}", path.display()),
+            Err(error) => format!("error: {error}"),
+        };
+    }
+
+    fn settings(&mut self, ui: &mut egui::Ui) {
+        let registry = implementations::registry();
+        egui::ComboBox::from_label("generator")
+            .selected_text(GENERATORS[self.generator].0)
+            .show_ui(ui, |ui| {
+                for (index, (name, _)) in GENERATORS.iter().enumerate() {
+                    ui.selectable_value(&mut self.generator, index, *name);
+                }
+            });
+        ui.add(
+            egui::DragValue::new(&mut self.width)
+                .range(1..=500)
+                .prefix("width "),
+        );
+        ui.add(
+            egui::DragValue::new(&mut self.height)
+                .range(1..=500)
+                .prefix("height "),
+        );
+        ui.add(egui::DragValue::new(&mut self.seed).prefix("seed "));
+        egui::ComboBox::from_label("algorithm")
+            .selected_text(registry[self.algorithm].name())
+            .show_ui(ui, |ui| {
+                for (index, entry) in registry.iter().enumerate() {
+                    ui.selectable_value(&mut self.algorithm, index, entry.name())
+                        .on_hover_text(entry.description());
+                }
+            });
+        let idle = self.recording.is_none();
+        if ui
+            .add_enabled(idle, egui::Button::new("Generate and solve"))
+            .clicked()
+        {
+            self.record();
+        }
+
+        ui.separator();
+        ui.text_edit_singleline(&mut self.export_path);
+        if ui
+            .add_enabled(self.replay.is_some(), egui::Button::new("Export PNG"))
+            .clicked()
+        {
+            self.export();
+        }
+        ui.label(&self.status);
+    }
+
+    fn timeline(&mut self, ui: &mut egui::Ui) {
+        let Some(replay) = &self.replay else {
+            return;
+        };
+        let last = replay.len() - 1;
+        ui.horizontal(|ui| {
+            let label = if self.playing { "Pause" } else { "Play" };
+            if ui.button(label).clicked() {
+                self.playing = !self.playing;
+            }
+            ui.spacing_mut().slider_width = ui.available_width() - 80.;
+            if ui
+                .add(egui::Slider::new(&mut self.tick, 0..=last).text("tick"))
+                .changed()
+            {
+                self.playing = false;
+            }
+        });
+        if self.playing {
+            self.tick = (self.tick + 1).min(last);
+            self.playing = self.tick < last;
+            ui.ctx().request_repaint();
+        }
+    }
+
+    fn maze(&self, ui: &mut egui::Ui) {
+        let Some(replay) = &self.replay else {
+            ui.centered_and_justified(|ui| ui.label("Generate a maze to start."));
+            return;
+        };
+        let (columns, rows) = replay.tiles();
+        let available = ui.available_size();
+        let size = (available.x / columns as f32).min(available.y / rows as f32);
+        let (response, painter) = ui.allocate_painter(
+            egui::vec2(size * columns as f32, size * rows as f32),
+            egui::Sense::hover(),
+        );
+        let origin = response.rect.min;
+        for (index, [r, g, b]) in replay.frame(self.tick).into_iter().enumerate() {
+            let (x, y) = ((index % columns) as f32, (index / columns) as f32);
+            let rect = egui::Rect::from_min_size(
+                origin + egui::vec2(x * size, y * size),
+                egui::vec2(size, size),
+            );
+            painter.rect_filled(rect, 0., egui::Color32::from_rgb(r, g, b));
+        }
+    }
+}
+
+impl eframe::App for Viewer {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.poll_recording(ctx);
+        egui::SidePanel::left("settings").show(ctx, |ui| self.settings(ui));
+        egui::TopBottomPanel::bottom("timeline").show(ctx, |ui| self.timeline(ui));
+        egui::CentralPanel::default().show(ctx, |ui| self.maze(ui));
+    }
+}
+
+fn main() -> eframe::Result {
+    eframe::run_native(
+        "labirust viewer",
+        eframe::NativeOptions::default(),
+        Box::new(|_| Ok(Box::<Viewer>::default())),
+    )
+}
@@ -0,0 +1,137 @@
+//! ## Replay
+//!
+//! This module contains the [`Replay`] of a resolution, recorded tick by tick to be scrubbed through,
+//! and its rasterization into a map of colored tiles laid out like the characters of [`Maze::display`].
+
+use std::path::Path;
+
+use image::RgbImage;
+use labirust::{Algorithm, Error, Execution, Maze, Pos, Result, TileKind, TraceFrame, VisitedSet};
+
+/// Number of ticks after which a recording is abandoned.
+const MAX_TICKS: usize = 100_000;
+
+pub type Rgb = [u8; 3];
+
+const WALL: Rgb = [26, 26, 26];
+const FREE: Rgb = [230, 230, 230];
+const START: Rgb = [51, 204, 51];
+const END: Rgb = [204, 51, 51];
+const PATH: Rgb = [230, 153, 26];
+const TRIED: Rgb = [102, 128, 179];
+
+/// A resolution of a [`Maze`], recorded to completion.
+pub struct Replay {
+    maze: Maze,
+    frames: Vec<TraceFrame>,
+}
+
+impl Replay {
+    /// Run `algorithm` on `maze` until it reaches the end, recording every tick.
+    /// Fails without running it if the maze is not laid out on a map of tiles (c.f. [`Maze::try_tiles`]).
+    pub fn record(maze: Maze, algorithm: Box<dyn Algorithm>) -> Result<Self> {
+        maze.try_tiles()?;
+        let mut execution = Execution::new(maze, algorithm);
+        let mut frames = Vec::new();
+        loop {
+            if frames.len() >= MAX_TICKS {
+                return Err(Error::Timeout { ticks: MAX_TICKS });
+            }
+            let finished = execution.step()?;
+            frames.extend(execution.frame());
+            if finished {
+                break;
+            }
+        }
+        let maze = execution.maze().clone();
+        Ok(Self { maze, frames })
+    }
+
+    /// Number of recorded ticks, the last one reaching the end of the maze.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Number of tiles of a frame, horizontally and vertically.
+    pub fn tiles(&self) -> (usize, usize) {
+        let (columns, rows) = self.maze.tiles();
+        (columns as usize, rows as usize)
+    }
+
+    /// Colors of the tiles at the end of `tick`, row by row.
+    pub fn frame(&self, tick: usize) -> Vec<Rgb> {
+        let Self { maze, frames } = self;
+        let mut tried = VisitedSet::new(maze.width(), maze.height());
+        for position in frames[..=tick].iter().flat_map(TraceFrame::fresh) {
+            tried.insert(*position);
+        }
+        let path = frames[tick].guess();
+        let on_path = |position: &Pos| path.contains(position);
+        let is_tried = |position: &Pos| tried.contains(*position);
+
+        let (columns, rows) = self.tiles();
+        let mut colors = Vec::with_capacity(columns * rows);
+        for y in 0..rows as isize {
+            for x in 0..columns as isize {
+                let kind = TileKind::of(maze, Pos::new(x, y));
+                let color = match kind {
+                    TileKind::Wall => WALL,
+                    TileKind::Cell(cell) if maze.is_start(cell) => START,
+                    TileKind::Cell(cell) if maze.is_end(cell) => END,
+                    _ if kind.cells().all(|cell| on_path(&cell)) => PATH,
+                    _ if kind.cells().all(|cell| is_tried(&cell)) => TRIED,
+                    _ => FREE,
+                };
+                colors.push(color);
+            }
+        }
+        colors
+    }
+
+    /// Write the frame at the end of `tick` to an image at `path`, in the format given by its extension such as PNG,
+    /// each tile taking `scale` by `scale` pixels.
+    pub fn export(&self, tick: usize, scale: usize, path: &Path) -> Result<()> {
+        let (columns, rows) = self.tiles();
+        let frame = self.frame(tick);
+        let (width, height) = ((columns * scale) as u32, (rows * scale) as u32);
+        let image = RgbImage::from_fn(width, height, |x, y| {
+            let (x, y) = (x as usize / scale, y as usize / scale);
+            image::Rgb(frame[y * columns + x])
+        });
+        image.save(path)?;
+        Ok(())
+    }
+}
+
+#[test]
+fn replay() {
+    use labirust::{implementations::BreathFirst, MazeGenerator, SimpleGenerator};
+
+    let maze = SimpleGenerator::new(6, 4).generate().unwrap();
+    let replay = Replay::record(maze.clone(), BreathFirst::new().boxed()).unwrap();
+    let (columns, rows) = replay.tiles();
+    let walls = maze
+        .display(None)
+        .chars()
+        .filter(|&c| c != ' ' && c != '\n')
+        .count();
+
+    let first = replay.frame(0);
+    assert_eq!(first.len(), columns * rows);
+    assert_eq!(first.iter().filter(|&&c| c == WALL).count(), walls);
+
+    // the last frame shows the path from the start to the end
+    let last = replay.frame(replay.len() - 1);
+    let path = last.iter().filter(|&&c| c == PATH).count();
+    let expected = maze
+        .distance_field(maze.start())
+        .distance(maze.end())
+        .unwrap();
+    assert_eq!(path, 2 * expected - 1);
+
+    let file = std::env::temp_dir().join("labirust-viewer-replay.png");
+    replay.export(replay.len() - 1, 2, &file).unwrap();
+    let data = std::fs::read(&file).unwrap();
+    assert!(data.starts_with(b"\x89PNG"));
+    std::fs::remove_file(file).unwrap();
+}
@@ -4,6 +4,8 @@
 //! Already existing implementations of that trait can be found in the [`crate::implementations`] module.
 //!
 
+use alloc::boxed::Box;
+
 use crate::{Context, Guess, Insight};
 
 /// Trait encapsulating the behavior of an algorithm solving mazes.
@@ -13,4 +15,51 @@ pub trait Algorithm {
     /// `insight` is a view on the position discovered on the previous movement.
     /// `ctx` is a view on the [`Maze`], useful for accessing properties of the maze.
     fn progress(&mut self, insight: &Insight, ctx: &mut Context) -> Guess;
+
+    /// Move the algorithm in a [`Box`], to be used as a trait object.
+    fn boxed(self) -> Box<dyn Algorithm>
+    where
+        Self: Sized + 'static,
+    {
+        Box::new(self)
+    }
+}
+
+impl<A: Algorithm + ?Sized> Algorithm for Box<A> {
+    fn progress(&mut self, insight: &Insight, ctx: &mut Context) -> Guess {
+        A::progress(self, insight, ctx)
+    }
+}
+
+/// An [`Algorithm`] that can be duplicated behind a trait object, implemented for every [`Clone`] algorithm.
+/// Allows cloning a `Box<dyn CloneableAlgorithm>` to get a fresh copy for each [`Maze`].
+pub trait CloneableAlgorithm: Algorithm {
+    /// Clone the algorithm in a new [`Box`].
+    fn clone_boxed(&self) -> Box<dyn CloneableAlgorithm>;
+}
+
+impl<A: Algorithm + Clone + 'static> CloneableAlgorithm for A {
+    fn clone_boxed(&self) -> Box<dyn CloneableAlgorithm> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn CloneableAlgorithm> {
+    fn clone(&self) -> Self {
+        (**self).clone_boxed()
+    }
+}
+
+#[cfg(all(feature = "std", feature = "generate"))]
+#[test]
+fn clone_boxed() {
+    use crate::{executor::drive, implementations::DepthFirst, MazeGenerator, SimpleGenerator};
+
+    let maze = SimpleGenerator::new(10, 10).generate().unwrap();
+    let algorithm: Box<dyn CloneableAlgorithm> = Box::new(DepthFirst::new());
+    let ticks = drive(&maze, &mut algorithm.clone(), Default::default(), |_, _| ()).unwrap();
+    assert_eq!(
+        drive(&maze, &mut algorithm.clone(), Default::default(), |_, _| ()).unwrap(),
+        ticks
+    );
 }
@@ -0,0 +1,317 @@
+//! ## Bench
+//!
+//! This module contains functions measuring the time taken by [`MazeGenerator`]s and [`Algorithm`]s,
+//! returning structured results shared by the CLI and downstream users,
+//! and comparing the grid of cells storing the walls of a [`Maze`] with the `HashMap` adjacency map it replaced.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    time::{Duration, Instant},
+};
+
+use crate::{executor::drive, Algorithm, Error, Grid, Maze, MazeGenerator, Pos, Result};
+
+/// Timing statistics over several iterations of a measured operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timing {
+    iterations: usize,
+    mean: Duration,
+    p95: Duration,
+    min: Duration,
+    max: Duration,
+}
+
+impl Timing {
+    /// Computes statistics from the duration of each iteration, fails with [`Error::InvalidConfig`] if there are none.
+    fn from_samples(mut samples: Vec<Duration>) -> Result<Self> {
+        if samples.is_empty() {
+            return Err(Error::InvalidConfig("no iteration measured"));
+        }
+        samples.sort();
+        let iterations = samples.len();
+        let total: Duration = samples.iter().sum();
+        let p95_index = ((iterations * 95).div_ceil(100)).saturating_sub(1);
+        Ok(Self {
+            iterations,
+            mean: total / iterations as u32,
+            p95: samples[p95_index],
+            min: samples[0],
+            max: samples[iterations - 1],
+        })
+    }
+
+    /// Number of measured iterations.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Mean duration of an iteration.
+    pub fn mean(&self) -> Duration {
+        self.mean
+    }
+
+    /// Duration under which 95% of the iterations completed.
+    pub fn p95(&self) -> Duration {
+        self.p95
+    }
+
+    /// Duration of the fastest iteration.
+    pub fn min(&self) -> Duration {
+        self.min
+    }
+
+    /// Duration of the slowest iteration.
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+}
+
+/// Measures of a [`MazeGenerator`] on mazes of a given size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeneratorBench {
+    size: (isize, isize),
+    timing: Timing,
+}
+
+impl GeneratorBench {
+    /// Size of the generated mazes.
+    pub fn size(&self) -> (isize, isize) {
+        self.size
+    }
+
+    /// Time taken to generate a maze.
+    pub fn timing(&self) -> Timing {
+        self.timing
+    }
+}
+
+/// Measures of an [`Algorithm`] solving a given [`Maze`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlgorithmBench {
+    size: (isize, isize),
+    ticks: usize,
+    timing: Timing,
+}
+
+impl AlgorithmBench {
+    /// Size of the solved maze.
+    pub fn size(&self) -> (isize, isize) {
+        self.size
+    }
+
+    /// Number of ticks the algorithm took to reach the end of the maze.
+    pub fn ticks(&self) -> usize {
+        self.ticks
+    }
+
+    /// Time taken to solve the maze.
+    pub fn timing(&self) -> Timing {
+        self.timing
+    }
+}
+
+/// Measures of breadth-first floods over the passages of a [`Maze`], read from its grid of cells and from an adjacency map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageBench {
+    size: (isize, isize),
+    reached: usize,
+    grid: Timing,
+    hash_map: Timing,
+}
+
+impl StorageBench {
+    /// Size of the flooded maze.
+    pub fn size(&self) -> (isize, isize) {
+        self.size
+    }
+
+    /// Number of cells reached by each flood from the start.
+    pub fn reached(&self) -> usize {
+        self.reached
+    }
+
+    /// Time taken to flood the maze through its grid of cells.
+    pub fn grid(&self) -> Timing {
+        self.grid
+    }
+
+    /// Time taken to flood the maze through a `HashMap<Pos, Vec<Pos>>` of the same passages.
+    pub fn hash_map(&self) -> Timing {
+        self.hash_map
+    }
+
+    /// How many times faster the flood through the grid is, comparing the mean durations.
+    pub fn speedup(&self) -> f64 {
+        self.hash_map.mean.as_secs_f64() / self.grid.mean.as_secs_f64().max(f64::MIN_POSITIVE)
+    }
+}
+
+/// Measures `iterations` generations for each of the `sizes`, `generator` being called to create a [`MazeGenerator`] of each size.
+/// Fails as soon as a generation fails, or with [`Error::InvalidConfig`] if `iterations` is 0.
+pub fn bench_generator<G, F>(
+    generator: F,
+    sizes: &[(isize, isize)],
+    iterations: usize,
+) -> Result<Vec<GeneratorBench>>
+where
+    G: MazeGenerator,
+    F: Fn(isize, isize) -> G,
+{
+    sizes
+        .iter()
+        .map(|&(width, height)| {
+            let mut generator = generator(width, height);
+            let samples = (0..iterations)
+                .map(|_| {
+                    let begin = Instant::now();
+                    let maze = generator.generate()?;
+                    let elapsed = begin.elapsed();
+                    drop(maze);
+                    Ok(elapsed)
+                })
+                .collect::<Result<_>>()?;
+            Ok(GeneratorBench {
+                size: (width, height),
+                timing: Timing::from_samples(samples)?,
+            })
+        })
+        .collect()
+}
+
+/// Measures `iterations` resolutions of each of the `mazes`, without any rendering nor delay.
+/// `algorithm` is called to create a fresh [`Algorithm`] for each resolution.
+/// Fails as soon as a resolution fails, or with [`Error::InvalidConfig`] if `iterations` is 0.
+pub fn bench_algorithm<A, F>(
+    algorithm: F,
+    mazes: &[Maze],
+    iterations: usize,
+) -> Result<Vec<AlgorithmBench>>
+where
+    A: Algorithm,
+    F: Fn() -> A,
+{
+    mazes
+        .iter()
+        .map(|maze| {
+            let mut ticks = 0;
+            let samples = (0..iterations)
+                .map(|_| {
+                    let mut algorithm = algorithm();
+                    let begin = Instant::now();
+                    ticks = drive(maze, &mut algorithm, Default::default(), |_, _| ())?;
+                    Ok(begin.elapsed())
+                })
+                .collect::<Result<_>>()?;
+            Ok(AlgorithmBench {
+                size: maze.size(),
+                ticks,
+                timing: Timing::from_samples(samples)?,
+            })
+        })
+        .collect()
+}
+
+/// Measures `iterations` breadth-first floods from the start of each of the `mazes`,
+/// once through its grid of cells and once through an adjacency map holding the same passages, built beforehand.
+/// Fails with [`Error::InvalidConfig`] if `iterations` is 0.
+pub fn bench_storage(mazes: &[Maze], iterations: usize) -> Result<Vec<StorageBench>> {
+    mazes
+        .iter()
+        .map(|maze| {
+            let adjacency: HashMap<Pos, Vec<Pos>> = maze
+                .iter()
+                .map(|(position, _)| (position, maze.paths_from_iter(position).collect()))
+                .collect();
+            let mut reached = 0;
+            let mut measure = |flood: &dyn Fn() -> usize| {
+                let samples = (0..iterations)
+                    .map(|_| {
+                        let begin = Instant::now();
+                        reached = flood();
+                        begin.elapsed()
+                    })
+                    .collect();
+                Timing::from_samples(samples)
+            };
+            let grid = measure(&|| flood_grid(maze))?;
+            let hash_map = measure(&|| flood_hash_map(&adjacency, maze.start()))?;
+            Ok(StorageBench {
+                size: maze.size(),
+                reached,
+                grid,
+                hash_map,
+            })
+        })
+        .collect()
+}
+
+/// Number of cells reached by a breadth-first flood from the start of `maze`, through its grid of cells.
+fn flood_grid(maze: &Maze) -> usize {
+    let mut visited = Grid::new(maze.width(), maze.height(), false);
+    let mut queue = VecDeque::from([maze.start()]);
+    visited[maze.start()] = true;
+    let mut reached = 0;
+    while let Some(position) = queue.pop_front() {
+        reached += 1;
+        for next in maze.paths_from_iter(position) {
+            if !visited[next] {
+                visited[next] = true;
+                queue.push_back(next);
+            }
+        }
+    }
+    reached
+}
+
+/// Number of cells reached by a breadth-first flood from `start`, through an `adjacency` map.
+fn flood_hash_map(adjacency: &HashMap<Pos, Vec<Pos>>, start: Pos) -> usize {
+    let mut visited = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+    let mut reached = 0;
+    while let Some(position) = queue.pop_front() {
+        reached += 1;
+        for &next in adjacency.get(&position).into_iter().flatten() {
+            if visited.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    reached
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn bench() {
+    use crate::{implementations::DepthFirst, SimpleGenerator};
+
+    let generators = bench_generator(SimpleGenerator::new, &[(5, 5), (10, 10)], 3).unwrap();
+    assert_eq!(generators.len(), 2);
+    assert_eq!(generators[1].size(), (10, 10));
+    assert_eq!(generators[1].timing().iterations(), 3);
+
+    let mazes = [SimpleGenerator::new(10, 10).generate().unwrap()];
+    let algorithms = bench_algorithm(DepthFirst::new, &mazes, 4).unwrap();
+    let timing = algorithms[0].timing();
+    assert!(algorithms[0].ticks() > 0);
+    assert!(timing.min() <= timing.mean() && timing.mean() <= timing.max());
+    assert!(timing.p95() <= timing.max());
+
+    let generators = bench_generator(SimpleGenerator::new, &[(5, 5)], 0);
+    assert!(matches!(generators, Err(Error::InvalidConfig(_))));
+    let algorithms = bench_algorithm(DepthFirst::new, &mazes, 0);
+    assert!(matches!(algorithms, Err(Error::InvalidConfig(_))));
+
+    // both storages reach every cell of a perfect maze
+    let storage = bench_storage(&mazes, 2).unwrap();
+    assert_eq!(storage[0].reached(), 100);
+    assert_eq!(storage[0].grid().iterations(), 2);
+    assert!(storage[0].speedup() > 0.0);
+    assert!(matches!(
+        bench_storage(&mazes, 0),
+        Err(Error::InvalidConfig(_))
+    ));
+}
@@ -0,0 +1,98 @@
+//! ## Direction
+//!
+//! This module contains the definition of the [`Direction`] type, used to represent orientations on the grid.
+
+use core::ops::{Add, Sub};
+
+use crate::Pos;
+
+/// One of the four cardinal directions of the 2D-grid, `y` growing towards the [`Direction::South`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Every direction, clockwise, starting from the [`Direction::North`].
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Unit vector pointing in this direction.
+    pub fn offset(self) -> Pos {
+        match self {
+            Direction::North => Pos::new(0, -1),
+            Direction::East => Pos::new(1, 0),
+            Direction::South => Pos::new(0, 1),
+            Direction::West => Pos::new(-1, 0),
+        }
+    }
+
+    /// Bit flag of the direction, used to store sets of directions in a single byte.
+    pub fn bit(self) -> u8 {
+        1 << (self as u8)
+    }
+
+    /// The direction pointing the other way.
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+
+    /// The direction a quarter turn to the right.
+    pub fn clockwise(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    /// The direction a quarter turn to the left.
+    pub fn counter_clockwise(self) -> Self {
+        self.clockwise().opposite()
+    }
+}
+
+impl Add<Direction> for Pos {
+    type Output = Pos;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        self + rhs.offset()
+    }
+}
+
+impl Sub<Direction> for Pos {
+    type Output = Pos;
+
+    fn sub(self, rhs: Direction) -> Self::Output {
+        self - rhs.offset()
+    }
+}
+
+#[test]
+fn arithmetic() {
+    use Direction::*;
+    let position = Pos::new(3, 3);
+    assert_eq!(position + North, Pos::new(3, 2));
+    assert_eq!(position - North, position + South);
+    assert_eq!(position + East.offset() * 2, Pos::new(5, 3));
+    for direction in Direction::ALL {
+        assert_eq!(position + direction + direction.opposite(), position);
+        assert_eq!(-direction.offset(), direction.opposite().offset());
+        assert_eq!(direction.clockwise().clockwise(), direction.opposite());
+        assert_eq!(direction.clockwise().counter_clockwise(), direction);
+    }
+}
@@ -0,0 +1,169 @@
+//! ## Error
+//!
+//! This module contains the [`Error`] type shared by the fallible operations of the crate, and the corresponding [`Result`] alias.
+
+use alloc::string::String;
+use core::fmt;
+
+use crate::{GenerationError, MazeError};
+
+/// Errors returned by the fallible operations of the crate.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// A [`crate::Maze`] could not be built from its description.
+    InvalidMaze(MazeError),
+    /// A [`crate::MazeGenerator`] failed to create a [`crate::Maze`].
+    Generation(GenerationError),
+    /// An [`crate::Algorithm`] returned a [`crate::Guess`] that can not be followed.
+    InvalidGuess { tick: usize, reason: &'static str },
+    /// The end of the [`crate::Maze`] can not be reached from its start.
+    Unsolvable,
+    /// The execution was stopped before reaching the end of the [`crate::Maze`].
+    Timeout { ticks: usize },
+    /// The configuration of an [`crate::Executor`] is inconsistent.
+    InvalidConfig(&'static str),
+    /// Reading or writing failed.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+    /// A textual description could not be parsed.
+    Parse { line: usize, message: String },
+    /// An image could not be loaded.
+    #[cfg(feature = "image")]
+    Image(image::ImageError),
+    /// A plugin could not be loaded.
+    #[cfg(feature = "plugins")]
+    Plugin(libloading::Error),
+}
+
+/// Result of the fallible operations of the crate.
+pub type Result<T> = core::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMaze(error) => write!(f, "invalid maze: {error}"),
+            Self::Generation(error) => write!(f, "generation failed: {error}"),
+            Self::InvalidGuess { tick, reason } => {
+                write!(f, "invalid guess at tick {tick}: {reason}")
+            }
+            Self::InvalidConfig(reason) => write!(f, "invalid configuration: {reason}"),
+            Self::Unsolvable => write!(f, "the end of the maze is unreachable"),
+            Self::Timeout { ticks } => write!(f, "no resolution after {ticks} ticks"),
+            #[cfg(feature = "std")]
+            Self::Io(error) => write!(f, "io error: {error}"),
+            Self::Parse { line, message } => write!(f, "parse error at line {line}: {message}"),
+            #[cfg(feature = "image")]
+            Self::Image(error) => write!(f, "image error: {error}"),
+            #[cfg(feature = "plugins")]
+            Self::Plugin(error) => write!(f, "plugin error: {error}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidMaze(error) => Some(error),
+            Self::Generation(error) => Some(error),
+            Self::Io(error) => Some(error),
+            #[cfg(feature = "image")]
+            Self::Image(error) => Some(error),
+            #[cfg(feature = "plugins")]
+            Self::Plugin(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<MazeError> for Error {
+    fn from(error: MazeError) -> Self {
+        Self::InvalidMaze(error)
+    }
+}
+
+impl From<GenerationError> for Error {
+    fn from(error: GenerationError) -> Self {
+        Self::Generation(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+#[cfg(feature = "image")]
+impl From<image::ImageError> for Error {
+    fn from(error: image::ImageError) -> Self {
+        Self::Image(error)
+    }
+}
+
+#[cfg(feature = "plugins")]
+impl From<libloading::Error> for Error {
+    fn from(error: libloading::Error) -> Self {
+        Self::Plugin(error)
+    }
+}
+
+#[test]
+fn errors() {
+    use alloc::string::ToString;
+
+    let error: Error = MazeError::InvalidSize {
+        width: 0,
+        height: 2,
+    }
+    .into();
+    assert!(matches!(
+        error,
+        Error::InvalidMaze(MazeError::InvalidSize { .. })
+    ));
+    assert_eq!(error.to_string(), "invalid maze: invalid maze size 0x2");
+    let error: Error = GenerationError::Unseedable.into();
+    assert!(matches!(
+        error,
+        Error::Generation(GenerationError::Unseedable)
+    ));
+    assert_eq!(
+        error.to_string(),
+        "generation failed: the generator can not be seeded"
+    );
+
+    let error = Error::InvalidGuess {
+        tick: 3,
+        reason: "not a path",
+    };
+    assert_eq!(error.to_string(), "invalid guess at tick 3: not a path");
+    let error = Error::InvalidConfig("no algorithm");
+    assert_eq!(error.to_string(), "invalid configuration: no algorithm");
+    assert_eq!(
+        Error::Unsolvable.to_string(),
+        "the end of the maze is unreachable"
+    );
+    let error = Error::Timeout { ticks: 10 };
+    assert_eq!(error.to_string(), "no resolution after 10 ticks");
+    let error = Error::Parse {
+        line: 2,
+        message: "expected 7 characters, found 6".into(),
+    };
+    assert_eq!(
+        error.to_string(),
+        "parse error at line 2: expected 7 characters, found 6"
+    );
+
+    #[cfg(feature = "std")]
+    {
+        use std::error::Error as _;
+
+        let error: Error = std::io::Error::other("disk full").into();
+        assert!(matches!(error, Error::Io(_)));
+        assert_eq!(error.to_string(), "io error: disk full");
+        assert_eq!(error.source().unwrap().to_string(), "disk full");
+        assert!(Error::Unsolvable.source().is_none());
+    }
+}
@@ -0,0 +1,54 @@
+//! ## Evcxr
+//!
+//! This module contains the display hooks of the [evcxr](https://github.com/evcxr/evcxr) Jupyter kernel,
+//! showing [`Maze`]s and [`Execution`]s as inline SVG figures in notebooks (c.f. [`Maze::to_svg`]).
+//! The kernel calls the `evcxr_display` method of the last evaluated value when it has one.
+//! Mazes without SVG figures, such as hexagonal ones, are shown as text instead.
+
+use crate::{Execution, Maze, Pos, VisitedSet};
+
+impl Maze {
+    /// Print the [`Maze`] as an SVG figure for the evcxr kernel.
+    pub fn evcxr_display(&self) {
+        print!("{}", evcxr_figure(self, None, &[]));
+    }
+}
+
+impl Execution {
+    /// Print the state of the [`Execution`] as an SVG figure for the evcxr kernel, showing the tried positions and the last guess.
+    pub fn evcxr_display(&self) {
+        let figure = evcxr_figure(self.maze(), Some(self.tried()), self.path());
+        print!("{figure}");
+    }
+}
+
+/// Content shown by the evcxr kernel for `maze`, its SVG figure (c.f. [`Maze::try_to_svg`]) or its text representation.
+fn evcxr_figure(maze: &Maze, visited: Option<&VisitedSet>, path: &[Pos]) -> String {
+    match maze.try_to_svg(visited, path) {
+        Ok(figure) => evcxr_content("image/svg+xml", &figure),
+        Err(_) => evcxr_content("text/plain", &maze.display(None)),
+    }
+}
+
+/// Wrap `content` of the `mime` type in the markers expected by the evcxr kernel.
+fn evcxr_content(mime: &str, content: &str) -> String {
+    format!("EVCXR_BEGIN_CONTENT {mime}\n{content}\nEVCXR_END_CONTENT\n")
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn figures() {
+    use crate::{MazeGenerator, SimpleGenerator};
+
+    let maze = SimpleGenerator::new(7, 5).generate().unwrap();
+    let figure = evcxr_figure(&maze, None, &[]);
+    assert!(figure.starts_with("EVCXR_BEGIN_CONTENT image/svg+xml\n<svg"));
+    assert!(figure.ends_with("</svg>\nEVCXR_END_CONTENT\n"));
+
+    let maze = SimpleGenerator::new(7, 5)
+        .topology(crate::Topology::Hex)
+        .generate()
+        .unwrap();
+    let figure = evcxr_figure(&maze, None, &[]);
+    assert!(figure.starts_with("EVCXR_BEGIN_CONTENT text/plain\n"));
+}
@@ -1,39 +1,133 @@
 //! ## Executor
 //!
-//! This module contains the definition of an [`Executor`], used to run an [`Algorithm`] and have a graphical output in the terminal.
+//! This module contains the definition of an [`Executor`], used to run an [`Algorithm`] and, with the `render-terminal` feature, have a graphical output in the terminal.
 //! This type is supposed to be created using the builder pattern (c.f. [`Executor`]`::build`).
 
-use std::{
-    collections::{HashMap, HashSet},
-    thread,
-    time::Duration,
-};
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+#[cfg(feature = "render-terminal")]
+use std::{sync::mpsc, thread};
+
+use alloc::{boxed::Box, vec::Vec};
 
-use crate::{Algorithm, Maze, Pos};
+use crate::{
+    labyrinth::{lock::KeyId, Neighbors},
+    Algorithm, Cell, Error, Maze, MazeError, Pos, Result, Topology, VisitedSet,
+};
 
+#[cfg(feature = "std")]
 use self::builder::{
     maze_state::{BuildableMazeState, Unprovided},
     new_builder, DynExecutorBuilder, ExecutorBuilder,
 };
+#[cfg(feature = "render-terminal")]
+use self::terminal::TerminalRenderer;
+
+/// State of an execution at the end of a tick, sent to the rendering thread.
+#[cfg(feature = "render-terminal")]
+pub(crate) struct Frame {
+    tick: usize,
+    tried: VisitedSet,
+    path: Vec<Pos>,
+}
 
 /// A guess to pass to the current [`Executor`] at the end of every `progress` call.
-pub struct Guess(Vec<Pos>);
+/// Either a path to follow, or the admission that the [`Algorithm`] has no more positions to explore.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Guess {
+    path: Option<Vec<Pos>>,
+    anchor: Anchor,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "is_false"))]
+    unsupported: bool,
+}
+
+impl Guess {
+    /// The guessed path, from its [`Anchor`] to the position to discover next, empty if the [`Algorithm`] gave up.
+    pub fn path(&self) -> &[Pos] {
+        self.path.as_deref().unwrap_or_default()
+    }
+
+    /// The end of the [`Maze`] the path begins at.
+    pub fn anchor(&self) -> Anchor {
+        self.anchor
+    }
+
+    /// Check if the [`Algorithm`] gave up the resolution (c.f. [`Context::give_up`]).
+    pub fn is_give_up(&self) -> bool {
+        self.path.is_none()
+    }
+
+    /// Check if the [`Algorithm`] gave up because it does not support the [`Topology`] of the [`Maze`] (c.f. [`Context::unsupported`]).
+    pub fn is_unsupported(&self) -> bool {
+        self.unsupported
+    }
+}
+
+#[cfg(feature = "serde")]
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+/// The end of the [`Maze`] a [`Guess`] begins at.
+/// The resolution is over once a guess links the start to the end, whichever its anchor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Anchor {
+    /// The path begins at the start of the [`Maze`], like most guesses (c.f. [`Context::guess`]).
+    #[default]
+    Start,
+    /// The path begins at the end of the [`Maze`] (c.f. [`Context::guess_from_end`]).
+    End,
+}
 
 /// An insight given to the [`Algorithm`] on every `progress` call.
 /// On the first time about the starting point and every consecutive call about the tail of the previous guess.
-pub struct Insight<'p> {
+/// It owns its data, so algorithms can clone and keep insights across calls.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Insight {
     position: Pos,
-    paths: &'p [Pos],
+    paths: Neighbors,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    keys: Vec<KeyId>,
 }
 
-impl<'p> Insight<'p> {
-    fn new(position: Pos, paths: &'p [Pos]) -> Self {
-        Self { paths, position }
+impl Insight {
+    /// Constructor, mostly useful to test an [`Algorithm`] without an [`Executor`].
+    pub fn new<I>(position: Pos, paths: I) -> Self
+    where
+        I: IntoIterator<Item = Pos>,
+    {
+        let paths = paths.into_iter().collect();
+        let keys = Vec::new();
+        Self {
+            paths,
+            position,
+            keys,
+        }
+    }
+
+    /// Constructor for the insight an [`Executor`] would give about `position` in `maze`, without any key.
+    pub fn from_position(position: Pos, maze: &Maze) -> Self {
+        Self::from_path(&[position], maze)
     }
 
-    fn from_position(position: Pos, maze: &'p Maze) -> Self {
-        let paths = maze.paths_from(position);
-        Self::new(position, paths)
+    /// Constructor for the insight an [`Executor`] would give at the end of `path` in `maze`, with the keys collected along it.
+    /// Passages locked by a door the keys do not open are left out.
+    pub fn from_path(path: &[Pos], maze: &Maze) -> Self {
+        let position = *path.last().expect("non empty path");
+        let keys = maze.keys_along(path);
+        let mut paths = maze.paths_from(position);
+        paths.retain(|next| !maze.is_locked(position, *next, &keys));
+        Self {
+            paths,
+            position,
+            keys,
+        }
     }
 
     /// The position of the insight.
@@ -43,6 +137,16 @@ impl<'p> Insight<'p> {
 
     /// the paths from that position.
     pub fn paths(&self) -> &[Pos] {
+        &self.paths
+    }
+
+    /// Ids of the keys collected by the path leading to that position, sorted.
+    pub fn keys(&self) -> &[KeyId] {
+        &self.keys
+    }
+
+    /// Take the paths from that position, without copying them.
+    pub fn into_paths(self) -> Neighbors {
         self.paths
     }
 }
@@ -50,17 +154,72 @@ impl<'p> Insight<'p> {
 /// A context given to the [`Algorithm`] on every `progress` call, provide informations about the maze and method to create a [`Guess`].
 pub struct Context<'m> {
     maze: &'m Maze,
+    discovered: Option<&'m VisitedSet>,
 }
 
 impl<'m> Context<'m> {
-    fn new(maze: &'m Maze) -> Self {
-        Self { maze }
+    /// Constructor, mostly useful to test an [`Algorithm`] without an [`Executor`].
+    /// The whole [`Maze`] is visible, as with [`Visibility::Full`].
+    pub fn new(maze: &'m Maze) -> Self {
+        let discovered = None;
+        Self { maze, discovered }
+    }
+
+    /// Constructor only revealing the `discovered` positions of the [`Maze`], as with [`Visibility::Discovered`].
+    pub fn with_discovered(maze: &'m Maze, discovered: &'m VisitedSet) -> Self {
+        let discovered = Some(discovered);
+        Self { maze, discovered }
     }
 
     /// Constructor for [`Guess`].
     /// Takes a path, that is a vector of positions from the starting point to the position to discover on the next call to `progress`.
     pub fn guess(&self, pos: Vec<Pos>) -> Guess {
-        Guess(pos)
+        let path = Some(pos);
+        let anchor = Anchor::Start;
+        let unsupported = false;
+        Guess {
+            path,
+            anchor,
+            unsupported,
+        }
+    }
+
+    /// Constructor for a [`Guess`] anchored at the end of the [`Maze`] instead of its start.
+    /// Takes a path from the end to the position to discover on the next call to `progress`,
+    /// allowing algorithms to explore from both ends at once.
+    pub fn guess_from_end(&self, pos: Vec<Pos>) -> Guess {
+        let path = Some(pos);
+        let anchor = Anchor::End;
+        let unsupported = false;
+        Guess {
+            path,
+            anchor,
+            unsupported,
+        }
+    }
+
+    /// Constructor for a [`Guess`] giving up the resolution, when every reachable position has been explored without finding the end.
+    /// The execution then stops with [`Error::Unsolvable`].
+    pub fn give_up(&self) -> Guess {
+        let anchor = Anchor::Start;
+        let unsupported = false;
+        Guess {
+            path: None,
+            anchor,
+            unsupported,
+        }
+    }
+
+    /// Constructor for a [`Guess`] giving up the resolution up front, when the [`Algorithm`] does not support the [`Topology`] of the [`Maze`].
+    /// The execution then stops with [`Error::InvalidMaze`] holding [`MazeError::UnsupportedTopology`].
+    pub fn unsupported(&self) -> Guess {
+        let anchor = Anchor::Start;
+        let unsupported = true;
+        Guess {
+            path: None,
+            anchor,
+            unsupported,
+        }
     }
 
     /// Returns the position of the `start` of the [`Maze`].
@@ -68,11 +227,21 @@ impl<'m> Context<'m> {
         self.maze.start()
     }
 
-    /// Returns the position of the `end` of the [`Maze`].
+    /// Returns the position of the `end` of the [`Maze`], the first one when it has several.
     pub fn end(&self) -> Pos {
         self.maze.end()
     }
 
+    /// Iterates over the ending positions of the [`Maze`], reaching any of them completing the resolution (c.f. [`Maze::ends`]).
+    pub fn ends(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.maze.ends()
+    }
+
+    /// Check if `position` is one of the ends of the [`Maze`].
+    pub fn is_end(&self, position: Pos) -> bool {
+        self.maze.is_end(position)
+    }
+
     /// Returns the `width` of the [`Maze`].
     pub fn width(&self) -> isize {
         self.maze.width()
@@ -80,35 +249,534 @@ impl<'m> Context<'m> {
 
     /// Returns the `height` of the [`Maze`].
     pub fn height(&self) -> isize {
-        self.maze.width()
+        self.maze.height()
     }
 
     /// Returns a tuple containing both the `width` and `height` of the [`Maze`].
     pub fn size(&self) -> (isize, isize) {
         self.maze.size()
     }
+
+    /// Returns how the cells of the [`Maze`] neighbor each other.
+    pub fn topology(&self) -> Topology {
+        self.maze.topology()
+    }
+
+    /// Lowest number of steps from `position` to any of the ends allowed by the [`Topology`], c.f. [`Maze::min_distance_to_end`].
+    pub fn min_distance_to_end(&self, position: Pos) -> usize {
+        self.maze.min_distance_to_end(position)
+    }
+
+    /// Constructor for an empty [`VisitedSet`] sized to the [`Maze`].
+    pub fn new_visited_set(&self) -> VisitedSet {
+        VisitedSet::new(self.width(), self.height())
+    }
+
+    /// Check if the layout around a position is visible to the [`Algorithm`], c.f. [`Visibility`].
+    pub fn is_known(&self, position: Pos) -> bool {
+        match self.discovered {
+            None => self.maze.is_inside(position),
+            Some(discovered) => discovered.contains(position),
+        }
+    }
+
+    /// Iterates over the known positions of the [`Maze`] in row-major order, along with their [`Cell`].
+    /// Allows algorithms to study the whole layout up front when it is visible.
+    pub fn cells(&self) -> impl Iterator<Item = (Pos, Cell)> + '_ {
+        self.maze
+            .iter()
+            .filter(|&(position, _)| self.is_known(position))
+    }
+
+    /// Returns all positions directly accessible from a position of the [`Maze`], `None` if it is not known.
+    pub fn paths_from(&self, position: Pos) -> Option<Neighbors> {
+        self.is_known(position)
+            .then(|| self.maze.paths_from(position))
+    }
+
+    /// Positions directly accessible from a position of the [`Maze`] with the `keys` collected so far,
+    /// leaving out the passages locked by a door they do not open, `None` if it is not known.
+    pub fn unlocked_paths_from(&self, position: Pos, keys: &[KeyId]) -> Option<Neighbors> {
+        let mut paths = self.paths_from(position)?;
+        paths.retain(|next| !self.maze.is_locked(position, *next, keys));
+        Some(paths)
+    }
+
+    /// Id of the key held by a position of the [`Maze`], `None` if it holds none or is not known.
+    pub fn key_at(&self, position: Pos) -> Option<KeyId> {
+        self.is_known(position)
+            .then(|| self.maze.key_at(position))
+            .flatten()
+    }
+
+    /// Ids of the keys collected by following `path`, sorted and without duplicates, unknown positions aside (c.f. [`Maze::keys_along`]).
+    pub fn keys_along(&self, path: &[Pos]) -> Vec<KeyId> {
+        let mut keys: Vec<_> = path.iter().filter_map(|&p| self.key_at(p)).collect();
+        keys.sort_unstable();
+        keys.dedup();
+        keys
+    }
+
+    /// Check if there is a wall between two adjascent positions of the [`Maze`], `None` if neither of them is known.
+    pub fn is_walled(&self, position_a: Pos, position_b: Pos) -> Option<bool> {
+        if self.is_known(position_a) {
+            Some(self.maze.is_walled(position_a, position_b))
+        } else if self.is_known(position_b) {
+            Some(self.maze.is_walled(position_b, position_a))
+        } else {
+            None
+        }
+    }
+
+    /// Cost of entering a position of the [`Maze`], `None` if it is not known (c.f. [`Maze::cost`]).
+    pub fn cost(&self, position: Pos) -> Option<u32> {
+        self.is_known(position).then(|| self.maze.cost(position))
+    }
+
+    /// Returns adjascent positions of `position` that are included in the [`Maze`].
+    /// Only depends on the size of the maze, hence always visible.
+    pub fn adjascent(&self, position: Pos) -> Neighbors {
+        self.maze.adjascent(position)
+    }
+
+    /// Returns the neighbors of `position` clockwise around it, whether they are included in the [`Maze`] or not (c.f. [`Maze::around`]).
+    /// Only depends on the size and topology of the maze, hence always visible.
+    pub fn around(&self, position: Pos) -> Neighbors {
+        self.maze.around(position)
+    }
+}
+
+/// What an [`Algorithm`] can see of the [`Maze`] through its [`Context`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Visibility {
+    /// The whole layout of the maze is visible.
+    #[default]
+    Full,
+    /// Only the positions given as [`Insight`]s so far are visible, the rest is under a fog of war.
+    Discovered,
+}
+
+/// Rules enforced while driving an [`Algorithm`] through a [`Maze`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct Rules {
+    pub max_ticks: Option<usize>,
+    pub visibility: Visibility,
+}
+
+/// State of the resolution of a [`Maze`] by an [`Algorithm`], progressing one tick at a time.
+pub(crate) struct Driver {
+    rules: Rules,
+    insight: Insight,
+    discovered: Option<VisitedSet>,
+    tick: usize,
+    finished: bool,
+}
+
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+impl Driver {
+    /// Constructor, for a resolution starting at the start of `maze`, already finished when the start is one of its ends.
+    pub fn new(maze: &Maze, rules: Rules) -> Self {
+        let insight = Insight::from_position(maze.start(), maze);
+        let discovered = match rules.visibility {
+            Visibility::Full => None,
+            Visibility::Discovered => Some(VisitedSet::new(maze.width(), maze.height())),
+        };
+        Self {
+            rules,
+            insight,
+            discovered,
+            tick: 0,
+            finished: maze.is_end(maze.start()),
+        }
+    }
+
+    /// Number of ticks done so far.
+    pub fn tick(&self) -> usize {
+        self.tick
+    }
+
+    /// Whether the last guess linked the start of the maze to its end.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Ask `algorithm` for its next guess and check it, returns the guessed path.
+    /// A guess anchored at the end that reaches the start is returned reversed, so that finishing paths always lead from the start to the end.
+    /// Fails as soon as a guess can not be followed or the `rules` are broken.
+    pub fn step(&mut self, maze: &Maze, algorithm: &mut dyn Algorithm) -> Result<Vec<Pos>> {
+        let Self {
+            rules,
+            insight,
+            discovered,
+            tick,
+            ..
+        } = self;
+        let tick = *tick;
+        if rules.max_ticks.is_some_and(|max_ticks| tick >= max_ticks) {
+            debug!("stopped after {tick} ticks");
+            return Err(Error::Timeout { ticks: tick });
+        }
+        let mut context = match discovered {
+            None => Context::new(maze),
+            Some(discovered) => {
+                discovered.insert(insight.position());
+                Context::with_discovered(maze, discovered)
+            }
+        };
+        let Guess {
+            path,
+            anchor,
+            unsupported,
+        } = algorithm.progress(insight, &mut context);
+        // TODO: extract metrics from the context
+        if unsupported {
+            debug!("algorithm does not support {:?} mazes", maze.topology());
+            return Err(MazeError::UnsupportedTopology.into());
+        }
+        let Some(mut guess) = path else {
+            debug!("algorithm gave up at tick {tick}");
+            return Err(Error::Unsolvable);
+        };
+        let tail = check_guess(maze, &guess, anchor).map_err(|reason| {
+            warn!("invalid guess at tick {tick}: {reason}");
+            Error::InvalidGuess { tick, reason }
+        })?;
+        trace!(
+            "tick {tick}: guessed a path of {} positions to {tail:?}",
+            guess.len()
+        );
+        self.insight = Insight::from_path(&guess, maze);
+        self.tick += 1;
+        self.finished = match anchor {
+            Anchor::Start => maze.is_end(tail),
+            Anchor::End => maze.is_start(tail),
+        };
+        if self.finished && anchor == Anchor::End {
+            guess.reverse();
+        }
+        Ok(guess)
+    }
+}
+
+/// Drive `algorithm` through `maze` until it guesses a path to the end, handing each guessed path to `on_tick`.
+/// Returns the number of ticks it took, zero when the start is one of the ends, or an error as soon as a guess can not be followed or the `rules` are broken.
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub(crate) fn drive<F>(
+    maze: &Maze,
+    algorithm: &mut dyn Algorithm,
+    rules: Rules,
+    mut on_tick: F,
+) -> Result<usize>
+where
+    F: FnMut(usize, Vec<Pos>),
+{
+    let mut driver = Driver::new(maze, rules);
+    while !driver.is_finished() {
+        let tick = driver.tick();
+        let guess = driver.step(maze, algorithm)?;
+        on_tick(tick, guess);
+    }
+    debug!("reached the end after {} ticks", driver.tick());
+    Ok(driver.tick())
 }
 
+/// An ongoing resolution of a [`Maze`] by an [`Algorithm`], progressing one tick per call to [`Execution::step`].
+/// Allows embedding a resolution in an event loop instead of running it to completion with an [`Executor`].
+pub struct Execution {
+    maze: Maze,
+    algorithm: Box<dyn Algorithm>,
+    driver: Driver,
+    tried: VisitedSet,
+    path: Vec<Pos>,
+    fresh: Vec<Pos>,
+    finished: bool,
+}
+
+impl Execution {
+    /// Constructor, for a resolution of `maze` by `algorithm` starting at the start of the maze.
+    /// The execution is finished from the start, without any tick, when the start is one of the ends.
+    pub fn new(maze: Maze, algorithm: Box<dyn Algorithm>) -> Self {
+        let driver = Driver::new(&maze, Rules::default());
+        let tried = VisitedSet::new(maze.width(), maze.height());
+        let finished = driver.is_finished();
+        Self {
+            maze,
+            algorithm,
+            driver,
+            tried,
+            path: Vec::new(),
+            fresh: Vec::new(),
+            finished,
+        }
+    }
+
+    /// Progress by one tick, returns whether the end of the maze has been reached.
+    /// Does nothing once the end has been reached, fails when the algorithm gives up or guesses an invalid path.
+    pub fn step(&mut self) -> Result<bool> {
+        if self.finished {
+            return Ok(true);
+        }
+        let path = self.driver.step(&self.maze, self.algorithm.as_mut())?;
+        self.fresh.clear();
+        for &position in &path {
+            if self.tried.insert(position) {
+                self.fresh.push(position);
+            }
+        }
+        self.finished = self.driver.is_finished();
+        self.path = path;
+        Ok(self.finished)
+    }
+
+    /// The resolved maze.
+    pub fn maze(&self) -> &Maze {
+        &self.maze
+    }
+
+    /// Number of ticks done so far.
+    pub fn tick(&self) -> usize {
+        self.driver.tick()
+    }
+
+    /// The path guessed on the last tick, empty before the first one.
+    pub fn path(&self) -> &[Pos] {
+        &self.path
+    }
+
+    /// Every position guessed so far.
+    pub fn tried(&self) -> &VisitedSet {
+        &self.tried
+    }
+
+    /// Positions guessed for the first time on the last tick.
+    pub fn fresh(&self) -> &[Pos] {
+        &self.fresh
+    }
+
+    /// Whether the end of the maze has been reached.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// The [`TraceFrame`] of the last tick, `None` before the first one.
+    pub fn frame(&self) -> Option<TraceFrame> {
+        let head = *self.path.last()?;
+        Some(TraceFrame {
+            tick: self.tick() - 1,
+            guess: self.path.clone(),
+            fresh: self.fresh.clone(),
+            head,
+        })
+    }
+}
+
+/// Record of a tick of an [`Execution`], sufficient to replay it frame by frame from the [`Maze`] alone.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TraceFrame {
+    tick: usize,
+    guess: Vec<Pos>,
+    fresh: Vec<Pos>,
+    head: Pos,
+}
+
+impl TraceFrame {
+    /// Index of the tick, starting at zero.
+    pub fn tick(&self) -> usize {
+        self.tick
+    }
+
+    /// The path guessed on this tick.
+    pub fn guess(&self) -> &[Pos] {
+        &self.guess
+    }
+
+    /// Positions guessed for the first time on this tick, the union of which over the previous frames is every tried position.
+    pub fn fresh(&self) -> &[Pos] {
+        &self.fresh
+    }
+
+    /// Last position of the guess, where the algorithm stands after this tick.
+    pub fn head(&self) -> Pos {
+        self.head
+    }
+}
+
+/// Check that a guessed path starts at the start of the [`Maze`] and only goes through open passages, returns its tail.
+fn check_guess(
+    maze: &Maze,
+    guess: &[Pos],
+    anchor: Anchor,
+) -> core::result::Result<Pos, &'static str> {
+    let (&first, &tail) = match (guess.first(), guess.last()) {
+        (Some(first), Some(tail)) => (first, tail),
+        _ => return Err("empty path"),
+    };
+    match anchor {
+        Anchor::Start if !maze.is_start(first) => {
+            return Err("path not beginning at the start of the maze")
+        }
+        Anchor::End if !maze.is_end(first) => {
+            return Err("path not beginning at the end of the maze")
+        }
+        _ => (),
+    }
+    for step in guess.windows(2) {
+        if !maze.is_inside(step[1]) {
+            return Err("path leaving the maze");
+        }
+        if maze.is_walled(step[0], step[1]) {
+            return Err("path going through a wall");
+        }
+    }
+    if maze.first_locked_step(guess).is_some() {
+        return Err("path going through a locked door");
+    }
+    Ok(tail)
+}
+
+/// Summary of a completed execution.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecutionReport {
+    ticks: usize,
+    explored: usize,
+    path: Vec<Pos>,
+    elapsed: Option<Duration>,
+    optimal: Option<Vec<Pos>>,
+}
+
+#[cfg(feature = "std")]
+impl ExecutionReport {
+    /// Number of ticks it took to reach the end of the [`Maze`].
+    pub fn ticks(&self) -> usize {
+        self.ticks
+    }
+
+    /// Number of distinct positions of the [`Maze`] covered by the guesses.
+    pub fn explored(&self) -> usize {
+        self.explored
+    }
+
+    /// The last guess, leading from the start to the end of the [`Maze`].
+    pub fn path(&self) -> &[Pos] {
+        &self.path
+    }
+
+    /// Duration of the execution, only measured when metrics are enabled in the builder.
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.elapsed
+    }
+
+    /// Shortest path from the start to the end reached, for comparison with [`ExecutionReport::path`] (c.f. [`Maze::shortest_path`]).
+    /// Only computed when metrics are enabled in the builder.
+    pub fn optimal(&self) -> Option<&[Pos]> {
+        self.optimal.as_deref()
+    }
+}
+
+/// How an [`Executor`] displays the progression of the [`Algorithm`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Renderer {
+    /// Draw each tick in the terminal, the default with the `render-terminal` feature.
+    #[cfg(feature = "render-terminal")]
+    #[default]
+    Terminal,
+    /// Draw each tick in the terminal, only showing the level of a multi-level [`Maze`] holding the head of the path (c.f. [`crate::labyrinth::level`]).
+    #[cfg(feature = "render-terminal")]
+    Levels,
+    /// Display nothing, the execution only produces an [`ExecutionReport`].
+    #[cfg_attr(not(feature = "render-terminal"), default)]
+    Headless,
+}
+
+/// Options of an [`Executor`], set through its builder.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct ExecutorConfig {
+    delay: Duration,
+    renderer: Renderer,
+    max_ticks: Option<usize>,
+    visibility: Visibility,
+    seed: Option<u64>,
+    metrics: bool,
+    #[cfg(feature = "generate")]
+    placement: Option<crate::Placement>,
+}
+
+#[cfg(feature = "std")]
+impl Default for ExecutorConfig {
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_millis(100),
+            renderer: Renderer::default(),
+            max_ticks: None,
+            visibility: Visibility::Full,
+            seed: None,
+            metrics: false,
+            #[cfg(feature = "generate")]
+            placement: None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ExecutorConfig {
+    /// Check that the options are consistent with each other.
+    fn validate(&self) -> Result<()> {
+        if self.max_ticks == Some(0) {
+            return Err(Error::InvalidConfig("the tick limit must be positive"));
+        }
+        Ok(())
+    }
+
+    /// Move the start and the end of `maze` if a placement is set, random positions being drawn from the seed if there is one.
+    fn place(&self, maze: &mut Maze) -> Result<()> {
+        #[cfg(feature = "generate")]
+        if let Some(placement) = self.placement {
+            use rand::{rngs::StdRng, thread_rng, SeedableRng};
+
+            match self.seed {
+                Some(seed) => placement.apply(maze, &mut StdRng::seed_from_u64(seed))?,
+                None => placement.apply(maze, &mut thread_rng())?,
+            }
+        }
+        #[cfg(not(feature = "generate"))]
+        let _ = maze;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
 mod builder;
+#[cfg(feature = "render-terminal")]
+mod terminal;
 
 /// A structure holding a [`Maze`] and iteratively solving it with a provided [`Algorithm`].
+#[cfg(feature = "std")]
 pub struct Executor {
-    delay: Duration,
+    config: ExecutorConfig,
     maze: Maze,
     algorithm: Box<dyn Algorithm>,
 }
 
+#[cfg(feature = "std")]
 impl Executor {
     /// Constructor.
-    fn new(maze: Maze, algorithm: Box<dyn Algorithm>, delay: Duration) -> Self {
+    fn new(maze: Maze, algorithm: Box<dyn Algorithm>, config: ExecutorConfig) -> Self {
         Self {
             maze,
             algorithm,
-            delay,
+            config,
         }
     }
 
-    pub fn build<'f, A, F, MS>(algorithm: A, builder: F) -> Self
+    /// Create an [`Executor`] configured by `builder`, fails if the configuration is inconsistent.
+    pub fn build<A, F, MS>(algorithm: A, builder: F) -> Result<Self>
     where
         A: Algorithm + 'static,
         MS: BuildableMazeState,
@@ -116,79 +784,347 @@ impl Executor {
     {
         let operation = builder;
         let builder = (operation)(new_builder());
-        let (maze, delay) = builder.build();
+        let (maze, config) = builder.build()?;
         let algorithm = Box::new(algorithm);
-        Self::new(maze, algorithm, delay)
+        Ok(Self::new(maze, algorithm, config))
     }
 
-    pub fn build_dyn<F>(algorithm: Box<dyn Algorithm>, builder: F) -> Self
+    /// Create an [`Executor`] for a boxed [`Algorithm`], fails if no maze is provided or if the configuration is inconsistent.
+    pub fn build_dyn<F>(algorithm: Box<dyn Algorithm>, builder: F) -> Result<Self>
     where
         F: Fn(DynExecutorBuilder) -> DynExecutorBuilder,
     {
         let operation = builder;
         let builder = (operation)(DynExecutorBuilder::new());
-        let (maze, delay) = builder.build();
-        Self::new(maze, algorithm, delay)
+        let (maze, config) = builder.build()?;
+        Ok(Self::new(maze, algorithm, config))
     }
 
     /// Submit the maze to the [`Algorithm`] and iteratively progress through the maze driven by said algorithm.
-    pub fn run(&mut self) {
+    /// Frames are drawn on a dedicated thread, so that the algorithm computes the next tick while the previous one is displayed.
+    /// Stops with an error when the algorithm gives up, guesses a path that can not be followed or exceeds the tick limit.
+    pub fn run(&mut self) -> Result<ExecutionReport> {
         let Self {
             maze,
             algorithm,
-            delay,
+            config,
         } = self;
-        let mut insight = Insight::from_position(maze.start(), &maze);
-        let mut tick = 0;
-        let mut tried = HashSet::new();
-        loop {
-            let mut context = Context::new(maze);
-            let Guess(guess) = algorithm.progress(&insight, &mut context);
-            // TODO:
-            // - extract metrics from the context
-            // - check if path is actually a path
+        let begin = Instant::now();
+
+        // waits for the renderer to restore the terminal, even when the algorithm panics
+        #[cfg(feature = "render-terminal")]
+        let mut rendering = match config.renderer {
+            Renderer::Terminal => Some(Rendering::spawn(maze.clone(), config.delay, false)),
+            Renderer::Levels => Some(Rendering::spawn(maze.clone(), config.delay, true)),
+            Renderer::Headless => None,
+        };
+
+        let mut tried = VisitedSet::new(maze.width(), maze.height());
+        // the start alone leads to the end when it is one of them, no guess being made
+        let mut last = Vec::from([maze.start()]);
+        let rules = Rules {
+            max_ticks: config.max_ticks,
+            visibility: config.visibility,
+        };
+        let result = drive(maze, algorithm.as_mut(), rules, |tick, guess| {
             guess.iter().for_each(|&p| {
                 tried.insert(p);
             });
-            let tail = *guess.last().expect("returned an empty path");
-
-            // draw
-            Self::draw(maze, &tried, tick, &guess);
-            thread::sleep(*delay);
-            tick += 1;
-
-            // check for next iteration
-            if maze.is_end(tail) {
-                break;
-            } else {
-                insight = Insight::from_position(tail, maze)
+            last.clone_from(&guess);
+            #[cfg(feature = "render-terminal")]
+            if let Some(rendering) = &mut rendering {
+                rendering.draw(maze, tick, &tried, guess);
             }
+            #[cfg(not(feature = "render-terminal"))]
+            let _ = tick;
+        });
+
+        #[cfg(feature = "render-terminal")]
+        let rendered = rendering.as_mut().map_or(Ok(()), Rendering::finish);
+        let ticks = result?;
+        #[cfg(feature = "render-terminal")]
+        rendered?;
+        let elapsed = config.metrics.then(|| begin.elapsed());
+        let optimal = match last.last() {
+            Some(&end) if config.metrics => maze.shortest_path(maze.start(), end),
+            _ => None,
+        };
+        Ok(ExecutionReport {
+            ticks,
+            explored: tried.len(),
+            path: last,
+            elapsed,
+            optimal,
+        })
+    }
+}
+
+/// Handle on the rendering thread of an execution, joining it once dropped.
+#[cfg(feature = "render-terminal")]
+struct Rendering {
+    sender: Option<mpsc::SyncSender<Frame>>,
+    handle: Option<thread::JoinHandle<std::io::Result<()>>>,
+    recycled: mpsc::Receiver<Frame>,
+    spare: Option<Frame>,
+    skip_frames: bool,
+}
+
+#[cfg(feature = "render-terminal")]
+impl Rendering {
+    /// Start the thread drawing the frames of the execution on `maze`, waiting `delay` after each one,
+    /// only drawing the level holding the head of the path if `per_level`.
+    fn spawn(maze: Maze, delay: Duration, per_level: bool) -> Self {
+        // the channel holds a single frame, keeping the algorithm at most one tick ahead of the display
+        let (sender, receiver) = mpsc::sync_channel::<Frame>(1);
+        // drawn frames are sent back to be filled again instead of allocating new ones every tick
+        let (recycler, recycled) = mpsc::channel::<Frame>();
+        let handle = thread::spawn(move || {
+            let mut renderer = TerminalRenderer::new(per_level);
+            for frame in receiver {
+                renderer.draw(&maze, &frame.tried, frame.tick, &frame.path)?;
+                thread::sleep(delay);
+                let _ = recycler.send(frame);
+            }
+            Ok(())
+        });
+        Self {
+            sender: Some(sender),
+            handle: Some(handle),
+            recycled,
+            spare: None,
+            skip_frames: delay.is_zero(),
         }
     }
 
-    fn draw(maze: &Maze, tried: &HashSet<Pos>, tick: usize, path: &Vec<Pos>) {
-        let mut overlay = HashMap::new();
-        for position in tried {
-            overlay.insert(*position, '░');
+    /// Hand the state of the execution at the end of `tick` to the rendering thread.
+    fn draw(&mut self, maze: &Maze, tick: usize, tried: &VisitedSet, path: Vec<Pos>) {
+        let frame = match self.recycle() {
+            Some(mut frame) => {
+                frame.tick = tick;
+                frame.tried.clone_from(tried);
+                frame.path = path;
+                frame
+            }
+            None => Frame {
+                tick,
+                tried: tried.clone(),
+                path,
+            },
+        };
+        let is_last = maze.is_end(*frame.path.last().expect("checked guess"));
+        self.send(frame, is_last);
+    }
+
+    /// A frame already drawn, to be filled again.
+    fn recycle(&mut self) -> Option<Frame> {
+        self.spare.take().or_else(|| self.recycled.try_recv().ok())
+    }
+
+    /// Hand a frame to the rendering thread.
+    /// Without delay, frames are skipped while the previous one is being drawn, except for the `last` one.
+    /// Frames are dropped once the thread stopped on an error, reported by [`Rendering::finish`].
+    fn send(&mut self, frame: Frame, last: bool) {
+        let Some(sender) = &self.sender else {
+            return;
+        };
+        let stopped = if self.skip_frames && !last {
+            match sender.try_send(frame) {
+                Ok(()) => false,
+                Err(mpsc::TrySendError::Full(frame)) => {
+                    self.spare = Some(frame);
+                    false
+                }
+                Err(mpsc::TrySendError::Disconnected(_)) => true,
+            }
+        } else {
+            sender.send(frame).is_err()
+        };
+        if stopped {
+            self.sender = None;
         }
-        for position in path {
-            overlay.insert(*position, '█');
+    }
+
+    /// Let the rendering thread draw the remaining frames and wait for it to stop.
+    /// Fails if the terminal could not be written to, resumes the panic of the thread if it panicked.
+    fn finish(&mut self) -> Result<()> {
+        drop(self.sender.take());
+        match self.handle.take().map(thread::JoinHandle::join) {
+            None | Some(Ok(Ok(()))) => Ok(()),
+            Some(Ok(Err(error))) => Err(Error::Io(error)),
+            Some(Err(payload)) if !thread::panicking() => std::panic::resume_unwind(payload),
+            Some(Err(_)) => Ok(()),
         }
-        overlay.insert(maze.start(), 'S');
-        overlay.insert(maze.end(), 'E');
-        overlay.insert(*path.last().unwrap(), 'G');
+    }
+}
 
-        let grid = maze.display(Some(overlay));
-        let text = format!("tick {tick}:\n{grid}\n");
+#[cfg(feature = "render-terminal")]
+impl Drop for Rendering {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
 
-        // DIRTY!
-        // print the frame on top of the previous one
-        if tick > 0 {
-            let count = text.lines().count();
-            let up = termion::cursor::Up(count as u16);
-            print!("{up}")
-        }
+#[test]
+fn drive_errors() {
+    use crate::implementations::{DepthFirst, Scripted};
+
+    // no passage leads to the end
+    let maze = Maze::new(2, 1, Pos::zero(), Pos::new(1, 0), Vec::new());
+    let result = drive(&maze, &mut DepthFirst::new(), Rules::default(), |_, _| ());
+    assert!(matches!(result, Err(Error::Unsolvable)));
+
+    let mut teleport = Scripted::new([alloc::vec![maze.start(), maze.end()]]);
+    let result = drive(&maze, &mut teleport, Rules::default(), |_, _| ());
+    assert!(matches!(result, Err(Error::InvalidGuess { tick: 0, .. })));
+}
+
+#[test]
+fn start_is_end() {
+    use alloc::boxed::Box;
+
+    use crate::implementations::{BreathFirst, DepthFirst};
+
+    // a single cell, the resolution is over before the algorithm is asked anything
+    let maze = Maze::new(1, 1, Pos::zero(), Pos::zero(), Vec::new());
+    let mut guesses = Vec::new();
+    let ticks = drive(&maze, &mut DepthFirst::new(), Rules::default(), |_, g| {
+        guesses.push(g)
+    });
+    assert_eq!(ticks.unwrap(), 0);
+    assert!(guesses.is_empty());
+
+    #[cfg(feature = "std")]
+    {
+        let executor = Executor::build(DepthFirst::new(), |b| {
+            b.maze(maze.clone()).renderer(Renderer::Headless)
+        });
+        let report = executor.unwrap().run().unwrap();
+        assert_eq!((report.ticks(), report.path()), (0, &[Pos::zero()][..]));
+    }
+
+    let mut execution = Execution::new(maze, Box::new(BreathFirst::new()));
+    assert!(execution.is_finished());
+    assert!(execution.step().unwrap());
+    assert_eq!((execution.tick(), execution.frame()), (0, None));
+}
+
+#[test]
+fn multiple_ends() {
+    use crate::implementations::BreathFirst;
+
+    // a corridor with an end at each side of the start
+    let (a, b, c) = (Pos::zero(), Pos::new(1, 0), Pos::new(2, 0));
+    let mut maze = Maze::new(
+        3,
+        1,
+        b,
+        c,
+        alloc::vec![(a, alloc::vec![b]), (b, alloc::vec![c])],
+    );
+    maze.try_add_end(a).unwrap();
+    let mut guesses = Vec::new();
+    let ticks = drive(&maze, &mut BreathFirst::new(), Rules::default(), |_, g| {
+        guesses.push(g)
+    });
+    assert_eq!(ticks.unwrap(), 1);
+    assert!(maze.is_end(*guesses[0].last().unwrap()));
+}
+
+#[cfg(all(feature = "std", feature = "generate"))]
+#[test]
+fn builder() {
+    use crate::{implementations::DepthFirst, MazeGenerator, SimpleGenerator};
+
+    let build = |max_ticks| {
+        Executor::build(DepthFirst::new(), |b| {
+            b.generated(SimpleGenerator::new(12, 8))
+                .seed(7)
+                .renderer(Renderer::Headless)
+                .max_ticks(max_ticks)
+                .metrics(true)
+        })
+    };
+    let report = build(1000).unwrap().run().unwrap();
+    assert!(report.elapsed().is_some());
+    let optimal = report.optimal().unwrap();
+    assert!(optimal.len() <= report.path().len());
+    assert_eq!(optimal.last(), report.path().last());
+    // the same seed gives the same maze, hence the same resolution
+    assert_eq!(build(1000).unwrap().run().unwrap().path(), report.path());
+    assert!(matches!(
+        build(1).unwrap().run(),
+        Err(Error::Timeout { ticks: 1 })
+    ));
+    assert!(matches!(build(0), Err(Error::InvalidConfig(_))));
+
+    let maze = SimpleGenerator::new(4, 4).generate().unwrap();
+    let seeded = Executor::build(DepthFirst::new(), |b| b.maze(maze.clone()).seed(7));
+    assert!(matches!(seeded, Err(Error::InvalidConfig(_))));
+    let missing = Executor::build_dyn(DepthFirst::new().boxed(), |b| b);
+    assert!(matches!(missing, Err(Error::InvalidConfig(_))));
+
+    let (start, end) = (Pos::new(1, 1), Pos::new(2, 3));
+    let placed = Executor::build(DepthFirst::new(), |b| {
+        b.maze(maze.clone())
+            .placement(crate::Placement::Explicit { start, end })
+    });
+    let placed = placed.unwrap();
+    assert_eq!((placed.maze.start(), placed.maze.end()), (start, end));
+
+    // a portal doubling an open passage is rejected before the execution
+    let mut doubled = maze.clone();
+    let neighbor = doubled.paths_from(doubled.start())[0];
+    doubled.add_portal(doubled.start(), neighbor);
+    let rejected = Executor::build(DepthFirst::new(), |b| b.maze(doubled.clone()));
+    assert!(matches!(
+        rejected,
+        Err(Error::InvalidMaze(crate::MazeError::AlreadyOpen(..)))
+    ));
+}
+
+#[test]
+fn visibility() {
+    let maze = Maze::new(
+        3,
+        1,
+        Pos::zero(),
+        Pos::new(2, 0),
+        alloc::vec![(Pos::zero(), alloc::vec![Pos::new(1, 0)])],
+    );
+    let mut discovered = VisitedSet::new(3, 1);
+    discovered.insert(Pos::zero());
+    let ctx = Context::with_discovered(&maze, &discovered);
+    assert_eq!(
+        ctx.paths_from(Pos::zero()).unwrap().as_slice(),
+        &[Pos::new(1, 0)]
+    );
+    assert_eq!(ctx.paths_from(Pos::new(1, 0)), None);
+    assert_eq!(ctx.is_walled(Pos::new(1, 0), Pos::zero()), Some(false));
+    assert_eq!(ctx.is_walled(Pos::new(1, 0), Pos::new(2, 0)), None);
+    assert_eq!(
+        Context::new(&maze).is_walled(Pos::new(1, 0), Pos::new(2, 0)),
+        Some(true)
+    );
+    assert_eq!(ctx.adjascent(Pos::new(1, 0)).len(), 2);
+}
+
+#[cfg(all(feature = "std", feature = "generate"))]
+#[test]
+fn execution_frames() {
+    use crate::{implementations::BreathFirst, MazeGenerator, SimpleGenerator};
 
-        print!("{text}");
+    let maze = SimpleGenerator::new(10, 6).generate().unwrap();
+    let mut execution = Execution::new(maze.clone(), BreathFirst::new().boxed());
+    assert_eq!(execution.frame(), None);
+    let mut tried = VisitedSet::new(10, 6);
+    while !execution.step().unwrap() {
+        let frame = execution.frame().unwrap();
+        assert_eq!(frame.tick() + 1, execution.tick());
+        assert_eq!(Some(&frame.head()), frame.guess().last());
+        frame.fresh().iter().for_each(|&p| assert!(tried.insert(p)));
     }
+    let frame = execution.frame().unwrap();
+    frame.fresh().iter().for_each(|&p| assert!(tried.insert(p)));
+    assert!(maze.is_end(frame.head()));
+    assert_eq!(&tried, execution.tried());
 }
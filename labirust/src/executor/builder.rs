@@ -1,37 +1,51 @@
 use std::time::Duration;
 
-use crate::{labyrinth::generator::MazeGenerator, Maze};
+#[cfg(feature = "generate")]
+use crate::Placement;
+use crate::{labyrinth::generator::MazeGenerator, Error, Maze, Result};
 
 use self::maze_state::{BuildableMazeState, Generated, MazeState, Provided, Unprovided};
+use super::{ExecutorConfig, Renderer, Visibility};
 
 pub mod maze_state {
 
-    use crate::{labyrinth::generator::MazeGenerator, Maze};
+    use crate::{labyrinth::generator::MazeGenerator, Error, Maze, Result};
 
     /// Describe the state of the [`Maze`] parameter in the builder of an [`crate::Executor`]. Not ment to be implemented.
     pub trait MazeState {}
     pub trait BuildableMazeState: MazeState {
-        fn get(&self) -> Maze;
+        /// Get the [`Maze`], generated from `seed` if provided.
+        fn get(&mut self, seed: Option<u64>) -> Result<Maze>;
     }
 
+    #[derive(Debug, Clone, Copy)]
     pub struct Unprovided;
 
     impl MazeState for Unprovided {}
 
+    #[derive(Debug, Clone)]
     pub struct Provided {
-        maze: Maze,
+        maze: Box<Maze>,
     }
 
     impl Provided {
         pub fn new(maze: Maze) -> Self {
+            let maze = Box::new(maze);
             Self { maze }
         }
     }
 
     impl MazeState for Provided {}
     impl BuildableMazeState for Provided {
-        fn get(&self) -> Maze {
-            self.maze.clone()
+        /// Get the provided [`Maze`], failing if it is inconsistent (c.f. [`Maze::validate`]).
+        fn get(&mut self, seed: Option<u64>) -> Result<Maze> {
+            self.maze.validate().check()?;
+            match seed {
+                None => Ok(*self.maze.clone()),
+                Some(_) => Err(Error::InvalidConfig(
+                    "a seed can not apply to a provided maze",
+                )),
+            }
         }
     }
 
@@ -55,8 +69,12 @@ pub mod maze_state {
 
     impl MazeState for Generated {}
     impl BuildableMazeState for Generated {
-        fn get(&self) -> Maze {
-            self.generator.generate()
+        fn get(&mut self, seed: Option<u64>) -> Result<Maze> {
+            let maze = match seed {
+                None => self.generator.generate()?,
+                Some(seed) => self.generator.generate_seeded(seed)?,
+            };
+            Ok(maze)
         }
     }
 }
@@ -67,25 +85,25 @@ where
     MS: MazeState,
 {
     maze_state: MS,
-    delay: Duration,
+    config: ExecutorConfig,
 }
 
 pub(crate) fn new_builder() -> ExecutorBuilder<Unprovided> {
     ExecutorBuilder {
         maze_state: Unprovided,
-        delay: Duration::from_millis(100),
+        config: ExecutorConfig::default(),
     }
 }
 
 impl<MS: MazeState> ExecutorBuilder<MS> {
-    /// Provide a specific [`Maze`] for the execution.
+    /// Provide a specific [`Maze`] for the execution, building the executor fails if it is inconsistent (c.f. [`Maze::validate`]).
     pub fn maze(self, maze: Maze) -> ExecutorBuilder<Provided> {
         let Self {
-            delay,
+            config,
             maze_state: _,
         } = self;
         ExecutorBuilder {
-            delay,
+            config,
             maze_state: Provided::new(maze),
         }
     }
@@ -96,31 +114,94 @@ impl<MS: MazeState> ExecutorBuilder<MS> {
         G: MazeGenerator + 'static,
     {
         let Self {
-            delay,
+            config,
             maze_state: _,
         } = self;
         ExecutorBuilder {
-            delay,
+            config,
             maze_state: Generated::new(generator),
         }
     }
 
     /// Sets the delay between terminal redraws, default is 100ms.
+    /// A delay of zero runs the algorithm as fast as possible, skipping the frames produced while the previous one is drawn.
     pub fn delay_ms(self, delay: u64) -> Self {
-        let delay = Duration::from_millis(delay);
-        let Self {
-            maze_state,
-            delay: _,
-        } = self;
-        Self { maze_state, delay }
+        self.delay(Duration::from_millis(delay))
+    }
+
+    /// Sets the delay between terminal redraws, c.f. [`ExecutorBuilder::delay_ms`].
+    pub fn delay(self, delay: Duration) -> Self {
+        let Self { maze_state, config } = self;
+        let config = ExecutorConfig { delay, ..config };
+        Self { maze_state, config }
+    }
+
+    /// Sets how the execution is displayed, default is [`Renderer::Terminal`] with the `render-terminal` feature and [`Renderer::Headless`] otherwise.
+    pub fn renderer(self, renderer: Renderer) -> Self {
+        let Self { maze_state, config } = self;
+        let config = ExecutorConfig { renderer, ..config };
+        Self { maze_state, config }
+    }
+
+    /// Stops the execution with [`Error::Timeout`] once the algorithm took `max_ticks` ticks without reaching the end.
+    pub fn max_ticks(self, max_ticks: usize) -> Self {
+        let Self { maze_state, config } = self;
+        let max_ticks = Some(max_ticks);
+        let config = ExecutorConfig {
+            max_ticks,
+            ..config
+        };
+        Self { maze_state, config }
+    }
+
+    /// Sets what the algorithm can see of the [`Maze`], default is [`Visibility::Full`].
+    pub fn visibility(self, visibility: Visibility) -> Self {
+        let Self { maze_state, config } = self;
+        let config = ExecutorConfig {
+            visibility,
+            ..config
+        };
+        Self { maze_state, config }
+    }
+
+    /// Generates the [`Maze`] from `seed`, only valid with a generator that can be seeded.
+    pub fn seed(self, seed: u64) -> Self {
+        let Self { maze_state, config } = self;
+        let seed = Some(seed);
+        let config = ExecutorConfig { seed, ..config };
+        Self { maze_state, config }
+    }
+
+    /// Sets whether the duration of the execution is measured and reported, default is `false`.
+    pub fn metrics(self, metrics: bool) -> Self {
+        let Self { maze_state, config } = self;
+        let config = ExecutorConfig { metrics, ..config };
+        Self { maze_state, config }
+    }
+
+    /// Moves the start and the end of the [`Maze`] according to `placement` before the execution.
+    #[cfg(feature = "generate")]
+    pub fn placement(self, placement: Placement) -> Self {
+        let Self { maze_state, config } = self;
+        let placement = Some(placement);
+        let config = ExecutorConfig {
+            placement,
+            ..config
+        };
+        Self { maze_state, config }
     }
 }
 
 impl<MS: BuildableMazeState> ExecutorBuilder<MS> {
-    pub(crate) fn build(self) -> (Maze, Duration) {
-        let maze = self.maze_state.get();
-        let delay = self.delay;
-        (maze, delay)
+    pub(crate) fn build(self) -> Result<(Maze, ExecutorConfig)> {
+        let Self {
+            mut maze_state,
+            config,
+        } = self;
+        config.validate()?;
+        let mut maze = maze_state.get(config.seed)?;
+        config.place(&mut maze)?;
+        Ok((maze, config))
     }
 }
 
@@ -131,49 +212,104 @@ pub enum DynMazeState {
 }
 
 impl DynMazeState {
-    pub fn get(self) -> Option<Maze> {
+    pub fn get(self, seed: Option<u64>) -> Result<Maze> {
         match self {
-            DynMazeState::None => None,
-            DynMazeState::Provided(provided) => Some(provided.get()),
-            DynMazeState::Generated(generated) => Some(generated.get()),
+            DynMazeState::None => Err(Error::InvalidConfig("no maze provided")),
+            DynMazeState::Provided(mut provided) => provided.get(seed),
+            DynMazeState::Generated(mut generated) => generated.get(seed),
         }
     }
 }
 
 pub struct DynExecutorBuilder {
     maze: DynMazeState,
-    delay: Duration,
+    config: ExecutorConfig,
 }
 
 impl DynExecutorBuilder {
     pub(crate) fn new() -> Self {
         Self {
             maze: DynMazeState::None,
-            delay: Duration::from_millis(100),
+            config: ExecutorConfig::default(),
         }
     }
 
     pub fn maze(self, maze: Maze) -> Self {
         let maze = DynMazeState::Provided(Provided::new(maze));
-        let Self { maze: _, delay } = self;
-        Self { maze, delay }
+        let Self { maze: _, config } = self;
+        Self { maze, config }
     }
 
     pub fn generated(self, generator: Box<dyn MazeGenerator>) -> Self {
         let maze = DynMazeState::Generated(Generated::new_dyn(generator));
-        let Self { maze: _, delay } = self;
-        Self { delay, maze }
+        let Self { maze: _, config } = self;
+        Self { config, maze }
     }
 
     pub fn delay_ms(self, delay: u64) -> Self {
-        let delay = Duration::from_millis(delay);
-        let Self { maze, delay: _ } = self;
-        Self { maze, delay }
+        self.delay(Duration::from_millis(delay))
+    }
+
+    pub fn delay(self, delay: Duration) -> Self {
+        let Self { maze, config } = self;
+        let config = ExecutorConfig { delay, ..config };
+        Self { maze, config }
+    }
+
+    pub fn renderer(self, renderer: Renderer) -> Self {
+        let Self { maze, config } = self;
+        let config = ExecutorConfig { renderer, ..config };
+        Self { maze, config }
+    }
+
+    pub fn max_ticks(self, max_ticks: usize) -> Self {
+        let Self { maze, config } = self;
+        let max_ticks = Some(max_ticks);
+        let config = ExecutorConfig {
+            max_ticks,
+            ..config
+        };
+        Self { maze, config }
+    }
+
+    pub fn visibility(self, visibility: Visibility) -> Self {
+        let Self { maze, config } = self;
+        let config = ExecutorConfig {
+            visibility,
+            ..config
+        };
+        Self { maze, config }
+    }
+
+    pub fn seed(self, seed: u64) -> Self {
+        let Self { maze, config } = self;
+        let seed = Some(seed);
+        let config = ExecutorConfig { seed, ..config };
+        Self { maze, config }
+    }
+
+    pub fn metrics(self, metrics: bool) -> Self {
+        let Self { maze, config } = self;
+        let config = ExecutorConfig { metrics, ..config };
+        Self { maze, config }
+    }
+
+    #[cfg(feature = "generate")]
+    pub fn placement(self, placement: Placement) -> Self {
+        let Self { maze, config } = self;
+        let placement = Some(placement);
+        let config = ExecutorConfig {
+            placement,
+            ..config
+        };
+        Self { maze, config }
     }
 
-    pub(crate) fn build(self) -> (Maze, Duration) {
-        let maze = self.maze.get().expect("no buildable maze provided");
-        let delay = self.delay;
-        (maze, delay)
+    pub(crate) fn build(self) -> Result<(Maze, ExecutorConfig)> {
+        let Self { maze, config } = self;
+        config.validate()?;
+        let mut maze = maze.get(config.seed)?;
+        config.place(&mut maze)?;
+        Ok((maze, config))
     }
 }
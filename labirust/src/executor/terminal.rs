@@ -0,0 +1,150 @@
+//! ## Terminal
+//!
+//! This module contains the renderer drawing the progression of an [`crate::Executor`] in the terminal.
+
+use core::fmt::Write as _;
+use std::{
+    collections::HashMap,
+    io::{self, stdout, Write},
+    string::String,
+};
+
+use termion::{
+    clear::UntilNewline,
+    cursor::{Down, Hide, Right, Show, Up},
+};
+
+use crate::{Maze, Pos, VisitedSet};
+
+/// Draws frames in the terminal, the first one entirely and the following ones by only rewriting the cells that changed.
+/// The overlays and the output text are kept between frames so that drawing does not allocate once the buffers have grown.
+/// The cursor is hidden while drawing and shown again once the renderer is dropped.
+/// When drawing `per_level`, only the level holding the head of the path is drawn, entirely again whenever it changes.
+pub(crate) struct TerminalRenderer {
+    overlay: HashMap<Pos, char>,
+    previous: HashMap<Pos, char>,
+    buffer: String,
+    lines: Option<usize>,
+    per_level: bool,
+    level: Option<isize>,
+}
+
+impl TerminalRenderer {
+    /// Constructor, drawing the whole maze or only the current level if `per_level`.
+    pub fn new(per_level: bool) -> Self {
+        Self {
+            overlay: HashMap::new(),
+            previous: HashMap::new(),
+            buffer: String::new(),
+            lines: None,
+            per_level,
+            level: None,
+        }
+    }
+
+    /// Draw the frame of a `tick` of the execution, fails if the terminal can not be written to.
+    pub fn draw(
+        &mut self,
+        maze: &Maze,
+        tried: &VisitedSet,
+        tick: usize,
+        path: &[Pos],
+    ) -> io::Result<()> {
+        let Self {
+            overlay,
+            previous,
+            buffer,
+            lines,
+            per_level,
+            level,
+        } = self;
+        core::mem::swap(overlay, previous);
+        Self::overlay(overlay, maze, tried, path);
+        let head = *path.last().unwrap();
+        let current = per_level.then(|| maze.level_of(head));
+        let header = match current {
+            Some(current) => format!("tick {tick}, level {current}:"),
+            None => format!("tick {tick}:"),
+        };
+
+        match *lines {
+            Some(lines) if current == *level => {
+                buffer.clear();
+                let up = lines as u16;
+                write!(buffer, "{}\r{header}\r{}", Up(up), Down(up)).unwrap();
+
+                let top = current.map_or(0, |current| current * maze.level_height());
+                let displayed =
+                    |p: &Pos| current.is_none_or(|current| maze.level_of(*p) == current);
+                let changed = overlay
+                    .iter()
+                    .filter(|(p, c)| displayed(p) && previous.get(p) != Some(c))
+                    .map(|(&p, &c)| (p, c));
+                let removed = previous
+                    .keys()
+                    .filter(|p| displayed(p) && !overlay.contains_key(p))
+                    .map(|&p| (p, ' '));
+                let mut count = 0;
+                for (position, character) in changed.chain(removed) {
+                    count += 1;
+                    let (column, line) = maze.display_coordinates(position - Pos::new(0, top));
+                    // the header line, then the lines of the maze
+                    let up = (lines - 1 - line) as u16;
+                    let right = column as u16;
+                    write!(
+                        buffer,
+                        "{}\r{}{character}\r{}",
+                        Up(up),
+                        Right(right),
+                        Down(up)
+                    )
+                    .unwrap();
+                }
+                trace!("drawing tick {tick}, {count} cells changed");
+            }
+            previous_lines => {
+                trace!("drawing tick {tick} entirely");
+                match current {
+                    Some(current) => maze.display_level_into(current, Some(overlay), buffer),
+                    None => maze.display_into(Some(overlay), buffer),
+                }
+                *lines = Some(buffer.lines().count() + 1);
+                *level = current;
+                // levels are the same size, so drawing another one overwrites the previous one
+                let up = previous_lines.map_or(String::new(), |up| format!("{}\r", Up(up as u16)));
+                buffer.insert_str(0, &format!("{up}{Hide}{header}{UntilNewline}\n"));
+                buffer.push('\n');
+            }
+        }
+
+        let mut out = stdout().lock();
+        out.write_all(buffer.as_bytes())?;
+        out.flush()
+    }
+
+    /// Fill `overlay` with the characters to draw on top of the cells of the [`Maze`].
+    fn overlay(overlay: &mut HashMap<Pos, char>, maze: &Maze, tried: &VisitedSet, path: &[Pos]) {
+        overlay.clear();
+        for position in tried.iter() {
+            overlay.insert(position, '░');
+        }
+        for position in path {
+            overlay.insert(*position, '█');
+        }
+        overlay.insert(maze.start(), 'S');
+        for end in maze.ends() {
+            overlay.insert(end, 'E');
+        }
+        overlay.insert(*path.last().unwrap(), 'G');
+    }
+}
+
+impl Drop for TerminalRenderer {
+    fn drop(&mut self) {
+        if self.lines.is_some() {
+            let mut out = stdout().lock();
+            let _ = write!(out, "{Show}");
+            let _ = out.flush();
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! ## Grid
+//!
+//! This module contains the definition of the [`Grid`] type, a container storing one value per position of a 2D-grid.
+
+use alloc::{vec, vec::Vec};
+use core::ops::{Index, IndexMut};
+
+use crate::Pos;
+
+/// A rectangular container holding one value of type `T` per position, stored in a flat row-major [`Vec`].
+/// Preferable to a [`std::collections::HashMap`] keyed by [`Pos`] whenever every position of a [`crate::Maze`] gets a value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "RawGrid<T>")
+)]
+pub struct Grid<T> {
+    width: isize,
+    height: isize,
+    cells: Vec<T>,
+}
+
+/// Fields of a deserialized [`Grid`], checked to hold one value per position before becoming one.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawGrid<T> {
+    width: isize,
+    height: isize,
+    cells: Vec<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<RawGrid<T>> for Grid<T> {
+    type Error = &'static str;
+
+    fn try_from(raw: RawGrid<T>) -> Result<Self, Self::Error> {
+        let RawGrid {
+            width,
+            height,
+            cells,
+        } = raw;
+        let count = width
+            .checked_mul(height)
+            .filter(|_| width >= 0 && height >= 0);
+        if count != Some(cells.len() as isize) {
+            return Err("grid dimensions do not match its number of cells");
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+}
+
+/// Data attached to each cell of a [`crate::Maze`], with its dimensions (c.f. [`crate::Maze::with_metadata`]).
+pub type MazeMap<T> = Grid<T>;
+
+impl<T> Grid<T> {
+    /// Constructor, initializing each cell with the result of `init` called on its position.
+    pub fn from_fn<F>(width: isize, height: isize, mut init: F) -> Self
+    where
+        F: FnMut(Pos) -> T,
+    {
+        let count = (width * height) as usize;
+        let cells = (0..count)
+            .map(|i| init(Pos::from_index(i, width)))
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Width of the [`Grid`].
+    pub fn width(&self) -> isize {
+        self.width
+    }
+
+    /// Height of the [`Grid`].
+    pub fn height(&self) -> isize {
+        self.height
+    }
+
+    /// Tuple containing both the width and height of the [`Grid`].
+    pub fn size(&self) -> (isize, isize) {
+        (self.width(), self.height())
+    }
+
+    /// Check if a position is included within the [`Grid`].
+    pub fn is_inside(&self, position: Pos) -> bool {
+        let (x, y) = position.decompose();
+        x >= 0 && x < self.width() && y >= 0 && y < self.height()
+    }
+
+    /// Reference to the value at `position`, `None` if out of bounds.
+    pub fn get(&self, position: Pos) -> Option<&T> {
+        if self.is_inside(position) {
+            self.cells.get(position.to_index(self.width))
+        } else {
+            None
+        }
+    }
+
+    /// Mutable reference to the value at `position`, `None` if out of bounds.
+    pub fn get_mut(&mut self, position: Pos) -> Option<&mut T> {
+        if self.is_inside(position) {
+            self.cells.get_mut(position.to_index(self.width))
+        } else {
+            None
+        }
+    }
+
+    /// Iterates over every position of the [`Grid`] in row-major order, along with its value.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, value)| (Pos::from_index(i, width), value))
+    }
+
+    /// Iterates mutably over every position of the [`Grid`] in row-major order, along with its value.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Pos, &mut T)> {
+        let width = self.width;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, value)| (Pos::from_index(i, width), value))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Constructor, initializing each cell with a copy of `value`.
+    pub fn new(width: isize, height: isize, value: T) -> Self {
+        let cells = vec![value; (width * height) as usize];
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Overwrite every cell of the [`Grid`] with a copy of `value`.
+    pub fn fill(&mut self, value: T) {
+        self.cells.fill(value);
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Pos) -> &Self::Output {
+        self.get(position).expect("position out of bounds")
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, position: Pos) -> &mut Self::Output {
+        self.get_mut(position).expect("position out of bounds")
+    }
+}
+
+#[test]
+fn indexing() {
+    let mut grid = Grid::new(4, 3, 0);
+    grid[Pos::new(3, 2)] = 5;
+    assert_eq!(grid[Pos::new(3, 2)], 5);
+    assert_eq!(grid.get(Pos::new(4, 0)), None);
+    assert_eq!(grid.get(Pos::new(-1, 0)), None);
+    assert_eq!(grid.iter().filter(|(_, &v)| v == 5).count(), 1);
+    grid.fill(1);
+    assert!(grid.iter().all(|(_, &v)| v == 1));
+
+    let grid = Grid::from_fn(4, 3, |p| p);
+    assert!(grid.iter().all(|(p, &v)| p == v));
+}
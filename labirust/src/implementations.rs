@@ -4,24 +4,431 @@
 //! They drive the resolution of a [`crate::Maze`] through different means.
 //!
 
+mod beam;
+mod bidirectional;
 mod breath_first;
 mod depth_first;
+mod fringe;
+mod ida_star;
+mod jump_point;
+#[cfg(feature = "std")]
+mod manual;
+mod mock;
+mod pledge;
+mod portfolio;
+#[cfg(feature = "generate")]
+mod random_mouse;
+mod scripted;
+mod tremaux;
+mod wall_follower;
+mod wavefront;
 
+pub use beam::BeamSearch;
+pub use bidirectional::Bidirectional;
 pub use breath_first::BreathFirst;
 pub use depth_first::DepthFirst;
+pub use fringe::FringeSearch;
+pub use ida_star::{Heuristic, IdaStar};
+pub use jump_point::JumpPointSearch;
+#[cfg(feature = "std")]
+pub use manual::{ManualControl, ManualHandle, Move};
+pub use mock::{Mock, MockCall};
+pub use pledge::Pledge;
+pub use portfolio::{Portfolio, Strategy};
+#[cfg(feature = "generate")]
+pub use random_mouse::{RandomMouse, Wander};
+pub use scripted::Scripted;
+pub use tremaux::Tremaux;
+pub use wall_follower::{Hand, WallFollower};
+pub use wavefront::Wavefront;
 
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::fmt;
+
+use crate::Algorithm;
+
+/// Description of one of the bundled [`Algorithm`]s, able to create instances of it.
+#[derive(Debug, Clone, Copy)]
+pub struct AlgorithmEntry {
+    name: &'static str,
+    description: &'static str,
+    factory: fn() -> Box<dyn Algorithm>,
+}
+
+impl AlgorithmEntry {
+    /// Constructor, for algorithms registered outside of the crate (c.f. [`Registry::register`]).
+    pub const fn new(
+        name: &'static str,
+        description: &'static str,
+        factory: fn() -> Box<dyn Algorithm>,
+    ) -> Self {
+        Self {
+            name,
+            description,
+            factory,
+        }
+    }
+
+    /// Name of the algorithm, in kebab case.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Short description of how the algorithm proceeds.
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+
+    /// Create a new instance of the algorithm, ready to solve a [`crate::Maze`].
+    pub fn create(&self) -> Box<dyn Algorithm> {
+        (self.factory)()
+    }
+}
+
+fn boxed<A: Algorithm + Default + 'static>() -> Box<dyn Algorithm> {
+    Box::new(A::default())
+}
+
+static REGISTRY: &[AlgorithmEntry] = &[
+    AlgorithmEntry {
+        name: "depth-first",
+        description: "explores each branch to its end before backtracking",
+        factory: boxed::<DepthFirst>,
+    },
+    AlgorithmEntry {
+        name: "breath-first",
+        description: "extends the closest discovered positions first, finding a shortest path",
+        factory: boxed::<BreathFirst>,
+    },
+    AlgorithmEntry {
+        name: "bidirectional",
+        description: "extends the closest positions to both the start and the end until they meet, finding a shortest path",
+        factory: boxed::<Bidirectional>,
+    },
+    AlgorithmEntry {
+        name: "wall-follower-left",
+        description: "walks with the left hand on the wall, only relying on the current position",
+        factory: || WallFollower::new(Hand::Left).boxed(),
+    },
+    AlgorithmEntry {
+        name: "wall-follower-right",
+        description: "walks with the right hand on the wall, only relying on the current position",
+        factory: || WallFollower::new(Hand::Right).boxed(),
+    },
+    AlgorithmEntry {
+        name: "tremaux",
+        description: "marks the entrances of the passages it walks through, walking each of them at most twice",
+        factory: boxed::<Tremaux>,
+    },
+    AlgorithmEntry {
+        name: "ida-star",
+        description: "repeats depth-first searches bounded by a growing estimation of the path length, finding a shortest path, fetching the keys of locked doors",
+        factory: boxed::<IdaStar>,
+    },
+    AlgorithmEntry {
+        name: "pledge",
+        description: "walks east, following walls with the right hand until its turns cancel out",
+        factory: boxed::<Pledge>,
+    },
+    AlgorithmEntry {
+        name: "jump-point",
+        description: "searches the shortest path between jump points, crossing straight runs in a single tick",
+        factory: boxed::<JumpPointSearch>,
+    },
+    AlgorithmEntry {
+        name: "wavefront",
+        description: "floods the maze from the end before walking downhill from the start, finding a shortest path",
+        factory: boxed::<Wavefront>,
+    },
+    AlgorithmEntry {
+        name: "beam",
+        description: "extends the four positions of each level closest to the end, setting the others aside",
+        factory: boxed::<BeamSearch>,
+    },
+    AlgorithmEntry {
+        name: "fringe",
+        description: "defers the positions exceeding a growing estimation of the path length to the next iteration, finding a shortest path",
+        factory: boxed::<FringeSearch>,
+    },
+    #[cfg(all(feature = "std", feature = "generate"))]
+    AlgorithmEntry {
+        name: "random-mouse",
+        description: "walks to a random unvisited position, or a random one when there are none",
+        factory: boxed::<RandomMouse>,
+    },
+];
+
+/// Every bundled [`Algorithm`], along with its name, description and a way to create it.
+pub fn registry() -> &'static [AlgorithmEntry] {
+    REGISTRY
+}
+
+/// Create the bundled [`Algorithm`] registered under `name` (c.f. [`registry`]).
+pub fn by_name(name: &str) -> Result<Box<dyn Algorithm>, UnknownAlgorithm> {
+    create(REGISTRY, name)
+}
+
+fn create(entries: &[AlgorithmEntry], name: &str) -> Result<Box<dyn Algorithm>, UnknownAlgorithm> {
+    entries
+        .iter()
+        .find(|entry| entry.name() == name)
+        .map(AlgorithmEntry::create)
+        .ok_or_else(|| UnknownAlgorithm {
+            name: name.into(),
+            expected: entries.iter().map(AlgorithmEntry::name).collect(),
+        })
+}
+
+/// A set of [`Algorithm`]s that can be created by name, starting with the bundled ones.
+/// Allows applications to offer their own algorithms, or those of plugins with the `plugins` feature, next to the bundled ones.
+#[derive(Debug, Clone)]
+pub struct Registry {
+    entries: Vec<AlgorithmEntry>,
+}
+
+impl Registry {
+    /// Constructor, holding the bundled algorithms (c.f. [`registry`]).
+    pub fn new() -> Self {
+        let entries = REGISTRY.to_vec();
+        Self { entries }
+    }
+
+    /// Add an algorithm, replacing the one of the same name if any.
+    pub fn register(&mut self, entry: AlgorithmEntry) {
+        match self.entries.iter_mut().find(|e| e.name() == entry.name()) {
+            Some(existing) => *existing = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    /// Every registered algorithm, the bundled ones first.
+    pub fn entries(&self) -> &[AlgorithmEntry] {
+        &self.entries
+    }
+
+    /// Create the [`Algorithm`] registered under `name`.
+    pub fn create(&self, name: &str) -> Result<Box<dyn Algorithm>, UnknownAlgorithm> {
+        create(&self.entries, name)
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Error returned by [`by_name`] and [`Registry::create`] when no [`Algorithm`] has the requested name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnknownAlgorithm {
+    name: String,
+    expected: Vec<&'static str>,
+}
+
+impl UnknownAlgorithm {
+    /// The name that was looked up.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Display for UnknownAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown algorithm \"{}\", expected one of:", self.name)?;
+        for (index, name) in self.expected.iter().enumerate() {
+            let separator = if index == 0 { "" } else { "," };
+            write!(f, "{separator} \"{name}\"")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnknownAlgorithm {}
+
+#[cfg(all(feature = "std", feature = "generate"))]
 #[test]
 fn depth_first() {
     use crate::{Executor, SimpleGenerator};
     let algorithm = DepthFirst::new();
-    let mut executor = Executor::build(algorithm, |b| b.generated(SimpleGenerator::new(40, 20)));
-    executor.run();
+    let mut executor = Executor::build(algorithm, |b| {
+        b.generated(SimpleGenerator::new(40, 20)).delay_ms(0)
+    })
+    .unwrap();
+    executor.run().unwrap();
 }
 
+#[cfg(all(feature = "std", feature = "generate"))]
 #[test]
 fn breath_first() {
     use crate::{Executor, SimpleGenerator};
     let algorithm = BreathFirst::new();
-    let mut executor = Executor::build(algorithm, |b| b.generated(SimpleGenerator::new(40, 20)));
-    executor.run();
+    let mut executor = Executor::build(algorithm, |b| {
+        b.generated(SimpleGenerator::new(40, 20)).delay_ms(0)
+    })
+    .unwrap();
+    executor.run().unwrap();
+}
+
+#[cfg(all(feature = "std", feature = "generate"))]
+#[test]
+fn registry_solves() {
+    use crate::{executor::drive, MazeGenerator, SimpleGenerator};
+    let maze = SimpleGenerator::new(20, 10).generate().unwrap();
+    for entry in registry() {
+        let mut algorithm = entry.create();
+        assert!(
+            drive(&maze, algorithm.as_mut(), Default::default(), |_, _| ()).is_ok(),
+            "{}",
+            entry.name()
+        );
+    }
+}
+
+#[test]
+fn lookup() {
+    assert!(by_name("breath-first").is_ok());
+    let error = by_name("random").err().unwrap();
+    assert_eq!(error.name(), "random");
+    assert!(alloc::format!("{error}").starts_with(
+        "unknown algorithm \"random\", expected one of: \"depth-first\", \"breath-first\""
+    ));
+}
+
+#[test]
+fn registration() {
+    let mut registry = Registry::new();
+    assert_eq!(registry.entries().len(), REGISTRY.len());
+    registry.register(AlgorithmEntry::new(
+        "copy",
+        "breath-first under another name",
+        || BreathFirst::new().boxed(),
+    ));
+    registry.register(AlgorithmEntry::new("depth-first", "replaced", || {
+        BreathFirst::new().boxed()
+    }));
+    assert_eq!(registry.entries().len(), REGISTRY.len() + 1);
+    assert_eq!(registry.entries()[0].description(), "replaced");
+    assert!(registry.create("copy").is_ok());
+    let error = alloc::format!("{}", registry.create("random").err().unwrap());
+    assert!(error.starts_with("unknown algorithm \"random\", expected one of: \"depth-first\""));
+    assert!(error.ends_with(", \"copy\""));
+}
+
+#[cfg(all(feature = "std", feature = "generate"))]
+#[test]
+fn several_ends() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::{braid, executor::drive, MazeGenerator, Pos, SimpleGenerator};
+
+    // solvers finding a shortest path to the closest end, and one only reaching any of them
+    let shortest = [
+        "breath-first",
+        "bidirectional",
+        "ida-star",
+        "jump-point",
+        "wavefront",
+        "fringe",
+    ];
+    for seed in 0..40 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut maze = SimpleGenerator::new(10, 8).generate_seeded(seed).unwrap();
+        braid(&mut maze, 1.0, &mut rng);
+        let extra = Pos::new(rng.gen_range(1..10), rng.gen_range(1..8));
+        maze.try_add_end(extra).unwrap();
+        let field = maze.distance_field(maze.start());
+        let expected = maze.ends().filter_map(|end| field.distance(end)).min();
+
+        for name in shortest.into_iter().chain(["beam"]) {
+            let mut algorithm = by_name(name).unwrap();
+            let mut last = Vec::new();
+            drive(&maze, algorithm.as_mut(), Default::default(), |_, g| {
+                last = g
+            })
+            .unwrap();
+            assert!(maze.is_end(*last.last().unwrap()), "{name}, seed {seed}");
+            if name != "beam" {
+                assert_eq!(Some(last.len() - 1), expected, "{name}, seed {seed}");
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "std", feature = "generate"))]
+#[test]
+fn topologies_shortest() {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{braid, executor::drive, MazeGenerator, SimpleGenerator, Topology};
+
+    // the estimations never overestimate, paths crossing the edges of toroidal mazes or going diagonally included
+    let shortest = [
+        "breath-first",
+        "bidirectional",
+        "ida-star",
+        "wavefront",
+        "fringe",
+    ];
+    for topology in [Topology::Hex, Topology::Torus, Topology::Diagonal] {
+        for seed in 0..20 {
+            let mut maze = SimpleGenerator::new(10, 8)
+                .topology(topology)
+                .generate_seeded(seed)
+                .unwrap();
+            braid(&mut maze, 1.0, &mut StdRng::seed_from_u64(seed));
+            let expected = maze.distance_field(maze.start()).distance(maze.end());
+
+            for name in shortest {
+                let mut last = Vec::new();
+                let mut algorithm = by_name(name).unwrap();
+                drive(&maze, algorithm.as_mut(), Default::default(), |_, g| {
+                    last = g
+                })
+                .unwrap();
+                assert_eq!(Some(last.len() - 1), expected, "{topology:?} {name} {seed}");
+            }
+        }
+    }
+}
+
+#[test]
+fn locked_doors() {
+    use alloc::vec;
+
+    use crate::{
+        executor::{drive, Rules},
+        Error, Maze, Pos,
+    };
+
+    // the key opening the door east of the start lies behind it
+    //  a b|c
+    //  d e f
+    let [a, b, c] = [0, 1, 2].map(|x| Pos::new(x, 0));
+    let f = Pos::new(2, 1);
+    let paths = vec![(a, vec![b]), (b, vec![c]), (c, vec![f])];
+    let mut maze = Maze::new(3, 2, b, f, paths);
+    maze.try_add_door(b, c, 0).unwrap();
+    maze.try_add_key(a, 0).unwrap();
+
+    // solvers never go through the door before holding its key, nor search forever
+    let rules = Rules {
+        max_ticks: Some(1000),
+        ..Default::default()
+    };
+    for entry in registry() {
+        let mut algorithm = entry.create();
+        let result = drive(&maze, algorithm.as_mut(), rules, |_, _| ());
+        let invalid = matches!(result, Err(Error::InvalidGuess { .. }));
+        assert!(!invalid, "{}: {result:?}", entry.name());
+    }
+
+    // searching over the keys collected, the key is fetched before going through the door
+    let mut last = Vec::new();
+    let mut algorithm = by_name("ida-star").unwrap();
+    drive(&maze, algorithm.as_mut(), Default::default(), |_, g| {
+        last = g
+    })
+    .unwrap();
+    assert_eq!(last, vec![b, a, b, c, f]);
 }
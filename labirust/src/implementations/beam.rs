@@ -0,0 +1,144 @@
+use alloc::{collections::VecDeque, vec, vec::Vec};
+use core::cmp::Reverse;
+
+use super::{ida_star::estimate, Heuristic};
+use crate::{Algorithm, Context, Grid, Guess, Insight, Pos, VisitedSet};
+
+/// [`Algorithm`] performing a breath-first search level by level, only keeping the `width` best positions of each level.
+/// Positions are ranked by their [`Heuristic`] estimation of the distance to the closest end, the others being set aside.
+/// When the beam dead-ends, it is refilled with the best positions set aside so far, so that the end is always found when reachable.
+#[derive(Debug, Clone)]
+pub struct BeamSearch {
+    width: usize,
+    heuristic: Option<Heuristic>,
+    beam: VecDeque<Pos>,
+    candidates: Vec<Pos>,
+    pruned: Vec<Pos>,
+    discovered: Option<VisitedSet>,
+    parents: Option<Grid<Option<Pos>>>,
+}
+
+impl BeamSearch {
+    /// Constructor, keeping `width` positions per level, at least one, and estimating distances with [`Context::min_distance_to_end`].
+    pub fn new(width: usize) -> Self {
+        Self::from_heuristic(width, None)
+    }
+
+    /// Constructor, keeping `width` positions per level, at least one, and estimating distances with `heuristic`.
+    pub fn with_heuristic(width: usize, heuristic: Heuristic) -> Self {
+        Self::from_heuristic(width, Some(heuristic))
+    }
+
+    fn from_heuristic(width: usize, heuristic: Option<Heuristic>) -> Self {
+        Self {
+            width: width.max(1),
+            heuristic,
+            beam: VecDeque::new(),
+            candidates: Vec::new(),
+            pruned: Vec::new(),
+            discovered: None,
+            parents: None,
+        }
+    }
+
+    /// Number of positions kept per level.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Rebuild the path leading to `position` by following parents back to the start.
+    fn path_to(parents: &Grid<Option<Pos>>, position: Pos) -> Vec<Pos> {
+        let mut path = vec![position];
+        let mut current = position;
+        while let Some(parent) = parents[current] {
+            path.push(parent);
+            current = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Fill the beam with the best `width` candidates, or positions set aside, setting the others aside.
+    fn refill(&mut self, from_pruned: bool, ctx: &Context) {
+        let heuristic = self.heuristic;
+        let positions = match from_pruned {
+            true => &mut self.pruned,
+            false => &mut self.candidates,
+        };
+        positions.sort_by_key(|&position| Reverse(estimate(heuristic, ctx, position)));
+        let kept = positions.len().saturating_sub(self.width);
+        self.beam.extend(positions.drain(kept..).rev());
+        if !from_pruned {
+            self.pruned.append(&mut self.candidates);
+        }
+    }
+}
+
+impl Default for BeamSearch {
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+impl Algorithm for BeamSearch {
+    fn progress(&mut self, insight: &Insight, ctx: &mut Context) -> Guess {
+        let discovered = self.discovered.get_or_insert_with(|| ctx.new_visited_set());
+        let (width, height) = ctx.size();
+        let parents = self
+            .parents
+            .get_or_insert_with(|| Grid::new(width, height, None));
+
+        let position = insight.position();
+        discovered.insert(position);
+        for &branch in insight.paths() {
+            if discovered.insert(branch) {
+                parents[branch] = Some(position);
+                self.candidates.push(branch);
+            }
+        }
+
+        // the current level is over, select the next one
+        if self.beam.is_empty() {
+            self.refill(false, ctx);
+        }
+        if self.beam.is_empty() {
+            self.refill(true, ctx);
+        }
+        let Some(next) = self.beam.pop_front() else {
+            return ctx.give_up();
+        };
+        let parents = self.parents.as_ref().expect("initialized");
+        ctx.guess(Self::path_to(parents, next))
+    }
+}
+
+#[test]
+fn beam_search() {
+    use alloc::vec;
+
+    use crate::{
+        executor::{drive, Rules},
+        Error, Maze,
+    };
+
+    // the closest position to the end is a dead end, the way around is set aside
+    //  s a e
+    //  b c d
+    let (s, a, e) = (Pos::new(0, 0), Pos::new(1, 0), Pos::new(2, 0));
+    let (b, c, d) = (Pos::new(0, 1), Pos::new(1, 1), Pos::new(2, 1));
+    let paths = vec![(s, vec![a, b]), (b, vec![c]), (c, vec![d]), (d, vec![e])];
+    let maze = Maze::new(3, 2, s, e, paths);
+
+    let mut guesses = Vec::new();
+    let ticks = drive(&maze, &mut BeamSearch::new(1), Rules::default(), |_, g| {
+        guesses.push(g)
+    });
+    assert_eq!(ticks.unwrap(), 5);
+    assert_eq!(guesses[0], vec![s, a]);
+    assert_eq!(guesses[1], vec![s, b]);
+    assert_eq!(guesses[4], vec![s, b, c, d, e]);
+
+    let maze = Maze::new(3, 2, s, e, vec![(s, vec![a, b])]);
+    let result = drive(&maze, &mut BeamSearch::new(1), Rules::default(), |_, _| ());
+    assert!(matches!(result, Err(Error::Unsolvable)));
+}
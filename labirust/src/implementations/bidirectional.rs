@@ -0,0 +1,167 @@
+use alloc::{collections::VecDeque, vec, vec::Vec};
+
+use crate::{Algorithm, Anchor, Context, Grid, Guess, Insight, Pos, VisitedSet};
+
+/// [`Algorithm`] running two breath-first searches at once, one from the start and one from the ends of the [`crate::Maze`].
+/// Ticks alternate between both searches, guessing paths anchored at the start or at the end (c.f. [`Context::guess_from_end`]),
+/// until a position discovered by one search is reached by the other, the two halves then forming a shortest path.
+/// Explores fewer positions than [`super::BreathFirst`] when the maze branches a lot.
+#[derive(Debug, Clone)]
+pub struct Bidirectional {
+    sides: Option<[Side; 2]>,
+    current: Anchor,
+}
+
+/// One of the two searches, growing from its anchor.
+#[derive(Debug, Clone)]
+struct Side {
+    frontier: VecDeque<Pos>,
+    discovered: VisitedSet,
+    parents: Grid<Option<Pos>>,
+}
+
+impl Side {
+    fn new(ctx: &Context) -> Self {
+        let (width, height) = ctx.size();
+        Self {
+            frontier: VecDeque::new(),
+            discovered: ctx.new_visited_set(),
+            parents: Grid::new(width, height, None),
+        }
+    }
+
+    /// Rebuild the path leading from the anchor to `position` by following parents back.
+    fn path_to(&self, position: Pos) -> Vec<Pos> {
+        let mut path = vec![position];
+        let mut current = position;
+        while let Some(parent) = self.parents[current] {
+            path.push(parent);
+            current = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+impl Bidirectional {
+    /// Constructor.
+    pub fn new() -> Self {
+        Self {
+            sides: None,
+            current: Anchor::Start,
+        }
+    }
+
+    fn index(anchor: Anchor) -> usize {
+        match anchor {
+            Anchor::Start => 0,
+            Anchor::End => 1,
+        }
+    }
+
+    fn opposite(anchor: Anchor) -> Anchor {
+        match anchor {
+            Anchor::Start => Anchor::End,
+            Anchor::End => Anchor::Start,
+        }
+    }
+
+    /// Join the path from the start to `from_start` with the one from `from_end` to the end, both positions being the same or adjacent.
+    fn join(sides: &[Side; 2], from_start: Pos, from_end: Pos) -> Vec<Pos> {
+        let mut path = sides[0].path_to(from_start);
+        let mut rest = sides[1].path_to(from_end);
+        rest.reverse();
+        let skip = usize::from(from_start == from_end);
+        path.extend_from_slice(&rest[skip..]);
+        path
+    }
+}
+
+impl Default for Bidirectional {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Algorithm for Bidirectional {
+    fn progress(&mut self, insight: &Insight, ctx: &mut Context) -> Guess {
+        let sides = self.sides.get_or_insert_with(|| {
+            let mut from_end = Side::new(ctx);
+            for end in ctx.ends() {
+                from_end.discovered.insert(end);
+                from_end.frontier.push_back(end);
+            }
+            [Side::new(ctx), from_end]
+        });
+
+        let current = self.current;
+        let (this, other) = (Self::index(current), Self::index(Self::opposite(current)));
+        let position = insight.position();
+        sides[this].discovered.insert(position);
+        let meeting = core::iter::once(&position)
+            .chain(insight.paths())
+            .find(|&&branch| sides[other].discovered.contains(branch));
+        if let Some(&meeting) = meeting {
+            let path = match current {
+                Anchor::Start => Self::join(sides, position, meeting),
+                Anchor::End => Self::join(sides, meeting, position),
+            };
+            return ctx.guess(path);
+        }
+
+        for &branch in insight.paths() {
+            let side = &mut sides[this];
+            if side.discovered.insert(branch) {
+                side.parents[branch] = Some(position);
+                side.frontier.push_back(branch);
+            }
+        }
+
+        // alternate between both sides, falling back to the current one when the other is exhausted
+        for anchor in [Self::opposite(current), current] {
+            let side = &mut sides[Self::index(anchor)];
+            if let Some(next) = side.frontier.pop_front() {
+                self.current = anchor;
+                let path = side.path_to(next);
+                return match anchor {
+                    Anchor::Start => ctx.guess(path),
+                    Anchor::End => ctx.guess_from_end(path),
+                };
+            }
+        }
+        ctx.give_up()
+    }
+}
+
+#[test]
+fn bidirectional() {
+    use crate::{
+        executor::{drive, Rules},
+        Maze,
+    };
+
+    // a straight corridor, each side taking its turn until they meet in the middle
+    let cells: Vec<_> = (0..5).map(|x| Pos::new(x, 0)).collect();
+    let paths = cells.windows(2).map(|w| (w[0], vec![w[1]])).collect();
+    let maze = Maze::new(5, 1, cells[0], cells[4], paths);
+    let mut guesses = Vec::new();
+    let ticks = drive(
+        &maze,
+        &mut Bidirectional::new(),
+        Rules::default(),
+        |_, g| guesses.push(g),
+    );
+    assert_eq!(ticks.unwrap(), 4);
+    assert_eq!(guesses.first().unwrap(), &vec![cells[4]]);
+    assert_eq!(guesses.last().unwrap(), &cells);
+
+    // the start being the end, solved without any tick
+    let maze = Maze::new(1, 1, Pos::zero(), Pos::zero(), Vec::new());
+    let ticks = drive(
+        &maze,
+        &mut Bidirectional::new(),
+        Rules::default(),
+        |_, _| (),
+    );
+    assert_eq!(ticks.unwrap(), 0);
+}
@@ -1,42 +1,67 @@
-use std::collections::{HashSet, VecDeque};
+use alloc::{collections::VecDeque, vec, vec::Vec};
 
-use crate::{Algorithm, Context, Guess, Insight, Pos};
+use crate::{Algorithm, Context, Grid, Guess, Insight, Pos, VisitedSet};
 
 /// [`Algorithm`] traversing the [`crate::Maze`] as a common graph.
-/// Storing each possible paths form shortest to longest and extending the shortest ones first.
+/// Storing each discovered position from closest to farthest and extending the closest ones first.
+/// Paths are not stored but rebuilt from the parent of each discovered position, keeping memory linear in the size of the maze.
 /// Most effective when the resolution is among the shortest possible paths.
+#[derive(Debug, Clone)]
 pub struct BreathFirst {
-    paths: VecDeque<Vec<Pos>>,
-    visited: HashSet<Pos>,
-    last_path: Vec<Pos>,
+    frontier: VecDeque<Pos>,
+    discovered: Option<VisitedSet>,
+    parents: Option<Grid<Option<Pos>>>,
 }
 
 impl BreathFirst {
     /// Constructor.
     pub fn new() -> Self {
         Self {
-            paths: VecDeque::new(),
-            visited: HashSet::new(),
-            last_path: Vec::new(),
+            frontier: VecDeque::new(),
+            discovered: None,
+            parents: None,
         }
     }
+
+    /// Rebuild the path leading to `position` by following parents back to the start.
+    fn path_to(parents: &Grid<Option<Pos>>, position: Pos) -> Vec<Pos> {
+        let mut path = vec![position];
+        let mut current = position;
+        while let Some(parent) = parents[current] {
+            path.push(parent);
+            current = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+impl Default for BreathFirst {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Algorithm for BreathFirst {
     fn progress(&mut self, insight: &Insight, ctx: &mut Context) -> Guess {
-        self.visited.insert(insight.position());
-        let path = self.last_path.clone();
+        let discovered = self.discovered.get_or_insert_with(|| ctx.new_visited_set());
+        let (width, height) = ctx.size();
+        let parents = self
+            .parents
+            .get_or_insert_with(|| Grid::new(width, height, None));
+
+        let position = insight.position();
+        discovered.insert(position);
         for &branch in insight.paths() {
-            if self.visited.contains(&branch) {
-                continue;
+            if discovered.insert(branch) {
+                parents[branch] = Some(position);
+                self.frontier.push_back(branch);
             }
-            let mut new_path = path.clone();
-            new_path.push(branch);
-            self.paths.push_back(new_path);
         }
 
-        let path = self.paths.pop_front().expect("no more options");
-        self.last_path = path.clone();
-        ctx.guess(path)
+        let Some(next) = self.frontier.pop_front() else {
+            return ctx.give_up();
+        };
+        ctx.guess(Self::path_to(parents, next))
     }
 }
@@ -1,8 +1,9 @@
-use std::collections::HashSet;
+use alloc::vec::Vec;
 
-use crate::{Algorithm, Context, Guess, Insight, Pos};
+use crate::{Algorithm, Context, Guess, Insight, Pos, VisitedSet};
 
 /// Frame of the stack used by a [`DepthFirst`] to retain its path and possible branches.
+#[derive(Debug, Clone)]
 pub struct Frame {
     position: Pos,
     remaining_branches: Vec<Pos>,
@@ -10,8 +11,9 @@ pub struct Frame {
 
 /// [`Algorithm`] driving the resolution of a [`crate::Maze`] traversing it as a common graph in a depth-first fashion.
 /// Stores the current path and possible branches in a stack.
+#[derive(Debug, Clone)]
 pub struct DepthFirst {
-    visited: HashSet<Pos>,
+    visited: Option<VisitedSet>,
     stack: Vec<Frame>,
 }
 
@@ -19,27 +21,36 @@ impl DepthFirst {
     /// constructor.
     pub fn new() -> Self {
         Self {
-            visited: HashSet::new(),
+            visited: None,
             stack: Vec::new(),
         }
     }
 }
 
+impl Default for DepthFirst {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Algorithm for DepthFirst {
     fn progress(&mut self, insight: &Insight, ctx: &mut Context) -> Guess {
         let position = insight.position();
-        let branches = insight.paths().iter().cloned().collect();
+        let branches = insight.paths().to_vec();
 
-        self.visited.insert(position);
+        let visited = self.visited.get_or_insert_with(|| ctx.new_visited_set());
+        visited.insert(position);
         self.stack.push(Frame {
             position,
             remaining_branches: branches,
         });
 
         loop {
-            let last = self.stack.last_mut().expect("no more options");
+            let Some(last) = self.stack.last_mut() else {
+                return ctx.give_up();
+            };
             if let Some(branch) = last.remaining_branches.pop() {
-                if !self.visited.contains(&branch) {
+                if !visited.contains(branch) {
                     let mut path: Vec<_> = self.stack.iter().map(|f| f.position).collect();
                     path.push(branch);
                     return ctx.guess(path);
@@ -50,3 +61,28 @@ impl Algorithm for DepthFirst {
         }
     }
 }
+
+#[test]
+fn progress() {
+    use crate::Maze;
+
+    // a corridor going east
+    let maze = Maze::new(
+        3,
+        1,
+        Pos::zero(),
+        Pos::new(2, 0),
+        alloc::vec![(Pos::zero(), alloc::vec![Pos::new(1, 0)])],
+    );
+    let mut algorithm = DepthFirst::new();
+    let mut ctx = Context::new(&maze);
+    // insights can be kept across ticks
+    let first = Insight::from_position(maze.start(), &maze);
+    let queued = alloc::vec![first.clone()];
+    let guess = algorithm.progress(&first, &mut ctx);
+    assert_eq!(guess.path(), &[Pos::zero(), Pos::new(1, 0)]);
+    // the corridor is walled before the end
+    let guess = algorithm.progress(&Insight::new(Pos::new(1, 0), [Pos::zero()]), &mut ctx);
+    assert!(guess.is_give_up());
+    assert_eq!(queued[0].clone().into_paths().as_slice(), &[Pos::new(1, 0)]);
+}
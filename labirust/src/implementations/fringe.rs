@@ -0,0 +1,190 @@
+use alloc::{collections::VecDeque, vec, vec::Vec};
+
+use super::{ida_star::estimate, Heuristic};
+use crate::{Algorithm, Context, Grid, Guess, Insight, Pos, VisitedSet};
+
+/// [`Algorithm`] performing a fringe search, an alternative to [`IdaStar`](super::IdaStar) keeping its fringe between iterations.
+/// Positions whose cost, their distance from the start plus the [`Heuristic`] estimation, exceeds a threshold are deferred to the next iteration,
+/// which raises the threshold to the smallest cost deferred, instead of searching again from the start.
+/// Positions discovered during a previous iteration are expanded again without spending a tick.
+#[derive(Debug, Clone)]
+pub struct FringeSearch {
+    heuristic: Option<Heuristic>,
+    threshold: usize,
+    next_threshold: Option<usize>,
+    now: VecDeque<Pos>,
+    later: VecDeque<Pos>,
+    tables: Option<Tables>,
+    expansions: usize,
+}
+
+/// Per-position state of a [`FringeSearch`], created on the first tick.
+#[derive(Debug, Clone)]
+struct Tables {
+    fringe: VisitedSet,
+    visited: VisitedSet,
+    costs: Grid<usize>,
+    parents: Grid<Option<Pos>>,
+}
+
+impl Tables {
+    fn new(ctx: &Context) -> Self {
+        let (width, height) = ctx.size();
+        let mut costs = Grid::new(width, height, usize::MAX);
+        costs[ctx.start()] = 0;
+        Self {
+            fringe: ctx.new_visited_set(),
+            visited: ctx.new_visited_set(),
+            costs,
+            parents: Grid::new(width, height, None),
+        }
+    }
+
+    /// Rebuild the path leading to `position` by following parents back to the start.
+    fn path_to(&self, position: Pos) -> Vec<Pos> {
+        let mut path = vec![position];
+        let mut current = position;
+        while let Some(parent) = self.parents[current] {
+            path.push(parent);
+            current = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Remove `position` from the fringe and insert the branches it improves right after it in `now`.
+    fn expand(&mut self, now: &mut VecDeque<Pos>, position: Pos, branches: &[Pos]) {
+        self.fringe.remove(position);
+        for &branch in branches.iter().rev() {
+            let cost = self.costs[position] + 1;
+            if cost >= self.costs[branch] {
+                continue;
+            }
+            self.costs[branch] = cost;
+            self.parents[branch] = Some(position);
+            self.fringe.insert(branch);
+            now.push_front(branch);
+        }
+    }
+}
+
+impl FringeSearch {
+    /// Constructor, estimating distances with [`Context::min_distance_to_end`], as [`IdaStar::new`](super::IdaStar::new).
+    pub fn new() -> Self {
+        Self::from_heuristic(None)
+    }
+
+    /// Constructor, estimating distances with `heuristic`, the estimation being the lowest over the ends.
+    /// Paths found are the shortest as long as it never overestimates the actual distance.
+    pub fn with_heuristic(heuristic: Heuristic) -> Self {
+        Self::from_heuristic(Some(heuristic))
+    }
+
+    fn from_heuristic(heuristic: Option<Heuristic>) -> Self {
+        Self {
+            heuristic,
+            threshold: 0,
+            next_threshold: None,
+            now: VecDeque::new(),
+            later: VecDeque::new(),
+            tables: None,
+            expansions: 0,
+        }
+    }
+
+    /// Number of positions expanded so far, positions expanded again after finding a shorter path to them included.
+    pub fn expansions(&self) -> usize {
+        self.expansions
+    }
+
+    /// Cost threshold of the current iteration.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+}
+
+impl Default for FringeSearch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Algorithm for FringeSearch {
+    fn progress(&mut self, insight: &Insight, ctx: &mut Context) -> Guess {
+        let position = insight.position();
+        let tables = self.tables.get_or_insert_with(|| {
+            self.threshold = estimate(self.heuristic, ctx, position);
+            Tables::new(ctx)
+        });
+        tables.visited.insert(position);
+        tables.expand(&mut self.now, position, insight.paths());
+        self.expansions += 1;
+
+        loop {
+            let Some(next) = self.now.pop_front() else {
+                // the iteration is over, start the next one with a higher threshold
+                let Some(next_threshold) = self.next_threshold.take() else {
+                    return ctx.give_up();
+                };
+                self.threshold = next_threshold;
+                core::mem::swap(&mut self.now, &mut self.later);
+                continue;
+            };
+            // positions inserted again are only handled once
+            if !tables.fringe.contains(next) {
+                continue;
+            }
+            let cost = tables.costs[next] + estimate(self.heuristic, ctx, next);
+            if cost > self.threshold {
+                let next_threshold = self.next_threshold.map_or(cost, |t| t.min(cost));
+                self.next_threshold = Some(next_threshold);
+                self.later.push_back(next);
+                continue;
+            }
+            if !tables.visited.contains(next) {
+                return ctx.guess(tables.path_to(next));
+            }
+            let keys = ctx.keys_along(&tables.path_to(next));
+            let branches = ctx.unlocked_paths_from(next, &keys).expect("visited");
+            tables.expand(&mut self.now, next, &branches);
+            self.expansions += 1;
+        }
+    }
+}
+
+#[test]
+fn fringe_search() {
+    use crate::{
+        executor::{drive, Rules},
+        Error, Maze,
+    };
+
+    // a corridor going east then back west below, the end being right under the start
+    //  a b c
+    //  f e d
+    let (a, b, c) = (Pos::new(0, 0), Pos::new(1, 0), Pos::new(2, 0));
+    let (d, e, f) = (Pos::new(2, 1), Pos::new(1, 1), Pos::new(0, 1));
+    let paths = vec![
+        (a, vec![b]),
+        (b, vec![c]),
+        (c, vec![d]),
+        (d, vec![e]),
+        (e, vec![f]),
+    ];
+    let maze = Maze::new(3, 2, a, f, paths);
+
+    let mut algorithm = FringeSearch::new();
+    let mut last = Vec::new();
+    let ticks = drive(&maze, &mut algorithm, Rules::default(), |_, guess| {
+        last = guess
+    });
+    // unlike iterative deepening, positions are only expanded once
+    assert_eq!(ticks.unwrap(), 5);
+    assert_eq!(last, vec![a, b, c, d, e, f]);
+    assert_eq!(algorithm.expansions(), 5);
+    assert_eq!(algorithm.threshold(), 5);
+
+    let maze = Maze::new(3, 2, a, f, vec![(a, vec![b]), (b, vec![c])]);
+    let result = drive(&maze, &mut FringeSearch::new(), Rules::default(), |_, _| ());
+    assert!(matches!(result, Err(Error::Unsolvable)));
+}
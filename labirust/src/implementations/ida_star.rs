@@ -0,0 +1,188 @@
+use alloc::vec::Vec;
+
+use crate::{Algorithm, Context, Guess, Insight, KeyId, Neighbors, Pos, VisitedSet};
+
+/// Estimation of the length of the path leading from a position to an end of the [`crate::Maze`], c.f. [`IdaStar::with_heuristic`].
+pub type Heuristic = fn(Pos, Pos) -> usize;
+
+/// Estimation by `heuristic` of the length of the path leading from `position` to the closest end of the [`crate::Maze`],
+/// or [`Context::min_distance_to_end`] without one.
+pub(super) fn estimate(heuristic: Option<Heuristic>, ctx: &Context, position: Pos) -> usize {
+    let Some(heuristic) = heuristic else {
+        return ctx.min_distance_to_end(position);
+    };
+    let estimations = ctx.ends().map(|end| heuristic(position, end));
+    estimations.min().unwrap_or(0)
+}
+
+/// Frame of the stack used by an [`IdaStar`] to retain its path and the branches left to explore.
+#[derive(Debug, Clone)]
+struct Frame {
+    position: Pos,
+    keys: Vec<KeyId>,
+    remaining_branches: Neighbors,
+}
+
+/// [`Algorithm`] performing an iterative deepening A* search.
+/// Repeats depth-first searches from the start, pruning the branches whose cost, their depth plus the [`Heuristic`] estimation, exceeds a threshold.
+/// Each search raises the threshold to the smallest cost pruned by the previous one, memory being bound to the length of the current path.
+/// Positions discovered during a previous search are walked through again without spending a tick.
+/// Searches over positions along with the keys collected on the way to them, walking back to the doors they open.
+#[derive(Debug, Clone)]
+pub struct IdaStar {
+    heuristic: Option<Heuristic>,
+    threshold: Option<usize>,
+    next_threshold: Option<usize>,
+    visited: Option<VisitedSet>,
+    stack: Vec<Frame>,
+}
+
+impl IdaStar {
+    /// Constructor, estimating distances with [`Context::min_distance_to_end`], which accounts for the topology of the maze.
+    pub fn new() -> Self {
+        Self::from_heuristic(None)
+    }
+
+    /// Constructor, estimating distances with `heuristic`, the estimation being the lowest over the ends.
+    /// Paths found are the shortest as long as it never overestimates the actual distance.
+    pub fn with_heuristic(heuristic: Heuristic) -> Self {
+        Self::from_heuristic(Some(heuristic))
+    }
+
+    fn from_heuristic(heuristic: Option<Heuristic>) -> Self {
+        Self {
+            heuristic,
+            threshold: None,
+            next_threshold: None,
+            visited: None,
+            stack: Vec::new(),
+        }
+    }
+
+    /// Manhattan distance between two positions, never overestimating the distance in a [`crate::Topology::Square`] maze without portals,
+    /// but overestimating it across the edges of a toroidal one.
+    pub fn manhattan(from: Pos, to: Pos) -> usize {
+        let (x, y) = (to - from).decompose();
+        x.unsigned_abs() + y.unsigned_abs()
+    }
+
+    /// Cost threshold of the current search, `None` before the first tick.
+    pub fn threshold(&self) -> Option<usize> {
+        self.threshold
+    }
+}
+
+impl Default for IdaStar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Algorithm for IdaStar {
+    fn progress(&mut self, insight: &Insight, ctx: &mut Context) -> Guess {
+        let visited = self.visited.get_or_insert_with(|| ctx.new_visited_set());
+        let position = insight.position();
+        visited.insert(position);
+        let threshold = self
+            .threshold
+            .get_or_insert_with(|| estimate(self.heuristic, ctx, position));
+        self.stack.push(Frame {
+            position,
+            keys: insight.keys().to_vec(),
+            remaining_branches: Neighbors::from_slice(insight.paths()),
+        });
+
+        loop {
+            let Some(last) = self.stack.last_mut() else {
+                // the search is over, start the next one with a higher threshold
+                let Some(next_threshold) = self.next_threshold.take() else {
+                    return ctx.give_up();
+                };
+                *threshold = next_threshold;
+                let start = ctx.start();
+                let keys: Vec<_> = ctx.key_at(start).into_iter().collect();
+                self.stack.push(Frame {
+                    position: start,
+                    remaining_branches: ctx.unlocked_paths_from(start, &keys).expect("visited"),
+                    keys,
+                });
+                continue;
+            };
+            let Some(branch) = last.remaining_branches.pop() else {
+                self.stack.pop();
+                continue;
+            };
+            let mut keys = last.keys.clone();
+            keys.extend(ctx.key_at(branch));
+            keys.sort_unstable();
+            keys.dedup();
+            // positions can be walked through again once a key was collected
+            let on_stack = |frame: &Frame| frame.position == branch && frame.keys == keys;
+            if self.stack.iter().any(on_stack) {
+                continue;
+            }
+            let cost = self.stack.len() + estimate(self.heuristic, ctx, branch);
+            if cost > *threshold {
+                let next = self.next_threshold.map_or(cost, |next| next.min(cost));
+                self.next_threshold = Some(next);
+                continue;
+            }
+            if visited.contains(branch) {
+                self.stack.push(Frame {
+                    position: branch,
+                    remaining_branches: ctx.unlocked_paths_from(branch, &keys).expect("visited"),
+                    keys,
+                });
+                continue;
+            }
+            let mut path: Vec<_> = self.stack.iter().map(|frame| frame.position).collect();
+            path.push(branch);
+            return ctx.guess(path);
+        }
+    }
+}
+
+#[test]
+fn ida_star() {
+    use alloc::vec;
+
+    use crate::{
+        executor::{drive, Rules},
+        Error, Maze,
+    };
+
+    // a corridor going east then back west below, the end being right under the start
+    //  a b c
+    //  f e d
+    let (a, b, c) = (Pos::new(0, 0), Pos::new(1, 0), Pos::new(2, 0));
+    let (d, e, f) = (Pos::new(2, 1), Pos::new(1, 1), Pos::new(0, 1));
+    let paths = vec![
+        (a, vec![b]),
+        (b, vec![c]),
+        (c, vec![d]),
+        (d, vec![e]),
+        (e, vec![f]),
+    ];
+    let maze = Maze::new(3, 2, a, f, paths);
+
+    let mut algorithm = IdaStar::new();
+    let mut last = Vec::new();
+    let ticks = drive(&maze, &mut algorithm, Rules::default(), |_, guess| {
+        last = guess
+    });
+    // every position is discovered once, the threshold growing from 1 to 5
+    assert_eq!(ticks.unwrap(), 5);
+    assert_eq!(last, vec![a, b, c, d, e, f]);
+    assert_eq!(algorithm.threshold(), Some(5));
+
+    // a heuristic ignoring the layout behaves as a plain iterative deepening search
+    let mut algorithm = IdaStar::with_heuristic(|_, _| 0);
+    assert_eq!(
+        drive(&maze, &mut algorithm, Rules::default(), |_, _| ()).unwrap(),
+        5
+    );
+
+    let maze = Maze::new(3, 2, a, f, vec![(a, vec![b]), (b, vec![c])]);
+    let result = drive(&maze, &mut IdaStar::new(), Rules::default(), |_, _| ());
+    assert!(matches!(result, Err(Error::Unsolvable)));
+}
@@ -0,0 +1,297 @@
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+use core::cmp::Reverse;
+
+use crate::{
+    path::direction_between, Algorithm, Context, Direction, Grid, Guess, Insight, Pos, Topology,
+    VisitedSet,
+};
+
+/// [`Algorithm`] performing an A* search over jump points only.
+/// Straight runs are skipped by looking ahead through the [`Context`] until a position where the shortest paths may turn,
+/// so that open areas are crossed in a single tick instead of being explored position by position.
+/// Under [`crate::Visibility::Discovered`], runs stop at the first position not known yet.
+/// Straight runs only make sense on [`Topology::Square`] mazes, other topologies are refused up front (c.f. [`Context::unsupported`]).
+/// Positions holding a portal are jump points, the portal being a jump of its own, so that paths found are the shortest ones
+/// as long as the portals are known from the start, as with [`crate::Visibility::Full`].
+#[derive(Debug, Clone)]
+pub struct JumpPointSearch {
+    open: BinaryHeap<Reverse<(usize, Pos)>>,
+    closed: Option<VisitedSet>,
+    costs: Option<Grid<usize>>,
+    /// The jump point each position is reached from, and whether it is reached through a portal.
+    parents: Option<Grid<Option<(Pos, bool)>>>,
+    /// Lower bound of the distance to the closest end through a portal, `usize::MAX` without any known portal.
+    through_portals: Option<usize>,
+}
+
+impl JumpPointSearch {
+    /// Constructor.
+    pub fn new() -> Self {
+        Self {
+            open: BinaryHeap::new(),
+            closed: None,
+            costs: None,
+            parents: None,
+            through_portals: None,
+        }
+    }
+
+    /// Check if the passage leaving `position` in `direction` is known to be open.
+    fn is_open(ctx: &Context, position: Pos, direction: Direction) -> bool {
+        ctx.is_walled(position, position + direction) == Some(false)
+    }
+
+    /// Check if a neighbor of `position`, reached in `direction` from the previous position, is only reachable through it as fast.
+    fn is_forced(ctx: &Context, position: Pos, direction: Direction) -> bool {
+        let previous = position - direction;
+        [direction.clockwise(), direction.counter_clockwise()]
+            .into_iter()
+            .any(|side| {
+                Self::is_open(ctx, position, side)
+                    && !(Self::is_open(ctx, previous, side)
+                        && Self::is_open(ctx, previous + side, direction))
+            })
+    }
+
+    /// Positions linked to `position` by a portal, empty if it is not known.
+    fn portals_from(ctx: &Context, position: Pos) -> Vec<Pos> {
+        let paths = ctx.paths_from(position).unwrap_or_default();
+        paths
+            .into_iter()
+            .filter(|&next| direction_between(position, next).is_none())
+            .collect()
+    }
+
+    /// Lower bound of the distance from any position to the closest end when going through a portal:
+    /// one step through the portal, then at least the distance from its exit.
+    fn through_portals(ctx: &Context) -> usize {
+        ctx.cells()
+            .flat_map(|(position, _)| Self::portals_from(ctx, position))
+            .map(|exit| 1 + Self::distance_to_end(ctx, exit))
+            .min()
+            .unwrap_or(usize::MAX)
+    }
+
+    /// Walk from `position` in `direction` until a jump point, `None` if the run ends without any.
+    fn jump(ctx: &Context, position: Pos, direction: Direction) -> Option<Pos> {
+        let mut current = position + direction;
+        loop {
+            if ctx.is_end(current)
+                || !ctx.is_known(current)
+                || Self::is_forced(ctx, current, direction)
+                || !Self::portals_from(ctx, current).is_empty()
+            {
+                return Some(current);
+            }
+            // horizontal runs stop where a vertical run leads to a jump point
+            if matches!(direction, Direction::East | Direction::West) {
+                let vertical = [Direction::North, Direction::South];
+                if vertical.into_iter().any(|side| {
+                    Self::is_open(ctx, current, side) && Self::jump(ctx, current, side).is_some()
+                }) {
+                    return Some(current);
+                }
+            }
+            if !Self::is_open(ctx, current, direction) {
+                return None;
+            }
+            current = current + direction;
+        }
+    }
+
+    /// Direction of the straight run leading from `from` to `to`.
+    fn heading(from: Pos, to: Pos) -> Option<Direction> {
+        let (x, y) = (to - from).decompose();
+        let unit = Pos::new(x.signum(), y.signum());
+        Direction::ALL
+            .into_iter()
+            .find(|direction| direction.offset() == unit)
+    }
+
+    /// Rebuild the path leading to `position`, filling the runs between jump points.
+    fn path_to(parents: &Grid<Option<(Pos, bool)>>, position: Pos) -> Vec<Pos> {
+        let mut path = vec![position];
+        let mut current = position;
+        while let Some((parent, portal)) = parents[current] {
+            if portal {
+                current = parent;
+                path.push(current);
+                continue;
+            }
+            let direction = Self::heading(current, parent).expect("straight run");
+            while current != parent {
+                current = current + direction;
+                path.push(current);
+            }
+        }
+        path.reverse();
+        path
+    }
+
+    fn distance(from: Pos, to: Pos) -> usize {
+        let (x, y) = (to - from).decompose();
+        x.unsigned_abs() + y.unsigned_abs()
+    }
+
+    /// Lowest distance from `position` to any of the ends.
+    fn distance_to_end(ctx: &Context, position: Pos) -> usize {
+        let distances = ctx.ends().map(|end| Self::distance(position, end));
+        distances.min().unwrap_or(0)
+    }
+}
+
+impl Default for JumpPointSearch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Algorithm for JumpPointSearch {
+    fn progress(&mut self, insight: &Insight, ctx: &mut Context) -> Guess {
+        if ctx.topology() != Topology::Square {
+            return ctx.unsupported();
+        }
+        let (width, height) = ctx.size();
+        let closed = self.closed.get_or_insert_with(|| ctx.new_visited_set());
+        let costs = self
+            .costs
+            .get_or_insert_with(|| Grid::new(width, height, usize::MAX));
+        let parents = self
+            .parents
+            .get_or_insert_with(|| Grid::new(width, height, None));
+
+        let through_portals = *self
+            .through_portals
+            .get_or_insert_with(|| Self::through_portals(ctx));
+
+        let position = insight.position();
+        if position == ctx.start() {
+            costs[position] = 0;
+        }
+        closed.insert(position);
+        let heading = match parents[position] {
+            Some((parent, false)) => Self::heading(parent, position),
+            _ => None,
+        };
+        for &branch in insight.paths() {
+            // portals are not part of straight runs, they lead to a jump point in a single step
+            let (jump_point, portal) = match direction_between(position, branch) {
+                None => (branch, true),
+                Some(direction) if heading == Some(direction.opposite()) => continue,
+                Some(direction) => match Self::jump(ctx, position, direction) {
+                    Some(jump_point) => (jump_point, false),
+                    None => continue,
+                },
+            };
+            let step = if portal {
+                1
+            } else {
+                Self::distance(position, jump_point)
+            };
+            let cost = costs[position] + step;
+            if cost < costs[jump_point] {
+                costs[jump_point] = cost;
+                parents[jump_point] = Some((position, portal));
+                let estimation = cost + Self::distance_to_end(ctx, jump_point).min(through_portals);
+                self.open.push(Reverse((estimation, jump_point)));
+            }
+        }
+
+        while let Some(Reverse((_, next))) = self.open.pop() {
+            if !closed.contains(next) {
+                return ctx.guess(Self::path_to(parents, next));
+            }
+        }
+        ctx.give_up()
+    }
+}
+
+#[test]
+fn jump_point_search() {
+    use crate::{
+        executor::{drive, Rules},
+        Error, Maze, MazeError,
+    };
+
+    // an open room, crossed in two runs
+    let inside = |position: Pos| position.x() < 5 && position.y() < 5;
+    let paths = (0..25)
+        .map(|index| Pos::from_index(index, 5))
+        .map(|position| {
+            let neighbors = [position + Direction::East, position + Direction::South];
+            (
+                position,
+                neighbors.into_iter().filter(|&p| inside(p)).collect(),
+            )
+        })
+        .collect();
+    let maze = Maze::new(5, 5, Pos::zero(), Pos::new(4, 4), paths);
+
+    let mut last = Vec::new();
+    let ticks = drive(
+        &maze,
+        &mut JumpPointSearch::new(),
+        Rules::default(),
+        |_, guess| last = guess,
+    );
+    assert_eq!(ticks.unwrap(), 2);
+    assert_eq!(last.len(), 9);
+    assert!(crate::path::is_adjacent_chain(&last));
+
+    let (a, b) = (Pos::zero(), Pos::new(1, 0));
+    let maze = Maze::new(3, 1, a, Pos::new(2, 0), vec![(a, vec![b])]);
+    let result = drive(
+        &maze,
+        &mut JumpPointSearch::new(),
+        Rules::default(),
+        |_, _| (),
+    );
+    assert!(matches!(result, Err(Error::Unsolvable)));
+
+    // runs would leave a toroidal maze instead of wrapping around it
+    let mut maze = Maze::try_new_torus(3, 3, a, Pos::new(2, 0)).unwrap();
+    maze.try_open_path(a, Pos::new(2, 0)).unwrap();
+    let result = drive(
+        &maze,
+        &mut JumpPointSearch::new(),
+        Rules::default(),
+        |_, _| (),
+    );
+    assert!(matches!(
+        result,
+        Err(Error::InvalidMaze(MazeError::UnsupportedTopology))
+    ));
+}
+
+#[cfg(all(feature = "std", feature = "generate"))]
+#[test]
+fn shortest() {
+    use crate::{
+        executor::{drive, Rules},
+        ImperfectGenerator, MazeGenerator,
+    };
+
+    // mazes with loops, then with a portal from near the start to near the end
+    for seed in 0..20 {
+        let mut maze = ImperfectGenerator::new(16, 12, 0.3)
+            .generate_seeded(seed)
+            .unwrap();
+        for portal in [false, true] {
+            if portal {
+                let (a, b) = (Pos::new(2, 1), Pos::new(12, 10));
+                maze.add_portal(a, b);
+                maze.add_portal(b, a);
+            }
+            let mut last = Vec::new();
+            drive(
+                &maze,
+                &mut JumpPointSearch::new(),
+                Rules::default(),
+                |_, guess| last = guess,
+            )
+            .unwrap();
+            let shortest = maze.shortest_path(maze.start(), maze.end()).unwrap();
+            assert_eq!(last.len(), shortest.len(), "seed {seed}, portal {portal}");
+        }
+    }
+}
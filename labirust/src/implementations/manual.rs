@@ -0,0 +1,121 @@
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use crate::{Algorithm, Context, Direction, Guess, Insight, Pos};
+
+/// A move sent to a [`ManualControl`] through its [`ManualHandle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Move {
+    /// Walk one cell in a direction, stepping back when it leads to the previous cell of the path.
+    Step(Direction),
+    /// Give up the resolution.
+    GiveUp,
+}
+
+/// [`Algorithm`] waiting for its moves on a channel, so that a player, a user interface or a remote program can drive the resolution.
+/// Each tick blocks until a move leading through an open passage is received, moves into walls are ignored.
+/// The resolution is given up when the [`ManualHandle`]s are all dropped.
+#[derive(Debug)]
+pub struct ManualControl {
+    moves: Receiver<Move>,
+    path: Vec<Pos>,
+}
+
+/// Handle sending moves to a [`ManualControl`], possibly from another thread.
+#[derive(Debug, Clone)]
+pub struct ManualHandle {
+    moves: Sender<Move>,
+}
+
+impl ManualControl {
+    /// Constructor, returns the algorithm along with the handle controlling it.
+    pub fn new() -> (Self, ManualHandle) {
+        let (sender, moves) = mpsc::channel();
+        let algorithm = Self {
+            moves,
+            path: Vec::new(),
+        };
+        (algorithm, ManualHandle { moves: sender })
+    }
+}
+
+impl ManualHandle {
+    /// Send a move, returns `false` if the [`ManualControl`] has been dropped.
+    pub fn send(&self, next: Move) -> bool {
+        self.moves.send(next).is_ok()
+    }
+
+    /// Walk one cell in `direction`, c.f. [`Move::Step`].
+    pub fn step(&self, direction: Direction) -> bool {
+        self.send(Move::Step(direction))
+    }
+
+    /// Give up the resolution, c.f. [`Move::GiveUp`].
+    pub fn give_up(&self) -> bool {
+        self.send(Move::GiveUp)
+    }
+}
+
+impl Algorithm for ManualControl {
+    fn progress(&mut self, insight: &Insight, ctx: &mut Context) -> Guess {
+        let position = insight.position();
+        if self.path.is_empty() {
+            self.path.push(position);
+        }
+        loop {
+            let Ok(Move::Step(direction)) = self.moves.recv() else {
+                return ctx.give_up();
+            };
+            let next = position + direction;
+            if !insight.paths().contains(&next) {
+                continue;
+            }
+            let previous = self.path.len().checked_sub(2).map(|i| self.path[i]);
+            if previous == Some(next) {
+                self.path.pop();
+            } else {
+                self.path.push(next);
+            }
+            return ctx.guess(self.path.clone());
+        }
+    }
+}
+
+#[test]
+fn manual() {
+    use std::thread;
+
+    use crate::{
+        executor::{drive, Rules},
+        Error, Maze,
+    };
+
+    // an L shaped corridor from the top left to the bottom right
+    let (a, b, c) = (Pos::zero(), Pos::new(1, 0), Pos::new(1, 1));
+    let maze = Maze::new(2, 2, a, c, vec![(a, vec![b]), (b, vec![c])]);
+
+    let (mut algorithm, handle) = ManualControl::new();
+    let player = thread::spawn(move || {
+        use Direction::*;
+        // the wall to the south is ignored, the player steps back once before reaching the end
+        for direction in [South, East, West, East, South] {
+            handle.step(direction);
+        }
+    });
+    let mut guesses = Vec::new();
+    let ticks = drive(&maze, &mut algorithm, Rules::default(), |_, g| {
+        guesses.push(g)
+    });
+    player.join().unwrap();
+    assert_eq!(ticks.unwrap(), 4);
+    assert_eq!(
+        guesses,
+        vec![vec![a, b], vec![a], vec![a, b], vec![a, b, c]]
+    );
+
+    // dropping the handle gives up
+    let (mut algorithm, handle) = ManualControl::new();
+    drop(handle);
+    let result = drive(&maze, &mut algorithm, Rules::default(), |_, _| ());
+    assert!(matches!(result, Err(Error::Unsolvable)));
+}
@@ -0,0 +1,154 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt;
+
+use crate::{Algorithm, Context, Guess, Insight, Pos, VisitedSet};
+
+/// What a [`Mock`] received on one call to `progress`.
+/// The [`Context`] borrows the maze, so what it exposes is copied instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockCall {
+    insight: Insight,
+    start: Pos,
+    end: Pos,
+    size: (isize, isize),
+    known: VisitedSet,
+}
+
+impl MockCall {
+    fn record(insight: &Insight, ctx: &Context) -> Self {
+        let (width, height) = ctx.size();
+        let mut known = ctx.new_visited_set();
+        for position in (0..height).flat_map(|y| (0..width).map(move |x| Pos::new(x, y))) {
+            if ctx.is_known(position) {
+                known.insert(position);
+            }
+        }
+        Self {
+            insight: insight.clone(),
+            start: ctx.start(),
+            end: ctx.end(),
+            size: ctx.size(),
+            known,
+        }
+    }
+
+    /// The insight given to the [`Mock`].
+    pub fn insight(&self) -> &Insight {
+        &self.insight
+    }
+
+    /// The start of the maze, as given by the [`Context`].
+    pub fn start(&self) -> Pos {
+        self.start
+    }
+
+    /// The end of the maze, as given by the [`Context`].
+    pub fn end(&self) -> Pos {
+        self.end
+    }
+
+    /// The size of the maze, as given by the [`Context`].
+    pub fn size(&self) -> (isize, isize) {
+        self.size
+    }
+
+    /// The positions whose layout was visible through the [`Context`] (c.f. [`Context::is_known`]).
+    pub fn known(&self) -> &VisitedSet {
+        &self.known
+    }
+}
+
+type Answer = Box<dyn FnMut(&Insight, &mut Context) -> Guess>;
+
+/// [`Algorithm`] recording every insight and context it receives, answering with a closure.
+/// Allows tests to check what an executor hands to algorithms, and to drive it with arbitrary guesses.
+pub struct Mock {
+    answer: Answer,
+    calls: Vec<MockCall>,
+}
+
+impl Mock {
+    /// Constructor, answering each call to `progress` with `answer`.
+    pub fn new<F>(answer: F) -> Self
+    where
+        F: FnMut(&Insight, &mut Context) -> Guess + 'static,
+    {
+        let answer = Box::new(answer);
+        let calls = Vec::new();
+        Self { answer, calls }
+    }
+
+    /// Every call received so far, in order.
+    pub fn calls(&self) -> &[MockCall] {
+        &self.calls
+    }
+
+    /// Forget the calls received so far.
+    pub fn clear(&mut self) {
+        self.calls.clear();
+    }
+}
+
+impl fmt::Debug for Mock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mock")
+            .field("calls", &self.calls)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Algorithm for Mock {
+    fn progress(&mut self, insight: &Insight, ctx: &mut Context) -> Guess {
+        self.calls.push(MockCall::record(insight, ctx));
+        (self.answer)(insight, ctx)
+    }
+}
+
+#[test]
+fn records() {
+    use alloc::vec;
+
+    use crate::{
+        executor::{drive, Rules},
+        Maze, Visibility,
+    };
+
+    // a corridor going east, walked one cell per tick
+    let maze = Maze::new(
+        3,
+        1,
+        Pos::zero(),
+        Pos::new(2, 0),
+        vec![
+            (Pos::zero(), vec![Pos::new(1, 0)]),
+            (Pos::new(1, 0), vec![Pos::new(2, 0)]),
+        ],
+    );
+    let mut path = vec![Pos::zero()];
+    let mut mock = Mock::new(move |insight, ctx| {
+        let next = *insight.paths().iter().find(|p| !path.contains(p)).unwrap();
+        path.push(next);
+        ctx.guess(path.clone())
+    });
+    let rules = Rules {
+        visibility: Visibility::Discovered,
+        ..Rules::default()
+    };
+    assert_eq!(drive(&maze, &mut mock, rules, |_, _| ()).unwrap(), 2);
+
+    let calls = mock.calls();
+    assert_eq!(calls.len(), 2);
+    assert_eq!(
+        calls[1].insight(),
+        &Insight::from_position(Pos::new(1, 0), &maze)
+    );
+    assert_eq!(
+        (calls[0].start(), calls[0].end(), calls[0].size()),
+        (Pos::zero(), Pos::new(2, 0), (3, 1))
+    );
+    // the fog of war lifts as the insights are given
+    assert_eq!(calls[0].known().len(), 1);
+    assert_eq!(calls[1].known().len(), 2);
+    mock.clear();
+    assert!(mock.calls().is_empty());
+}
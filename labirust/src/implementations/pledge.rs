@@ -0,0 +1,191 @@
+use alloc::{collections::BTreeSet, vec::Vec};
+
+use super::Hand;
+use crate::{Algorithm, Context, Direction, Guess, Insight, Pos, Topology};
+
+/// [`Algorithm`] walking towards a preferred heading, and following walls when it is blocked.
+/// While following a wall, turns are summed into an angle, and the wall is only left once the angle is back to zero with the preferred heading open.
+/// Unlike a [`super::WallFollower`], escapes walls standing apart from the others, such as pillars in the middle of loops.
+/// Gives up when it comes back to a position while walking straight towards the preferred heading, as it would then walk in circles,
+/// or when the angle grows past what following a single wall can reach, as it then circles a wall it can not leave.
+/// Headings and angles only make sense on [`Topology::Square`] mazes, other topologies are refused up front (c.f. [`Context::unsupported`]).
+#[derive(Debug, Clone)]
+pub struct Pledge {
+    preferred: Direction,
+    hand: Hand,
+    heading: Direction,
+    angle: isize,
+    states: BTreeSet<Pos>,
+    path: Vec<Pos>,
+}
+
+impl Pledge {
+    /// Constructor, walking towards `preferred` and following walls with `hand`.
+    pub fn new(preferred: Direction, hand: Hand) -> Self {
+        Self {
+            preferred,
+            hand,
+            heading: preferred,
+            angle: 0,
+            states: BTreeSet::new(),
+            path: Vec::new(),
+        }
+    }
+
+    /// The heading walked towards when not following a wall.
+    pub fn preferred(&self) -> Direction {
+        self.preferred
+    }
+
+    /// The hand kept on the wall.
+    pub fn hand(&self) -> Hand {
+        self.hand
+    }
+
+    /// Sum of the turns made while following the current wall, in clockwise quarter turns.
+    pub fn angle(&self) -> isize {
+        self.angle
+    }
+
+    /// Directions to try while following a wall by order of preference, along with the turn they make.
+    fn preferences(&self) -> [(Direction, isize); 4] {
+        let heading = self.heading;
+        let towards = match self.hand {
+            Hand::Left => -1,
+            Hand::Right => 1,
+        };
+        let turn = |quarters: isize| match quarters.rem_euclid(4) {
+            1 => heading.clockwise(),
+            2 => heading.opposite(),
+            3 => heading.counter_clockwise(),
+            _ => heading,
+        };
+        [
+            (turn(towards), towards),
+            (heading, 0),
+            (turn(-towards), -towards),
+            (turn(-2 * towards), -2 * towards),
+        ]
+    }
+}
+
+impl Default for Pledge {
+    fn default() -> Self {
+        Self::new(Direction::East, Hand::default())
+    }
+}
+
+impl Algorithm for Pledge {
+    fn progress(&mut self, insight: &Insight, ctx: &mut Context) -> Guess {
+        if ctx.topology() != Topology::Square {
+            return ctx.unsupported();
+        }
+        let position = insight.position();
+        if self.path.is_empty() {
+            self.path.push(position);
+        }
+        // the angle is zero once back to walking straight, the heading then being the preferred one
+        if self.angle == 0 && !self.states.insert(position) {
+            return ctx.give_up();
+        }
+        // a turn of the wall around every cell changes the angle by at most 2, each cell being walked around at most 4 times
+        let cells = ctx.width() * ctx.height();
+        if self.angle.abs() > 8 * cells {
+            return ctx.give_up();
+        }
+
+        let is_open = |direction| insight.paths().contains(&(position + direction));
+        let next = match self.angle == 0 && is_open(self.preferred) {
+            true => Some((self.preferred, 0)),
+            false => self
+                .preferences()
+                .into_iter()
+                .find(|&(direction, _)| is_open(direction)),
+        };
+        let Some((direction, turn)) = next else {
+            return ctx.give_up();
+        };
+        self.heading = direction;
+        self.angle += turn;
+
+        let next = position + direction;
+        match self.path.iter().rposition(|&position| position == next) {
+            Some(index) => self.path.truncate(index + 1),
+            None => self.path.push(next),
+        }
+        ctx.guess(self.path.clone())
+    }
+}
+
+#[test]
+fn pledge() {
+    use alloc::vec;
+
+    use crate::{
+        executor::{drive, Rules},
+        Error, Maze, MazeError,
+    };
+
+    // a ring around a pillar, the end being east of the ring
+    //  . . . #
+    //  s # . e
+    //  . . . #
+    let ring = [
+        (0, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+        (2, 1),
+        (2, 2),
+        (1, 2),
+        (0, 2),
+    ];
+    let ring: Vec<_> = ring.into_iter().map(|(x, y)| Pos::new(x, y)).collect();
+    let mut paths: Vec<_> = ring.windows(2).map(|w| (w[0], vec![w[1]])).collect();
+    paths.push((ring[7], vec![ring[0]]));
+    let (start, end) = (Pos::new(0, 1), Pos::new(3, 1));
+    paths.push((Pos::new(2, 1), vec![end]));
+    let maze = Maze::new(4, 3, start, end, paths);
+
+    let mut algorithm = Pledge::new(Direction::East, Hand::Right);
+    let mut last = Vec::new();
+    let ticks = drive(&maze, &mut algorithm, Rules::default(), |_, guess| {
+        last = guess
+    });
+    assert_eq!(ticks.unwrap(), 5);
+    assert_eq!(last, vec![start, ring[7], ring[6], ring[5], ring[4], end]);
+    assert_eq!(algorithm.angle(), 0);
+
+    // walking straight east would circle a toroidal maze forever
+    let maze = Maze::try_new_torus(3, 3, start, Pos::new(2, 2)).unwrap();
+    let result = drive(&maze, &mut Pledge::default(), Rules::default(), |_, _| ());
+    assert!(matches!(
+        result,
+        Err(Error::InvalidMaze(MazeError::UnsupportedTopology))
+    ));
+}
+
+#[cfg(all(feature = "std", feature = "generate"))]
+#[test]
+fn loops() {
+    use crate::{
+        executor::{drive, Rules},
+        Error, ImperfectGenerator, MazeGenerator,
+    };
+
+    // every maze is either solved or given up on, well before walking the whole maze many times
+    for seed in 0..30 {
+        let maze = ImperfectGenerator::new(14, 9, 0.3)
+            .generate_seeded(seed)
+            .unwrap();
+        let rules = Rules {
+            max_ticks: Some(20_000),
+            ..Rules::default()
+        };
+        let result = drive(&maze, &mut Pledge::default(), rules, |_, _| ());
+        assert!(
+            matches!(result, Ok(_) | Err(Error::Unsolvable)),
+            "{result:?}"
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::fmt;
+
+use crate::{Algorithm, Context, Guess, Insight};
+
+/// How a [`Portfolio`] shares the ticks between its members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Strategy {
+    /// Members take turns, one tick each, the first guess reaching the end winning the race.
+    Race,
+    /// Members run one after the other from the start, each one for at most `budget` ticks but the last.
+    Fallback { budget: usize },
+}
+
+/// [`Algorithm`] combining several others, either racing them or falling back from one to the next (c.f. [`Strategy`]).
+/// Each member is given the insights of its own guesses, as if it was running alone, and members giving up are skipped.
+/// The resolution is only given up once every member did.
+pub struct Portfolio {
+    members: Vec<Box<dyn Algorithm>>,
+    strategy: Strategy,
+    start: Option<Insight>,
+    insights: Vec<Option<Insight>>,
+    given_up: Vec<bool>,
+    current: Option<usize>,
+    ticks: usize,
+}
+
+impl Portfolio {
+    /// Constructor, with the members taking turns (c.f. [`Strategy::Race`]).
+    pub fn race(members: Vec<Box<dyn Algorithm>>) -> Self {
+        Self::new(members, Strategy::Race)
+    }
+
+    /// Constructor, with each member running for at most `budget` ticks before the next one (c.f. [`Strategy::Fallback`]).
+    pub fn fallback(members: Vec<Box<dyn Algorithm>>, budget: usize) -> Self {
+        Self::new(members, Strategy::Fallback { budget })
+    }
+
+    /// Constructor.
+    pub fn new(members: Vec<Box<dyn Algorithm>>, strategy: Strategy) -> Self {
+        let count = members.len();
+        Self {
+            members,
+            strategy,
+            start: None,
+            insights: vec![None; count],
+            given_up: vec![false; count],
+            current: None,
+            ticks: 0,
+        }
+    }
+
+    /// How the ticks are shared between members.
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    /// Index of the member that made the last guess, `None` before the first tick.
+    pub fn current(&self) -> Option<usize> {
+        self.current
+    }
+
+    /// Let `member` progress from its last insight, returns `None` if it gives up.
+    fn play(&mut self, member: usize, ctx: &mut Context) -> Option<Guess> {
+        let insight = self.insights[member].as_ref().expect("insight received");
+        let guess = self.members[member].progress(insight, ctx);
+        if guess.is_give_up() {
+            self.given_up[member] = true;
+            return None;
+        }
+        self.current = Some(member);
+        Some(guess)
+    }
+}
+
+impl fmt::Debug for Portfolio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Portfolio")
+            .field("strategy", &self.strategy)
+            .field("given_up", &self.given_up)
+            .field("current", &self.current)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Algorithm for Portfolio {
+    fn progress(&mut self, insight: &Insight, ctx: &mut Context) -> Guess {
+        let count = self.members.len();
+        match self.current {
+            // the start, shared by every member
+            None => {
+                self.start = Some(insight.clone());
+                self.insights.fill(self.start.clone());
+            }
+            Some(current) => self.insights[current] = Some(insight.clone()),
+        }
+
+        match self.strategy {
+            Strategy::Race => {
+                let first = self.current.map_or(0, |current| current + 1);
+                for member in (first..first + count).map(|index| index % count) {
+                    if self.given_up[member] {
+                        continue;
+                    }
+                    if let Some(guess) = self.play(member, ctx) {
+                        return guess;
+                    }
+                }
+            }
+            Strategy::Fallback { budget } => {
+                let mut member = self.current.unwrap_or(0);
+                while member < count {
+                    let is_last = member + 1 == count;
+                    if self.given_up[member] || (self.ticks >= budget && !is_last) {
+                        // the next member starts over from the start
+                        self.given_up[member] = true;
+                        self.ticks = 0;
+                        member += 1;
+                        if member < count {
+                            self.insights[member] = self.start.clone();
+                        }
+                        continue;
+                    }
+                    if let Some(guess) = self.play(member, ctx) {
+                        self.ticks += 1;
+                        return guess;
+                    }
+                }
+            }
+        }
+        ctx.give_up()
+    }
+}
+
+#[test]
+fn portfolio() {
+    use super::{BreathFirst, Scripted};
+    use crate::{
+        executor::{drive, Rules},
+        Error, Maze, Pos,
+    };
+
+    // a corridor going east
+    let (a, b, c, d) = (Pos::zero(), Pos::new(1, 0), Pos::new(2, 0), Pos::new(3, 0));
+    let maze = Maze::new(4, 1, a, d, vec![(a, vec![b]), (b, vec![c]), (c, vec![d])]);
+
+    // the breath-first member keeps its own insights once the scripted one gave up
+    let mut algorithm = Portfolio::race(vec![
+        Scripted::new([vec![a, b, c]]).boxed(),
+        BreathFirst::new().boxed(),
+    ]);
+    let mut guesses = Vec::new();
+    let ticks = drive(&maze, &mut algorithm, Rules::default(), |_, g| {
+        guesses.push(g)
+    });
+    assert_eq!(ticks.unwrap(), 4);
+    assert_eq!(
+        guesses,
+        vec![vec![a, b, c], vec![a, b], vec![a, b, c], vec![a, b, c, d]]
+    );
+    assert_eq!(algorithm.current(), Some(1));
+
+    // the scripted member runs out of budget, the breath-first one starts over
+    let mut algorithm = Portfolio::fallback(
+        vec![
+            Scripted::new([vec![a, b], vec![a]]).boxed(),
+            BreathFirst::new().boxed(),
+        ],
+        1,
+    );
+    let mut guesses = Vec::new();
+    let ticks = drive(&maze, &mut algorithm, Rules::default(), |_, g| {
+        guesses.push(g)
+    });
+    assert_eq!(ticks.unwrap(), 4);
+    assert_eq!(
+        guesses,
+        vec![vec![a, b], vec![a, b], vec![a, b, c], vec![a, b, c, d]]
+    );
+
+    let mut algorithm = Portfolio::race(vec![Scripted::default().boxed()]);
+    let result = drive(&maze, &mut algorithm, Rules::default(), |_, _| ());
+    assert!(matches!(result, Err(Error::Unsolvable)));
+}
@@ -0,0 +1,121 @@
+use alloc::vec::Vec;
+
+use rand::{prelude::SliceRandom, rngs::StdRng, SeedableRng};
+
+use crate::{Algorithm, Context, Guess, Insight, Pos, VisitedSet};
+
+/// The passages a [`RandomMouse`] chooses from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Wander {
+    /// Passages leading to unvisited positions when there are some, any passage otherwise.
+    #[default]
+    Unvisited,
+    /// Any passage, the previous position included.
+    Any,
+}
+
+/// [`Algorithm`] walking the [`crate::Maze`] at random, one passage per tick, as a worst-case baseline for the others.
+/// Loops are erased from its path as soon as it walks back to a position of it, so that guesses stay short.
+/// Never gives up unless its start is walled in, the resolution of mazes without solutions should be limited in ticks.
+#[derive(Debug, Clone)]
+pub struct RandomMouse {
+    wander: Wander,
+    rng: StdRng,
+    visited: Option<VisitedSet>,
+    path: Vec<Pos>,
+}
+
+impl RandomMouse {
+    /// Constructor, drawing its seed from the thread random generator.
+    #[cfg(feature = "std")]
+    pub fn new(wander: Wander) -> Self {
+        Self::seeded(wander, rand::random())
+    }
+
+    /// Constructor, always walking the same way for a given `seed`.
+    pub fn seeded(wander: Wander, seed: u64) -> Self {
+        Self {
+            wander,
+            rng: StdRng::seed_from_u64(seed),
+            visited: None,
+            path: Vec::new(),
+        }
+    }
+
+    /// The passages chosen from.
+    pub fn wander(&self) -> Wander {
+        self.wander
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for RandomMouse {
+    fn default() -> Self {
+        Self::new(Wander::default())
+    }
+}
+
+impl Algorithm for RandomMouse {
+    fn progress(&mut self, insight: &Insight, ctx: &mut Context) -> Guess {
+        let position = insight.position();
+        if self.path.is_empty() {
+            self.path.push(position);
+        }
+        let visited = self.visited.get_or_insert_with(|| ctx.new_visited_set());
+        visited.insert(position);
+
+        let unvisited: Vec<_> = match self.wander {
+            Wander::Unvisited => insight
+                .paths()
+                .iter()
+                .copied()
+                .filter(|&branch| !visited.contains(branch))
+                .collect(),
+            Wander::Any => Vec::new(),
+        };
+        let candidates = match unvisited.is_empty() {
+            true => insight.paths(),
+            false => &unvisited,
+        };
+        let Some(&next) = candidates.choose(&mut self.rng) else {
+            return ctx.give_up();
+        };
+
+        match self.path.iter().rposition(|&position| position == next) {
+            Some(index) => self.path.truncate(index + 1),
+            None => self.path.push(next),
+        }
+        ctx.guess(self.path.clone())
+    }
+}
+
+#[test]
+fn random_mouse() {
+    use alloc::vec;
+
+    use crate::{
+        executor::{drive, Rules},
+        Maze,
+    };
+
+    // a corridor, the unvisited positions leading straight to the end
+    let cells: Vec<_> = (0..5).map(|x| Pos::new(x, 0)).collect();
+    let paths = cells.windows(2).map(|w| (w[0], vec![w[1]])).collect();
+    let maze = Maze::new(5, 1, cells[0], cells[4], paths);
+    let mut algorithm = RandomMouse::seeded(Wander::Unvisited, 0);
+    let ticks = drive(&maze, &mut algorithm, Rules::default(), |_, _| ());
+    assert_eq!(ticks.unwrap(), 4);
+
+    // going back and forth, the path never holds the same position twice
+    for seed in 0..8 {
+        let mut algorithm = RandomMouse::seeded(Wander::Any, seed);
+        let mut last = Vec::new();
+        drive(&maze, &mut algorithm, Rules::default(), |_, guess| {
+            assert!(guess.len() <= cells.len());
+            last = guess;
+        })
+        .unwrap();
+        assert_eq!(last, cells);
+    }
+}
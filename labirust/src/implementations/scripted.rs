@@ -0,0 +1,69 @@
+use alloc::{collections::VecDeque, vec::Vec};
+
+use crate::{Algorithm, Context, Guess, Insight, Pos};
+
+/// [`Algorithm`] playing back a predefined sequence of guesses, one per tick, then giving up.
+/// Ignores its insights, which makes the resolution fully deterministic, to test executors and replays.
+#[derive(Debug, Clone, Default)]
+pub struct Scripted {
+    guesses: VecDeque<Vec<Pos>>,
+    played: usize,
+}
+
+impl Scripted {
+    /// Constructor, guessing each path of `guesses` in order.
+    pub fn new<I>(guesses: I) -> Self
+    where
+        I: IntoIterator<Item = Vec<Pos>>,
+    {
+        let guesses = guesses.into_iter().collect();
+        Self { guesses, played: 0 }
+    }
+
+    /// Number of guesses played so far.
+    pub fn played(&self) -> usize {
+        self.played
+    }
+
+    /// Number of guesses left to play before giving up.
+    pub fn remaining(&self) -> usize {
+        self.guesses.len()
+    }
+}
+
+impl Algorithm for Scripted {
+    fn progress(&mut self, _insight: &Insight, ctx: &mut Context) -> Guess {
+        match self.guesses.pop_front() {
+            Some(guess) => {
+                self.played += 1;
+                ctx.guess(guess)
+            }
+            None => ctx.give_up(),
+        }
+    }
+}
+
+#[test]
+fn playback() {
+    use alloc::vec;
+
+    use crate::{
+        executor::{drive, Rules},
+        Error, Maze,
+    };
+
+    let (a, b, c) = (Pos::zero(), Pos::new(1, 0), Pos::new(2, 0));
+    let maze = Maze::new(3, 1, a, c, vec![(a, vec![b]), (b, vec![c])]);
+    let mut guessed = Vec::new();
+    let mut algorithm = Scripted::new([vec![a, b], vec![a, b, c]]);
+    let ticks = drive(&maze, &mut algorithm, Rules::default(), |_, g| {
+        guessed.push(g)
+    });
+    assert_eq!(ticks.unwrap(), 2);
+    assert_eq!(guessed, vec![vec![a, b], vec![a, b, c]]);
+    assert_eq!((algorithm.played(), algorithm.remaining()), (2, 0));
+
+    let mut algorithm = Scripted::new([vec![a, b]]);
+    let result = drive(&maze, &mut algorithm, Rules::default(), |_, _| ());
+    assert!(matches!(result, Err(Error::Unsolvable)));
+}
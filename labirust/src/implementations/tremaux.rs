@@ -0,0 +1,149 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use crate::{Algorithm, Context, Guess, Insight, Pos};
+
+/// [`Algorithm`] following Trémaux's rules, marking the entrances of each passage it walks through.
+/// Fresh passages are preferred, passages leading back to known positions are walked back immediately,
+/// and passages marked twice are never entered again, so that every passage is walked at most twice.
+/// The passages marked once always form the path from the start to the current position.
+/// Only relies on the paths of each [`Insight`], hence works with any [`crate::Topology`] and through portals.
+#[derive(Debug, Clone)]
+pub struct Tremaux {
+    marks: BTreeMap<(Pos, Pos), u8>,
+    previous: Option<Pos>,
+    path: Vec<Pos>,
+}
+
+impl Tremaux {
+    /// Constructor.
+    pub fn new() -> Self {
+        Self {
+            marks: BTreeMap::new(),
+            previous: None,
+            path: Vec::new(),
+        }
+    }
+
+    /// Number of marks on the entrance of the passage leading from `position` to `neighbor`.
+    pub fn marks(&self, position: Pos, neighbor: Pos) -> u8 {
+        self.marks.get(&(position, neighbor)).copied().unwrap_or(0)
+    }
+
+    /// Choose the position to walk to next, `None` if every passage has been walked twice.
+    fn choose(&self, insight: &Insight) -> Option<Pos> {
+        let position = insight.position();
+        let open = || insight.paths().iter().copied();
+        let back = self.previous;
+        let mut others = open().filter(|&neighbor| Some(neighbor) != back);
+        let is_new = others
+            .clone()
+            .all(|neighbor| self.marks(position, neighbor) == 0);
+
+        match back {
+            // a fresh position, leave by an unmarked passage or go back from the dead end, unless it was walked twice
+            _ if is_new => others
+                .next()
+                .or(back.filter(|&back| self.marks(position, back) < 2)),
+            // a known position reached through a fresh passage, walk it back
+            Some(back) if self.marks(position, back) == 1 => Some(back),
+            // backtracking, prefer unmarked passages
+            _ => open()
+                .filter(|&neighbor| self.marks(position, neighbor) < 2)
+                .min_by_key(|&neighbor| self.marks(position, neighbor)),
+        }
+    }
+}
+
+impl Default for Tremaux {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Algorithm for Tremaux {
+    fn progress(&mut self, insight: &Insight, ctx: &mut Context) -> Guess {
+        let position = insight.position();
+        if self.path.is_empty() {
+            self.path.push(position);
+        }
+        let Some(next) = self.choose(insight) else {
+            return ctx.give_up();
+        };
+
+        *self.marks.entry((position, next)).or_default() += 1;
+        *self.marks.entry((next, position)).or_default() += 1;
+        self.previous = Some(position);
+
+        let previous = self.path.len().checked_sub(2).map(|i| self.path[i]);
+        if previous == Some(next) {
+            self.path.pop();
+        } else {
+            self.path.push(next);
+        }
+        ctx.guess(self.path.clone())
+    }
+}
+
+#[test]
+fn tremaux() {
+    use alloc::vec;
+
+    use crate::{
+        executor::{drive, Rules},
+        Error, Maze,
+    };
+
+    // a square loop with a tail to the south leading to the end
+    //  a b
+    //  c d
+    //  e
+    let (a, b) = (Pos::new(0, 0), Pos::new(1, 0));
+    let (c, d, e) = (Pos::new(0, 1), Pos::new(1, 1), Pos::new(0, 2));
+    let paths = vec![(a, vec![b, c]), (b, vec![d]), (c, vec![d, e])];
+    let maze = Maze::new(2, 3, a, e, paths);
+
+    // closing the loop, the follower walks back the fresh passage before leaving by the unmarked one
+    let mut guesses = Vec::new();
+    let ticks = drive(&maze, &mut Tremaux::new(), Rules::default(), |_, g| {
+        guesses.push(g)
+    });
+    assert_eq!(ticks.unwrap(), 6);
+    assert_eq!(guesses[3], vec![a, b, d, c, a]);
+    assert_eq!(guesses[4], vec![a, b, d, c]);
+    assert_eq!(guesses[5], vec![a, b, d, c, e]);
+
+    // the end is walled off, every passage ends up marked twice
+    let paths = vec![(a, vec![b, c]), (b, vec![d]), (c, vec![d])];
+    let maze = Maze::new(2, 3, a, e, paths);
+    let mut algorithm = Tremaux::new();
+    let result = drive(&maze, &mut algorithm, Rules::default(), |_, _| ());
+    assert!(matches!(result, Err(Error::Unsolvable)));
+    for (position, neighbor) in [(a, b), (c, a), (d, c)] {
+        assert_eq!(algorithm.marks(position, neighbor), 2);
+    }
+}
+
+#[cfg(all(feature = "std", feature = "generate"))]
+#[test]
+fn topologies() {
+    use crate::{
+        executor::{drive, Rules},
+        MazeGenerator, SimpleGenerator, Topology,
+    };
+
+    // the passages are only known from the insights, whichever way the cells neighbor each other
+    for topology in [Topology::Hex, Topology::Torus, Topology::Diagonal] {
+        for seed in 0..10 {
+            let maze = SimpleGenerator::new(9, 7)
+                .topology(topology)
+                .generate_seeded(seed)
+                .unwrap();
+            let mut last = Vec::new();
+            let ticks = drive(&maze, &mut Tremaux::new(), Rules::default(), |_, guess| {
+                last = guess
+            });
+            assert!(ticks.is_ok(), "{topology:?} {seed}: {ticks:?}");
+            assert!(maze.is_end(*last.last().unwrap()));
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use alloc::vec::Vec;
+
+use crate::{Algorithm, Context, Guess, Insight, Pos};
+
+/// The hand a [`WallFollower`] keeps on the wall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Hand {
+    Left,
+    #[default]
+    Right,
+}
+
+/// [`Algorithm`] walking the [`crate::Maze`] with a hand on the wall, only relying on the [`Insight`] of the current position.
+/// At each position, sweeps the neighbors around it towards its [`Hand`], starting from the one it came from, and takes the first open one:
+/// on square mazes, turns towards its hand if possible, else goes straight, else turns the other way, else steps back.
+/// Works with any [`crate::Topology`], portals being ignored as they do not stand around the position.
+/// Starts by walking straight from the start, towards its first neighbor, until it hits a wall to put its hand on,
+/// which is more likely to be linked to the outer walls than the walls of the start, so that it does not circle an inner wall.
+/// Always reaches the end of perfect mazes, gives up when it comes back to its first move along the wall without having found it.
+#[derive(Debug, Clone)]
+pub struct WallFollower {
+    hand: Hand,
+    from: Option<Pos>,
+    following: bool,
+    first: Option<(Pos, Pos)>,
+    path: Vec<Pos>,
+}
+
+impl WallFollower {
+    /// Constructor.
+    pub fn new(hand: Hand) -> Self {
+        Self {
+            hand,
+            from: None,
+            following: false,
+            first: None,
+            path: Vec::new(),
+        }
+    }
+
+    /// The hand kept on the wall.
+    pub fn hand(&self) -> Hand {
+        self.hand
+    }
+
+    /// Neighbors to try when coming from `from`, by order of preference, the position it came from being the last one.
+    fn preferences(&self, ctx: &Context, position: Pos, from: Pos) -> Vec<Pos> {
+        let around = ctx.around(position);
+        let count = around.len();
+        let back = around.iter().position(|&p| p == from).unwrap_or(0);
+        (1..=count)
+            .map(|turn| match self.hand {
+                Hand::Left => around[(back + turn) % count],
+                Hand::Right => around[(back + count - turn) % count],
+            })
+            .collect()
+    }
+
+    /// Neighbor straight ahead of `position`, opposite to the one it came from, the first neighbor at the start.
+    fn ahead(&self, ctx: &Context, position: Pos) -> Pos {
+        let around = ctx.around(position);
+        let count = around.len();
+        let back = self
+            .from
+            .and_then(|from| around.iter().position(|&p| p == from));
+        match back {
+            Some(back) => around[(back + count / 2) % count],
+            None => around[0],
+        }
+    }
+
+    /// Move from `position` to `next`, stepping back along the path when it is where it came from.
+    fn step(&mut self, position: Pos, next: Pos, ctx: &mut Context) -> Guess {
+        self.from = Some(position);
+        let previous = self.path.len().checked_sub(2).map(|i| self.path[i]);
+        if previous == Some(next) {
+            self.path.pop();
+        } else {
+            self.path.push(next);
+        }
+        ctx.guess(self.path.clone())
+    }
+}
+
+impl Default for WallFollower {
+    fn default() -> Self {
+        Self::new(Hand::default())
+    }
+}
+
+impl Algorithm for WallFollower {
+    fn progress(&mut self, insight: &Insight, ctx: &mut Context) -> Guess {
+        let position = insight.position();
+        if self.path.is_empty() {
+            self.path.push(position);
+        }
+        let from = match self.from {
+            Some(from) if self.following => from,
+            _ => {
+                // walking straight until hitting a wall, or coming back to the start of toroidal mazes
+                let ahead = self.ahead(ctx, position);
+                if insight.paths().contains(&ahead) && !self.path.contains(&ahead) {
+                    return self.step(position, ahead, ctx);
+                }
+                // then putting the hand on it, as if coming from it
+                self.following = true;
+                ahead
+            }
+        };
+        let Some(next) = self
+            .preferences(ctx, position, from)
+            .into_iter()
+            .find(|next| insight.paths().contains(next))
+        else {
+            return ctx.give_up();
+        };
+        match self.first {
+            None => self.first = Some((position, next)),
+            Some(first) if first == (position, next) => return ctx.give_up(),
+            Some(_) => (),
+        }
+        self.step(position, next, ctx)
+    }
+}
+
+#[test]
+fn wall_follower() {
+    use alloc::vec;
+
+    use crate::{
+        executor::{drive, Rules},
+        Error, Maze,
+    };
+
+    // a T shaped maze, the end being at the bottom of the T
+    let (a, b, c) = (Pos::zero(), Pos::new(1, 0), Pos::new(2, 0));
+    let d = Pos::new(1, 1);
+    let maze = Maze::new(3, 2, b, d, vec![(a, vec![b]), (b, vec![c, d])]);
+
+    // with the hand on the northern border, the right hand visits the west branch before going south
+    let mut guesses = Vec::new();
+    let ticks = drive(
+        &maze,
+        &mut WallFollower::new(Hand::Right),
+        Rules::default(),
+        |_, g| guesses.push(g),
+    );
+    assert_eq!(ticks.unwrap(), 3);
+    assert_eq!(guesses, vec![vec![b, a], vec![b], vec![b, d]]);
+
+    let mut guesses = Vec::new();
+    let ticks = drive(
+        &maze,
+        &mut WallFollower::new(Hand::Left),
+        Rules::default(),
+        |_, g| guesses.push(g),
+    );
+    assert_eq!(ticks.unwrap(), 3);
+    assert_eq!(guesses, vec![vec![b, c], vec![b], vec![b, d]]);
+
+    // a ring around a pillar, the end being east of the ring
+    //  . . . #
+    //  s # . e
+    //  . . . #
+    // with the hand on the pillar, the right hand would keep circling it, walking north first puts it on the border leading to the end instead
+    let ring = [
+        (0, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+        (2, 1),
+        (2, 2),
+        (1, 2),
+        (0, 2),
+    ];
+    let ring: Vec<_> = ring.into_iter().map(|(x, y)| Pos::new(x, y)).collect();
+    let mut paths: Vec<_> = ring.windows(2).map(|w| (w[0], vec![w[1]])).collect();
+    paths.push((ring[7], vec![ring[0]]));
+    let end = Pos::new(3, 1);
+    paths.push((ring[4], vec![end]));
+    let maze = Maze::new(4, 3, ring[0], end, paths.clone());
+    let ticks = drive(
+        &maze,
+        &mut WallFollower::new(Hand::Right),
+        Rules::default(),
+        |_, _| (),
+    );
+    assert_eq!(ticks.unwrap(), 7);
+
+    // the same ring in the middle of a larger maze, the wall hit north of the start being linked to the border through the closed cells around the ring
+    //  # # # # #
+    //  # . . . #
+    //  # s # . e
+    //  # . . . #
+    //  # # # # #
+    let offset = Pos::new(1, 1);
+    let mut paths: Vec<_> = paths
+        .into_iter()
+        .map(|(from, to)| {
+            (
+                from + offset,
+                to.into_iter().map(|to| to + offset).collect(),
+            )
+        })
+        .collect();
+    let end = Pos::new(4, 2);
+    paths.pop();
+    paths.push((ring[4] + offset, vec![end]));
+    let maze = Maze::new(5, 5, ring[0] + offset, end, paths);
+    let mut last = Vec::new();
+    let ticks = drive(
+        &maze,
+        &mut WallFollower::new(Hand::Right),
+        Rules::default(),
+        |_, guess| last = guess,
+    );
+    assert_eq!(ticks.unwrap(), 7);
+    let expected: Vec<_> = [0, 7, 6, 5, 4].map(|i| ring[i] + offset).to_vec();
+    assert_eq!(last[..5], expected);
+    assert_eq!(last[5], end);
+
+    // the end is out of reach, the follower gives up once back to its first move
+    let maze = Maze::new(3, 2, b, d, vec![(a, vec![b]), (b, vec![c])]);
+    let result = drive(
+        &maze,
+        &mut WallFollower::default(),
+        Rules::default(),
+        |_, _| (),
+    );
+    assert!(matches!(result, Err(Error::Unsolvable)));
+}
+
+#[cfg(all(feature = "std", feature = "generate"))]
+#[test]
+fn topologies() {
+    use crate::{
+        executor::{drive, Rules},
+        MazeGenerator, SimpleGenerator, Topology,
+    };
+
+    // sweeping around each position visits every branch of a perfect maze, whichever way its cells neighbor each other
+    for topology in [Topology::Hex, Topology::Torus, Topology::Diagonal] {
+        for hand in [Hand::Left, Hand::Right] {
+            for seed in 0..10 {
+                let maze = SimpleGenerator::new(9, 7)
+                    .topology(topology)
+                    .generate_seeded(seed)
+                    .unwrap();
+                let mut last = Vec::new();
+                let ticks = drive(
+                    &maze,
+                    &mut WallFollower::new(hand),
+                    Rules::default(),
+                    |_, guess| last = guess,
+                );
+                assert!(ticks.is_ok(), "{topology:?} {hand:?} {seed}: {ticks:?}");
+                assert!(maze.is_end(*last.last().unwrap()));
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use alloc::{collections::VecDeque, vec::Vec};
+
+use crate::{Algorithm, Context, Grid, Guess, Insight, Pos};
+
+/// [`Algorithm`] flooding the [`crate::Maze`] from its ends before moving, then walking downhill from the start.
+/// The distance of every position to the closest end is computed on the first tick from the positions visible through the [`Context`] (c.f. [`Context::paths_from`]),
+/// each following tick stepping to a neighbor closer to the end, so that the path walked is always a shortest one.
+/// Gives up right away when no end can be reached through the visible cells, as under [`crate::Visibility::Discovered`].
+#[derive(Debug, Clone)]
+pub struct Wavefront {
+    distances: Option<Grid<Option<usize>>>,
+    path: Vec<Pos>,
+}
+
+impl Wavefront {
+    /// Constructor.
+    pub fn new() -> Self {
+        Self {
+            distances: None,
+            path: Vec::new(),
+        }
+    }
+
+    /// Distance from `position` to the closest end, `None` before the first tick or if no end can be reached from it.
+    pub fn distance(&self, position: Pos) -> Option<usize> {
+        self.distances.as_ref()?.get(position).copied().flatten()
+    }
+
+    /// Breath-first flood of the visible cells, starting from every end.
+    fn flood(ctx: &Context) -> Grid<Option<usize>> {
+        let (width, height) = ctx.size();
+        let mut distances = Grid::new(width, height, None);
+        let mut wave = VecDeque::new();
+        for end in ctx.ends().filter(|&end| ctx.is_known(end)) {
+            distances[end] = Some(0);
+            wave.push_back(end);
+        }
+        while let Some(position) = wave.pop_front() {
+            let distance = distances[position].expect("flooded");
+            let paths = ctx.paths_from(position).unwrap_or_default();
+            for neighbor in paths {
+                if distances[neighbor].is_none() {
+                    distances[neighbor] = Some(distance + 1);
+                    wave.push_back(neighbor);
+                }
+            }
+        }
+        distances
+    }
+}
+
+impl Default for Wavefront {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Algorithm for Wavefront {
+    fn progress(&mut self, insight: &Insight, ctx: &mut Context) -> Guess {
+        let distances = self.distances.get_or_insert_with(|| Self::flood(ctx));
+        let position = insight.position();
+        if self.path.is_empty() {
+            self.path.push(position);
+        }
+        let Some(distance) = distances[position] else {
+            return ctx.give_up();
+        };
+        let downhill = insight
+            .paths()
+            .iter()
+            .copied()
+            .find(|&branch| distances[branch] == distance.checked_sub(1));
+        let Some(next) = downhill else {
+            return ctx.give_up();
+        };
+        self.path.push(next);
+        ctx.guess(self.path.clone())
+    }
+}
+
+#[test]
+fn wavefront() {
+    use alloc::vec;
+
+    use crate::{
+        executor::{drive, Rules},
+        Error, Maze, Visibility,
+    };
+
+    // a T shaped maze, the end being at the bottom of the T
+    let (a, b, c) = (Pos::zero(), Pos::new(1, 0), Pos::new(2, 0));
+    let d = Pos::new(1, 1);
+    let maze = Maze::new(3, 2, a, d, vec![(a, vec![b]), (b, vec![c, d])]);
+
+    let mut algorithm = Wavefront::new();
+    let mut guesses = Vec::new();
+    let ticks = drive(&maze, &mut algorithm, Rules::default(), |_, g| {
+        guesses.push(g)
+    });
+    assert_eq!(ticks.unwrap(), 2);
+    assert_eq!(guesses, vec![vec![a, b], vec![a, b, d]]);
+    assert_eq!(algorithm.distance(c), Some(2));
+    assert_eq!(algorithm.distance(Pos::new(0, 1)), None);
+
+    // the end is hidden until discovered
+    let rules = Rules {
+        visibility: Visibility::Discovered,
+        ..Rules::default()
+    };
+    let result = drive(&maze, &mut Wavefront::new(), rules, |_, _| ());
+    assert!(matches!(result, Err(Error::Unsolvable)));
+}
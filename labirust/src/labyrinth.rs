@@ -2,23 +2,239 @@
 //!
 //! This module contains the data structure representing a maze for the rest of the library.
 
-use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::{collections::HashMap, string::String};
 
-use crate::Pos;
+use alloc::vec::Vec;
+use core::{fmt, ops::Index};
+
+use smallvec::SmallVec;
+
+#[cfg(feature = "std")]
+use crate::Direction;
+use crate::{Grid, MazeMap, Pos};
+
+use self::cell::{Cell, Cells};
+use self::portal::Portals;
+#[cfg(feature = "std")]
+use self::portal::PORTAL;
+use self::topology::Topology;
+#[cfg(feature = "std")]
+use self::topology::{WRAP_HORIZONTAL, WRAP_VERTICAL};
+
+/// Positions accessible from a cell of a [`Maze`], stored inline for the usual four neighbors.
+pub type Neighbors = SmallVec<[Pos; 4]>;
 
 /// Data structure representing a maze on a grid.
-/// stores each possible paths as a [`HashMap`] mapping each positions to the accessible adjascent ones.
-#[derive(Debug, Clone)]
+/// stores each [`Cell`], holding its open directions, in a [`Grid`], along with the cost of entering each cell when set (c.f. [`Maze::cost`]).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "RawMaze")
+)]
 pub struct Maze {
     width: isize,
     height: isize,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "level::single", skip_serializing_if = "level::is_single")
+    )]
+    levels: isize,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Topology::is_square")
+    )]
+    topology: Topology,
     start: Pos,
     end: Pos,
-    paths: HashMap<Pos, Vec<Pos>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    extra_ends: Vec<Pos>,
+    cells: Grid<Cell>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            serialize_with = "portal::serialize",
+            skip_serializing_if = "Portals::is_empty"
+        )
+    )]
+    portals: Portals,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    doors: Vec<(Pos, Pos, lock::KeyId)>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    keys: Vec<(Pos, lock::KeyId)>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    costs: Option<Grid<u32>>,
+}
+
+/// Fields of a deserialized [`Maze`], checked and normalized before becoming one (c.f. [`Maze::validate`]).
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawMaze {
+    width: isize,
+    height: isize,
+    #[serde(default = "level::single")]
+    levels: isize,
+    #[serde(default)]
+    topology: Topology,
+    start: Pos,
+    end: Pos,
+    #[serde(default)]
+    extra_ends: Vec<Pos>,
+    cells: Grid<Cell>,
+    #[serde(default)]
+    portals: Vec<(Pos, Pos)>,
+    #[serde(default)]
+    doors: Vec<(Pos, Pos, lock::KeyId)>,
+    #[serde(default)]
+    keys: Vec<(Pos, lock::KeyId)>,
+    #[serde(default)]
+    costs: Option<Grid<u32>>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawMaze> for Maze {
+    type Error = MazeError;
+
+    /// Rebuild the [`Maze`] through its fallible constructors, failing on any inconsistency instead of panicking later.
+    fn try_from(raw: RawMaze) -> Result<Self, Self::Error> {
+        let RawMaze {
+            width,
+            height,
+            levels,
+            topology,
+            start,
+            end,
+            extra_ends,
+            cells,
+            portals,
+            doors,
+            keys,
+            costs,
+        } = raw;
+        let sizes = [Some(cells.size()), costs.as_ref().map(Grid::size)];
+        if levels <= 0
+            || height % levels != 0
+            || sizes
+                .into_iter()
+                .flatten()
+                .any(|size| size != (width, height))
+        {
+            return Err(MazeError::InvalidSize { width, height });
+        }
+        let mut result = Self::try_new_topology(width, height, start, end, topology)?;
+        result.levels = levels;
+        for (position, cell) in cells.iter() {
+            result.cells[position].open(cell.openings());
+        }
+        for extra in extra_ends {
+            result.try_add_end(extra)?;
+        }
+        for (position_a, position_b) in portals {
+            result.try_add_portal(position_a, position_b)?;
+        }
+        for (position_a, position_b, key) in doors {
+            result.try_add_door(position_a, position_b, key)?;
+        }
+        for (position, key) in keys {
+            result.try_add_key(position, key)?;
+        }
+        for (position, &cost) in costs.iter().flat_map(Grid::iter) {
+            result.try_set_cost(position, cost)?;
+        }
+        result.validate().check()?;
+        Ok(result)
+    }
+}
+
+/// Reasons a [`Maze`] can not be built from a description.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MazeError {
+    /// The width or height is not strictly positive.
+    InvalidSize { width: isize, height: isize },
+    /// A position lies outside of the [`Maze`].
+    OutOfBounds(Pos),
+    /// A path links two positions that are not adjacent.
+    NotAdjacent(Pos, Pos),
+    /// The wall between two positions is already open.
+    AlreadyOpen(Pos, Pos),
+    /// The wall between two positions is already closed.
+    AlreadyClosed(Pos, Pos),
+    /// A passage is open from the first position but not from the second one.
+    OneSided(Pos, Pos),
+    /// The operation does not apply to the topology of the maze, such as stitching layered mazes or drawing hexagonal ones.
+    UnsupportedTopology,
+    /// The sides of two mazes to join differ in length.
+    MismatchedSeam { expected: isize, found: isize },
+}
+
+impl fmt::Display for MazeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSize { width, height } => write!(f, "invalid maze size {width}x{height}"),
+            Self::OutOfBounds(position) => write!(f, "position {position:?} out of bounds"),
+            Self::NotAdjacent(position_a, position_b) => {
+                write!(
+                    f,
+                    "positions {position_a:?} and {position_b:?} not adjacent"
+                )
+            }
+            Self::AlreadyOpen(position_a, position_b) => {
+                write!(
+                    f,
+                    "path between {position_a:?} and {position_b:?} already open"
+                )
+            }
+            Self::AlreadyClosed(position_a, position_b) => {
+                write!(
+                    f,
+                    "path between {position_a:?} and {position_b:?} already closed"
+                )
+            }
+            Self::OneSided(position_a, position_b) => {
+                write!(
+                    f,
+                    "path from {position_a:?} to {position_b:?} not open from {position_b:?}"
+                )
+            }
+            Self::UnsupportedTopology => write!(f, "unsupported maze topology"),
+            Self::MismatchedSeam { expected, found } => {
+                write!(f, "seam of length {found} where {expected} was expected")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MazeError {}
+
+impl Index<Pos> for Maze {
+    type Output = Cell;
+
+    /// The [`Cell`] at `position`, panics if it is out of bounds.
+    fn index(&self, position: Pos) -> &Self::Output {
+        &self.cells[position]
+    }
 }
 
 impl Maze {
-    /// Constructor.
+    /// Constructor, panics when the size is not positive, when the start, the end or a path is out of bounds,
+    /// or when a path links non adjacent positions.
+    /// Descriptions coming from outside the program, such as user input, should go through [`Maze::try_new`] instead, returning a [`MazeError`].
     pub fn new(
         width: isize,
         height: isize,
@@ -26,40 +242,287 @@ impl Maze {
         end: Pos,
         paths_: Vec<(Pos, Vec<Pos>)>,
     ) -> Self {
-        let mut paths = HashMap::new();
+        match Self::try_new(width, height, start, end, paths_) {
+            Ok(result) => result,
+            Err(error) => panic!("{error}"),
+        }
+    }
 
-        for y in 0..height {
-            for x in 0..width {
-                paths.insert((x, y).into(), Vec::new());
-            }
+    /// Fallible constructor, returns an error instead of panicking when the size is not positive,
+    /// when the start, the end or a path is out of bounds, or when a path links non adjacent positions.
+    pub fn try_new(
+        width: isize,
+        height: isize,
+        start: Pos,
+        end: Pos,
+        paths_: Vec<(Pos, Vec<Pos>)>,
+    ) -> Result<Self, MazeError> {
+        if width <= 0 || height <= 0 {
+            return Err(MazeError::InvalidSize { width, height });
         }
+        let cells = Grid::new(width, height, Cell::default());
 
         let mut result = Self {
             width,
             height,
+            levels: 1,
+            topology: Topology::Square,
             start,
             end,
-            paths,
+            extra_ends: Vec::new(),
+            cells,
+            portals: Portals::new(),
+            doors: Vec::new(),
+            keys: Vec::new(),
+            costs: None,
         };
+        for position in [start, end] {
+            if !result.is_inside(position) {
+                return Err(MazeError::OutOfBounds(position));
+            }
+        }
+        result.cells[start].mark_start();
+        result.cells[end].mark_end();
 
         for (position, accessibles) in paths_ {
             for accessible in accessibles {
-                result.create_path(position, accessible);
+                result.try_create_path(position, accessible)?;
             }
         }
 
-        result
+        Ok(result)
     }
 
+    /// Deterministically build a [`Maze`] from any byte string, for fuzzing parsers and [`crate::Algorithm`]s.
+    /// The first bytes are the width, height, start and end coordinates, missing bytes reading as zero.
+    /// The following bits open, two per cell in row order, the passages to the east and to the south,
+    /// passages leading out of the maze being ignored.
+    /// Fails like [`Maze::try_new`] when the size is zero or the start or end is out of bounds.
+    pub fn from_arbitrary_bytes(bytes: &[u8]) -> crate::Result<Self> {
+        let byte = |index: usize| bytes.get(index).copied().unwrap_or(0) as isize;
+        let (width, height) = (byte(0), byte(1));
+        let (start, end) = (Pos::new(byte(2), byte(3)), Pos::new(byte(4), byte(5)));
+        let mut result = Self::try_new(width, height, start, end, Vec::new())?;
+
+        let mut bits = bytes
+            .iter()
+            .skip(6)
+            .flat_map(|byte| (0..8).map(move |bit| (byte >> bit) & 1 == 1));
+        let cells = (0..height).flat_map(|y| (0..width).map(move |x| Pos::new(x, y)));
+        for cell in cells {
+            let (east, south) = (bits.next(), bits.next());
+            if east.is_none() {
+                break;
+            }
+            if east == Some(true) && cell.x() + 1 < width {
+                result.try_create_path(cell, Pos::new(cell.x() + 1, cell.y()))?;
+            }
+            if south == Some(true) && cell.y() + 1 < height {
+                result.try_create_path(cell, Pos::new(cell.x(), cell.y() + 1))?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Open the wall between two positions, panics if they are out of bounds or not adjacent.
+    /// Debug builds also panic when the wall is already open, catching generators carving the same passage twice.
+    #[cfg(feature = "generate")]
     fn create_path(&mut self, position_a: Pos, position_b: Pos) {
-        self.paths
-            .get_mut(&position_a)
-            .expect("position out of bounds")
-            .push(position_b); // warning: mutation before all preconditions are checked
-        self.paths
-            .get_mut(&position_b)
-            .expect("position out of bounds")
-            .push(position_a);
+        let result = if cfg!(debug_assertions) {
+            self.try_open_path(position_a, position_b)
+        } else {
+            self.try_create_path(position_a, position_b)
+        };
+        if let Err(error) = result {
+            panic!("{error}");
+        }
+    }
+
+    /// Open the wall between two positions if it is not already, checking every precondition before mutating the [`Maze`].
+    fn try_create_path(&mut self, position_a: Pos, position_b: Pos) -> Result<(), MazeError> {
+        let (bit_a, bit_b) = self.try_bits(position_a, position_b)?;
+        self.cells[position_a].open(bit_a);
+        self.cells[position_b].open(bit_b);
+        Ok(())
+    }
+
+    /// Bit flags of the passage between two positions, checking that both are inside the [`Maze`] and adjacent.
+    fn try_bits(&self, position_a: Pos, position_b: Pos) -> Result<(u16, u16), MazeError> {
+        for position in [position_a, position_b] {
+            if !self.is_inside(position) {
+                return Err(MazeError::OutOfBounds(position));
+            }
+        }
+        self.bits_between(position_a, position_b)
+            .ok_or(MazeError::NotAdjacent(position_a, position_b))
+    }
+
+    /// Bit flags of the passage between two positions, `None` if they are not adjacent (c.f. [`topology`] and [`level`]).
+    fn bits_between(&self, position_a: Pos, position_b: Pos) -> Option<(u16, u16)> {
+        self.topology
+            .bits_between(position_a, position_b, self.size())
+            .filter(|_| self.is_same_level(position_a, position_b))
+    }
+
+    /// Open the wall between two adjacent positions of the [`Maze`].
+    /// Fails without modifying the maze if a position is out of bounds, if they are not adjacent or if the wall is already open.
+    pub fn try_open_path(&mut self, position_a: Pos, position_b: Pos) -> Result<(), MazeError> {
+        let (bit_a, bit_b) = self.try_bits(position_a, position_b)?;
+        if self.openings(position_a) & bit_a != 0 {
+            return Err(MazeError::AlreadyOpen(position_a, position_b));
+        }
+        self.cells[position_a].open(bit_a);
+        self.cells[position_b].open(bit_b);
+        Ok(())
+    }
+
+    /// Close the wall between two adjacent positions of the [`Maze`], removing the door locking it if any.
+    /// Fails without modifying the maze if a position is out of bounds, if they are not adjacent or if the wall is already closed.
+    pub fn try_close_path(&mut self, position_a: Pos, position_b: Pos) -> Result<(), MazeError> {
+        let (bit_a, bit_b) = self.try_bits(position_a, position_b)?;
+        if self.openings(position_a) & bit_a == 0 {
+            return Err(MazeError::AlreadyClosed(position_a, position_b));
+        }
+        self.cells[position_a].close(bit_a);
+        self.cells[position_b].close(bit_b);
+        self.remove_door(position_a, position_b);
+        Ok(())
+    }
+
+    /// Add a passage between two adjacent positions of the [`Maze`], to build mazes programmatically.
+    /// Every precondition is checked before any mutation, c.f. [`Maze::try_open_path`].
+    pub fn add_passage(&mut self, position_a: Pos, position_b: Pos) -> Result<(), MazeError> {
+        self.try_open_path(position_a, position_b)
+    }
+
+    /// Remove the passage between two adjacent positions of the [`Maze`], along with the door locking it if any.
+    /// Every precondition is checked before any mutation, c.f. [`Maze::try_close_path`].
+    pub fn remove_passage(&mut self, position_a: Pos, position_b: Pos) -> Result<(), MazeError> {
+        self.try_close_path(position_a, position_b)
+    }
+
+    /// Open the wall between two adjacent positions of the [`Maze`] if it is not already, for mazes changing while being solved.
+    /// Returns whether the wall was closed, fails without modifying the maze if a position is out of bounds or if they are not adjacent.
+    pub fn open_wall(&mut self, position_a: Pos, position_b: Pos) -> Result<bool, MazeError> {
+        match self.try_open_path(position_a, position_b) {
+            Ok(()) => Ok(true),
+            Err(MazeError::AlreadyOpen(..)) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Close the wall between two adjacent positions of the [`Maze`] if it is not already, removing the door locking it if any.
+    /// Returns whether the wall was open, fails without modifying the maze if a position is out of bounds or if they are not adjacent.
+    pub fn close_wall(&mut self, position_a: Pos, position_b: Pos) -> Result<bool, MazeError> {
+        match self.try_close_path(position_a, position_b) {
+            Ok(()) => Ok(true),
+            Err(MazeError::AlreadyClosed(..)) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Move the start and the end of the [`Maze`], fails without modifying it if either is out of bounds.
+    pub fn try_set_endpoints(&mut self, start: Pos, end: Pos) -> Result<(), MazeError> {
+        for position in [start, end] {
+            if !self.is_inside(position) {
+                return Err(MazeError::OutOfBounds(position));
+            }
+        }
+        self.cells[self.start].unmark();
+        self.cells[self.end].unmark();
+        self.start = start;
+        self.end = end;
+        self.cells[start].mark_start();
+        self.cells[end].mark_end();
+        for &extra in &self.extra_ends {
+            self.cells[extra].mark_end();
+        }
+        Ok(())
+    }
+
+    /// Add an end to the [`Maze`], reaching any of its ends completing its resolution.
+    /// Does nothing if `position` is already an end, fails without modifying the maze if it is out of bounds.
+    pub fn try_add_end(&mut self, position: Pos) -> Result<(), MazeError> {
+        if !self.is_inside(position) {
+            return Err(MazeError::OutOfBounds(position));
+        }
+        if !self.is_end(position) {
+            let width = self.width;
+            let index = self
+                .extra_ends
+                .partition_point(|end| end.to_index(width) < position.to_index(width));
+            self.extra_ends.insert(index, position);
+            self.cells[position].mark_end();
+        }
+        Ok(())
+    }
+
+    /// Bit flags of the open directions of a cell.
+    fn openings(&self, position: Pos) -> u16 {
+        self[position].openings()
+    }
+
+    /// The [`Cell`] at `position`, `None` if out of bounds.
+    pub fn get(&self, position: Pos) -> Option<&Cell> {
+        self.cells.get(position)
+    }
+
+    /// Iterates over every position of the [`Maze`] in row-major order, along with its [`Cell`].
+    pub fn iter(&self) -> Cells<'_> {
+        Cells::new(self)
+    }
+
+    /// Iterates over every position of the [`Maze`] in row-major order, along with its [`Cell`], c.f. [`Maze::iter`].
+    pub fn cells(&self) -> Cells<'_> {
+        self.iter()
+    }
+
+    /// A [`MazeMap`] attaching the default value of `T` to each cell of the [`Maze`], instead of a side table keyed by [`Pos`].
+    pub fn with_metadata<T: Clone + Default>(&self) -> MazeMap<T> {
+        Grid::new(self.width, self.height, T::default())
+    }
+
+    /// A [`MazeMap`] attaching the result of `init` on its position and [`Cell`] to each cell of the [`Maze`].
+    pub fn with_metadata_from<T>(&self, mut init: impl FnMut(Pos, Cell) -> T) -> MazeMap<T> {
+        Grid::from_fn(self.width, self.height, |position| {
+            init(position, self[position])
+        })
+    }
+
+    /// Check if `map` has the dimensions of the [`Maze`], holding a value for each of its cells.
+    pub fn fits<T>(&self, map: &MazeMap<T>) -> bool {
+        map.size() == self.size()
+    }
+
+    /// Iterates over the open passages between adjacent positions of the [`Maze`], each once, portals aside (c.f. [`Maze::portals`]).
+    pub fn passages(&self) -> impl Iterator<Item = (Pos, Pos)> + '_ {
+        self.sides()
+            .filter(|&(_, _, open)| open)
+            .map(|(position_a, position_b, _)| (position_a, position_b))
+    }
+
+    /// Iterates over the walls between adjacent positions of the [`Maze`], each once, the outer walls aside.
+    pub fn walls(&self) -> impl Iterator<Item = (Pos, Pos)> + '_ {
+        self.sides()
+            .filter(|&(_, _, open)| !open)
+            .map(|(position_a, position_b, _)| (position_a, position_b))
+    }
+
+    /// Iterates over the pairs of adjacent positions of the [`Maze`], each from the first one in row-major order,
+    /// along with whether the passage between them is open.
+    fn sides(&self) -> impl Iterator<Item = (Pos, Pos, bool)> + '_ {
+        let width = self.width;
+        self.iter().flat_map(move |(position, cell)| {
+            self.topology
+                .links(position, self.size())
+                .into_iter()
+                .filter(move |&(_, next)| {
+                    self.is_inside(next)
+                        && self.is_same_level(position, next)
+                        && position.to_index(width) < next.to_index(width)
+                })
+                .map(move |(bit, next)| (position, next, cell.openings() & bit != 0))
+        })
     }
 
     /// Width of the [`Maze`].
@@ -82,25 +545,67 @@ impl Maze {
         self.start
     }
 
-    /// The ending position of the [`Maze`].
+    /// The ending position of the [`Maze`], the first one when it has several (c.f. [`Maze::ends`]).
     pub fn end(&self) -> Pos {
         self.end
     }
 
+    /// Iterates over the ending positions of the [`Maze`], starting with [`Maze::end`] followed by the ones added later in row-major order.
+    pub fn ends(&self) -> impl Iterator<Item = Pos> + '_ {
+        core::iter::once(self.end).chain(self.extra_ends.iter().copied())
+    }
+
     /// Check if the provided position is the start of the [`Maze`].
     pub fn is_start(&self, position: Pos) -> bool {
         self.start() == position
     }
 
-    /// Check if the provided position is the end of the [`Maze`].
+    /// Check if the provided position is one of the ends of the [`Maze`].
     pub fn is_end(&self, position: Pos) -> bool {
-        self.end() == position
+        self.get(position).is_some_and(|cell| cell.is_end())
     }
 
-    /// Returns an array of all positions directly accessible from a position in the [`Maze`].
-    pub fn paths_from(&self, position: Pos) -> &[Pos] {
-        let accessibles = self.paths.get(&position).expect("position out of bounds");
-        accessibles
+    /// Returns all positions directly accessible from a position in the [`Maze`], through its open walls and then its portals.
+    /// Panics if `position` is out of bounds (c.f. [`Maze::try_paths_from`]).
+    pub fn paths_from(&self, position: Pos) -> Neighbors {
+        self.paths_from_iter(position).collect()
+    }
+
+    /// Returns all positions directly accessible from a position in the [`Maze`], fails if `position` is out of bounds.
+    pub fn try_paths_from(&self, position: Pos) -> Result<Neighbors, MazeError> {
+        if !self.is_inside(position) {
+            return Err(MazeError::OutOfBounds(position));
+        }
+        Ok(self.paths_from(position))
+    }
+
+    /// Iterates over all positions directly accessible from a position in the [`Maze`], without allocating.
+    pub fn paths_from_iter(&self, position: Pos) -> impl Iterator<Item = Pos> + '_ {
+        let openings = self.openings(position);
+        self.topology
+            .links(position, self.size())
+            .into_iter()
+            .filter(move |&(bit, _)| openings & bit != 0)
+            .map(|(_, neighbor)| neighbor)
+            .chain(self.portals_from(position))
+    }
+
+    /// Number of passages leaving a position of the [`Maze`], through its walls and portals alike (c.f. [`Maze::paths_from_iter`]).
+    /// Read from the open directions stored for each cell and the portals map, so it is always up to date.
+    pub fn degree(&self, position: Pos) -> usize {
+        self.openings(position).count_ones() as usize + self.portals_from(position).count()
+    }
+
+    /// Check if a position of the [`Maze`] has a single passage leaving it.
+    pub fn is_dead_end(&self, position: Pos) -> bool {
+        self.degree(position) == 1
+    }
+
+    /// Iterates over the dead ends of the [`Maze`] in row-major order, counting passages through portals (c.f. [`Maze::is_dead_end`]).
+    pub fn dead_ends(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.iter()
+            .map(|(position, _)| position)
+            .filter(|&position| self.is_dead_end(position))
     }
 
     /// Check if a position is included within the [`Maze`].
@@ -109,93 +614,118 @@ impl Maze {
         x >= 0 && x < self.width() && y >= 0 && y < self.height()
     }
 
-    /// Returns adjascent positions of `position` that are included in the [`Maze`].
-    pub fn adjascent(&self, position: Pos) -> Vec<Pos> {
-        let (x, y) = position.decompose();
-        [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+    /// Returns adjascent positions of `position` that are included in the [`Maze`], fails if `position` is out of bounds.
+    pub fn try_adjacent(&self, position: Pos) -> Result<Neighbors, MazeError> {
+        if !self.is_inside(position) {
+            return Err(MazeError::OutOfBounds(position));
+        }
+        Ok(self.adjascent(position))
+    }
+
+    /// Returns adjascent positions of `position` that are included in the [`Maze`], on the same level (c.f. [`level`]),
+    /// in the order of the directions of its [`Topology`].
+    pub fn adjascent(&self, position: Pos) -> Neighbors {
+        self.topology
+            .links(position, self.size())
+            .into_iter()
+            .map(|(_, p)| p)
+            .filter(|&p| self.is_inside(p) && self.is_same_level(position, p))
+            .collect()
+    }
+
+    /// Returns the neighbors of `position` given by the [`Topology`] of the [`Maze`], clockwise around it, whether they are included in the maze or not.
+    pub fn around(&self, position: Pos) -> Neighbors {
+        self.topology
+            .around(position, self.size())
             .into_iter()
-            .map(|t| t.into())
-            .filter(|&p| self.is_inside(p))
             .collect()
     }
 
     /// Check if there is a wall between two adjascent positions in the [`Maze`].
+    /// Positions that are not adjacent are walled unless a portal links them.
     pub fn is_walled(&self, position_a: Pos, position_b: Pos) -> bool {
-        self.paths_from(position_a)
-            .iter()
-            .find(|&&p| p == position_b)
-            .is_none()
+        match self.bits_between(position_a, position_b) {
+            Some((bit, _)) => self.openings(position_a) & bit == 0,
+            None => !self.is_portal(position_a, position_b),
+        }
     }
 
-    /// return a string representing the [`Maze`].
+    /// return a string representing the [`Maze`], cells holding a portal being marked with `◊` or stairs (c.f. [`Maze::display_level`]), doors and keys with letters (c.f. [`lock`])
+    /// and cells costing more than 1 to enter being shaded.
+    #[cfg(feature = "std")]
     pub fn display(&self, overlay: Option<HashMap<Pos, char>>) -> String {
-        // output
-        let mut out: Vec<Vec<_>> = (0..(self.height() * 2 + 1))
-            .map(|_| (0..(self.width() * 2 + 1)).map(|_| ' ').collect())
-            .collect();
-
-        // outer walls
-        for x in 0..self.width() {
-            let mapped_x = (x * 2 + 1) as usize;
-            out.first_mut().unwrap()[mapped_x] = '─';
-            out.last_mut().unwrap()[mapped_x] = '─';
-        }
-        for y in 0..self.height() {
-            let mapped_y = (y * 2 + 1) as usize;
-            *out[mapped_y].first_mut().unwrap() = '│';
-            *out[mapped_y].last_mut().unwrap() = '│';
-        }
-
-        // vertical walls
-        for y in 0..self.height() {
-            for x in 1..self.width() {
-                let current_cell = (x, y).into();
-                let left_cell = current_cell - (1, 0).into();
-                if self.is_walled(left_cell, current_cell) {
-                    let mapped_y = (2 * y + 1) as usize;
-                    let mapped_x = (2 * x) as usize;
-                    out[mapped_y][mapped_x] = '│'
-                }
-            }
-        }
-        // horizontal walls
-        for y in 1..self.height() {
-            for x in 0..self.width() {
-                let current_cell = (x, y).into();
-                let upper_cell = current_cell - (0, 1).into();
-                if self.is_walled(upper_cell, current_cell) {
-                    let mapped_x = (2 * x + 1) as usize;
-                    let mapped_y = (2 * y) as usize;
-                    out[mapped_y][mapped_x] = '─';
-                }
-            }
+        let mut out = String::new();
+        self.display_into(overlay.as_ref(), &mut out);
+        out
+    }
+
+    /// Write the representation of the [`Maze`] returned by [`Maze::display`] into `out`, replacing its content.
+    /// Hexagonal mazes are drawn with offset rows instead (c.f. [`topology`]).
+    /// Reusing the same buffer across calls avoids allocating the whole grid each time.
+    #[cfg(feature = "std")]
+    pub fn display_into(&self, overlay: Option<&HashMap<Pos, char>>, out: &mut String) {
+        if self.topology == Topology::Hex {
+            return self.display_hex_into(overlay, out);
         }
-        // corners
-        for y in 0..(self.height() + 1) {
-            for x in 0..(self.width() + 1) {
-                let mapped_x = (2 * x) as usize;
-                let mapped_y = (2 * y) as usize;
-                out[mapped_y][mapped_x] = '•';
+        out.clear();
+        let (width, height) = self.size();
+        let wraps = self.topology == Topology::Torus;
+        for mapped_y in 0..(height * 2 + 1) {
+            if mapped_y > 0 {
+                out.push('\n');
             }
-        }
-
-        // overlay
-        if let Some(overlay) = overlay {
-            for (position, character) in overlay {
-                let (x, y) = position.decompose();
-                let mapped_x = (x * 2 + 1) as usize;
-                let mapped_y = (y * 2 + 1) as usize;
-                out[mapped_y][mapped_x] = character;
+            for mapped_x in 0..(width * 2 + 1) {
+                let (x, y) = (mapped_x / 2, mapped_y / 2);
+                let character = match (mapped_x % 2 == 1, mapped_y % 2 == 1) {
+                    // corners
+                    (false, false) => self.corner(Pos::new(x, y)),
+                    // horizontal walls
+                    (true, false) => {
+                        let outer = y == 0 || y == height;
+                        let below = self.wrap(Pos::new(x, y));
+                        let above = self.wrap(Pos::new(x, y) + Direction::North);
+                        if outer && !wraps || self.is_walled(above, below) {
+                            '─'
+                        } else if outer {
+                            WRAP_HORIZONTAL
+                        } else {
+                            self.door(above, below)
+                                .map_or(' ', |key| lock::glyph(key, true))
+                        }
+                    }
+                    // vertical walls
+                    (false, true) => {
+                        let outer = x == 0 || x == width;
+                        let right = self.wrap(Pos::new(x, y));
+                        let left = self.wrap(Pos::new(x, y) + Direction::West);
+                        if outer && !wraps || self.is_walled(left, right) {
+                            '│'
+                        } else if outer {
+                            WRAP_VERTICAL
+                        } else {
+                            self.door(left, right)
+                                .map_or(' ', |key| lock::glyph(key, true))
+                        }
+                    }
+                    // cells
+                    (true, true) => overlay
+                        .and_then(|overlay| overlay.get(&Pos::new(x, y)).copied())
+                        .or_else(|| {
+                            let position = Pos::new(x, y);
+                            let portal = self.portals_from(position).next();
+                            let portal = portal.map(|_| self.stairs(position).unwrap_or(PORTAL));
+                            let key = || self.key_at(position).map(|key| lock::glyph(key, false));
+                            portal.or_else(key).or_else(|| self.shade(position))
+                        })
+                        .unwrap_or(' '),
+                };
+                out.push(character);
             }
         }
-
-        out.into_iter()
-            .map(|line| line.into_iter().collect::<String>())
-            .collect::<Vec<_>>()
-            .join("\n")
     }
 }
 
+#[cfg(feature = "std")]
 #[test]
 fn display() {
     let maze = Maze::new(
@@ -213,6 +743,256 @@ fn display() {
 
     let text = maze.display(None);
     println!("{text}");
+    assert_eq!(
+        text,
+        "•─•─•─•\n│   │ │\n• • •─•\n│ │ │ │\n•─• •─•\n│ │   │\n•─•─•─•"
+    );
+}
+
+#[test]
+fn walls() {
+    use alloc::vec;
+
+    let maze = Maze::new(
+        2,
+        2,
+        (0, 0).into(),
+        (1, 1).into(),
+        vec![((0, 0).into(), vec![(1, 0).into()])],
+    );
+    assert!(!maze.is_walled((0, 0).into(), (1, 0).into()));
+    assert!(!maze.is_walled((1, 0).into(), (0, 0).into()));
+    assert!(maze.is_walled((0, 0).into(), (0, 1).into()));
+    assert!(maze.is_walled((0, 0).into(), (1, 1).into()));
+    assert_eq!(maze.degree((0, 0).into()), 1);
+    assert_eq!(
+        maze.try_paths_from((0, 0).into()).unwrap().as_slice(),
+        [Pos::new(1, 0)]
+    );
+    assert_eq!(
+        maze.try_paths_from((0, 2).into()),
+        Err(MazeError::OutOfBounds(Pos::new(0, 2)))
+    );
+    assert_eq!(maze.degree((0, 1).into()), 0);
+    assert_eq!(
+        maze.dead_ends().collect::<Vec<_>>(),
+        vec![Pos::new(0, 0), Pos::new(1, 0)]
+    );
+    // portals count as passages, like in the statistics of the maze
+    let mut linked = maze.clone();
+    linked.add_portal(Pos::zero(), Pos::one());
+    assert_eq!(linked.degree(Pos::zero()), 2);
+    assert_eq!(
+        linked.dead_ends().collect::<Vec<_>>(),
+        vec![Pos::new(1, 0), Pos::one()]
+    );
+    assert_eq!(linked.dead_ends().count(), linked.stats().dead_ends());
+    assert_eq!(maze.cells().len(), 4);
+    let mut visits = maze.with_metadata::<u32>();
+    visits[Pos::one()] += 2;
+    assert!(maze.fits(&visits) && !maze.fits(&Grid::new(2, 3, 0)));
+    assert_eq!(visits.iter().map(|(_, &v)| v).sum::<u32>(), 2);
+    let degrees = maze.with_metadata_from(|_, cell| cell.degree());
+    assert_eq!(degrees[Pos::zero()], 1);
+    assert_eq!(
+        maze.passages().collect::<Vec<_>>(),
+        vec![(Pos::zero(), Pos::new(1, 0))]
+    );
+    assert_eq!(
+        maze.walls().collect::<Vec<_>>(),
+        vec![
+            (Pos::zero(), Pos::new(0, 1)),
+            (Pos::new(1, 0), Pos::one()),
+            (Pos::new(0, 1), Pos::one())
+        ]
+    );
+}
+
+#[test]
+fn try_new() {
+    use alloc::vec;
+
+    let paths = |a: (isize, isize), b: (isize, isize)| vec![(a.into(), vec![b.into()])];
+    let build = |paths| Maze::try_new(2, 2, Pos::zero(), Pos::one(), paths);
+    assert!(build(paths((0, 0), (0, 1))).is_ok());
+    assert_eq!(
+        build(paths((0, 0), (1, 1))).unwrap_err(),
+        MazeError::NotAdjacent(Pos::zero(), Pos::one())
+    );
+    assert_eq!(
+        build(paths((1, 1), (2, 1))).unwrap_err(),
+        MazeError::OutOfBounds(Pos::new(2, 1))
+    );
+    assert_eq!(
+        Maze::try_new(0, 2, Pos::zero(), Pos::one(), Vec::new()).unwrap_err(),
+        MazeError::InvalidSize {
+            width: 0,
+            height: 2
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid maze size 0x2")]
+fn new_panics() {
+    Maze::new(0, 2, Pos::zero(), Pos::one(), Vec::new());
+}
+
+#[test]
+fn passages() {
+    let (a, b, c) = (Pos::zero(), Pos::new(1, 0), Pos::one());
+    let mut maze = Maze::try_new(2, 2, a, c, Vec::new()).unwrap();
+    maze.add_passage(a, b).unwrap();
+    maze.add_passage(c, b).unwrap();
+    let built = maze.clone();
+    assert_eq!(maze.add_passage(b, a), Err(MazeError::AlreadyOpen(b, a)));
+    assert_eq!(maze.add_passage(a, c), Err(MazeError::NotAdjacent(a, c)));
+    assert_eq!(
+        maze.add_passage(b, Pos::new(2, 0)),
+        Err(MazeError::OutOfBounds(Pos::new(2, 0)))
+    );
+    assert_eq!(
+        maze.remove_passage(a, Pos::new(0, 1)),
+        Err(MazeError::AlreadyClosed(a, Pos::new(0, 1)))
+    );
+    assert_eq!(maze, built);
+    assert_eq!(maze.validate().disconnected(), [Pos::new(0, 1)]);
+
+    maze.remove_passage(b, c).unwrap();
+    assert!(maze.is_walled(c, b) && !maze.is_walled(a, b));
+}
+
+#[test]
+fn from_arbitrary_bytes() {
+    use crate::Error;
+
+    assert!(matches!(
+        Maze::from_arbitrary_bytes(&[]),
+        Err(Error::InvalidMaze(MazeError::InvalidSize {
+            width: 0,
+            height: 0
+        }))
+    ));
+    assert!(matches!(
+        Maze::from_arbitrary_bytes(&[2, 2, 0, 0, 2, 1]),
+        Err(Error::InvalidMaze(MazeError::OutOfBounds(position))) if position == Pos::new(2, 1)
+    ));
+
+    // (0, 0) opens east and south, (1, 0) would open east out of the maze, (0, 1) opens east
+    let maze = Maze::from_arbitrary_bytes(&[2, 2, 0, 0, 1, 1, 0b0101_0111]).unwrap();
+    assert_eq!(maze.start(), Pos::zero());
+    assert_eq!(maze.end(), Pos::one());
+    assert!(!maze.is_walled(Pos::zero(), Pos::new(1, 0)));
+    assert!(!maze.is_walled(Pos::zero(), Pos::new(0, 1)));
+    assert!(!maze.is_walled(Pos::new(0, 1), Pos::one()));
+    assert!(maze.is_walled(Pos::new(1, 0), Pos::one()));
+    assert_eq!(
+        maze,
+        Maze::from_arbitrary_bytes(&[2, 2, 0, 0, 1, 1, 0b0101_0111]).unwrap()
+    );
+}
+
+#[cfg(all(feature = "serde", feature = "generate"))]
+#[test]
+fn serde() {
+    use crate::{MazeGenerator, SimpleGenerator};
+    let maze = SimpleGenerator::new(8, 5).generate().unwrap();
+    let text = serde_json::to_string(&maze).unwrap();
+    assert_eq!(serde_json::from_str::<Maze>(&text).unwrap(), maze);
+
+    // descriptions that do not match their cells, or with passages leading out of the maze
+    let mut json: serde_json::Value = serde_json::from_str(&text).unwrap();
+    json["width"] = 50.into();
+    assert!(serde_json::from_value::<Maze>(json.clone()).is_err());
+    json["cells"]["width"] = 50.into();
+    assert!(serde_json::from_value::<Maze>(json.clone()).is_err());
+    let mut json: serde_json::Value = serde_json::from_str(&text).unwrap();
+    json["cells"]["cells"][0] = 0b1111.into();
+    assert!(serde_json::from_value::<Maze>(json).is_err());
+
+    // portals are listed once per pair
+    let mut maze = maze;
+    maze.add_portal(Pos::new(7, 4), Pos::zero());
+    maze.add_portal(Pos::zero(), Pos::new(3, 2));
+    let text = serde_json::to_string(&maze).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(json["portals"].as_array().unwrap().len(), 2);
+    assert_eq!(serde_json::from_str::<Maze>(&text).unwrap(), maze);
+}
+
+#[test]
+fn open_close() {
+    use alloc::vec;
+
+    let mut maze = Maze::new(2, 2, Pos::zero(), Pos::one(), Vec::new());
+    let (a, b) = (Pos::zero(), Pos::new(0, 1));
+    assert_eq!(
+        maze.try_close_path(a, b),
+        Err(MazeError::AlreadyClosed(a, b))
+    );
+    assert_eq!(maze.try_open_path(a, b), Ok(()));
+    assert!(!maze.is_walled(b, a));
+    assert_eq!(maze.try_open_path(b, a), Err(MazeError::AlreadyOpen(b, a)));
+    assert_eq!(maze.try_close_path(b, a), Ok(()));
+    assert!(maze.is_walled(a, b));
+    assert_eq!(
+        maze.try_open_path(a, Pos::new(0, 2)),
+        Err(MazeError::OutOfBounds(Pos::new(0, 2)))
+    );
+    assert_eq!(
+        maze.try_adjacent(Pos::new(-1, 0)),
+        Err(MazeError::OutOfBounds(Pos::new(-1, 0)))
+    );
+    assert_eq!(maze.try_adjacent(a).unwrap().len(), 2);
+
+    assert_eq!(maze.open_wall(a, b), Ok(true));
+    assert_eq!(maze.open_wall(b, a), Ok(false));
+    maze.try_add_door(a, b, 0).unwrap();
+    assert_eq!(maze.close_wall(b, a), Ok(true));
+    assert_eq!(maze.close_wall(a, b), Ok(false));
+    assert!(maze.is_walled(a, b) && maze.door(a, b).is_none());
+    assert_eq!(maze.paths_from(a).len(), 0);
+    assert_eq!(
+        maze.open_wall(a, Pos::one()),
+        Err(MazeError::NotAdjacent(a, Pos::one()))
+    );
+
+    assert_eq!(maze.try_set_endpoints(b, a), Ok(()));
+    assert!(maze[b].is_start() && maze[a].is_end() && !maze[Pos::one()].is_end());
+    assert_eq!(
+        maze.try_set_endpoints(a, Pos::new(2, 0)),
+        Err(MazeError::OutOfBounds(Pos::new(2, 0)))
+    );
+    assert_eq!((maze.start(), maze.end()), (b, a));
+
+    assert_eq!(maze.try_add_end(Pos::one()), Ok(()));
+    assert_eq!(maze.try_add_end(a), Ok(()));
+    assert_eq!(maze.ends().collect::<Vec<_>>(), vec![a, Pos::one()]);
+    assert!(maze.is_end(Pos::one()) && !maze.is_end(b));
+    assert_eq!(
+        maze.try_add_end(Pos::new(0, 2)),
+        Err(MazeError::OutOfBounds(Pos::new(0, 2)))
+    );
 }
 
+pub mod cell;
+pub mod crop;
+pub mod distance;
+pub mod fingerprint;
 pub mod generator;
+#[cfg(feature = "image")]
+pub mod image;
+#[cfg(feature = "generate")]
+pub mod infinite;
+pub mod level;
+pub mod lock;
+pub mod portal;
+pub mod stats;
+pub mod stitch;
+#[cfg(feature = "std")]
+pub mod svg;
+pub mod terrain;
+mod text;
+pub mod tile;
+pub mod topology;
+pub mod validation;
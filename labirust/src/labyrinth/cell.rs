@@ -0,0 +1,164 @@
+//! ## Cell
+//!
+//! This module contains the [`Cell`] type, describing one position of a [`Maze`], and the [`Cells`] iterator over a maze.
+
+use core::iter::FusedIterator;
+
+use crate::{Direction, Maze, Pos};
+
+const START: u16 = 1 << 6;
+const END: u16 = 1 << 7;
+const DIRECTIONS: u16 = !(START | END);
+
+/// A cell of a [`Maze`], obtained by indexing it with a [`Pos`].
+/// Stores the open directions as bit flags (c.f. [`Direction::bit`]) along with whether it is the start or the end of the maze,
+/// diagonal directions being stored past those flags (c.f. [`crate::labyrinth::topology::DiagonalDirection::bit`]).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Cell(u16);
+
+impl Cell {
+    /// Check if the passage in `direction` is open, for cells of square mazes (c.f. [`crate::labyrinth::topology`]).
+    pub fn is_open(self, direction: Direction) -> bool {
+        self.0 & u16::from(direction.bit()) != 0
+    }
+
+    /// Iterates over the directions of the open passages leaving the [`Cell`], for cells of square mazes.
+    pub fn open_directions(self) -> impl Iterator<Item = Direction> {
+        Direction::ALL
+            .into_iter()
+            .filter(move |&direction| self.is_open(direction))
+    }
+
+    /// Number of open walls of the [`Cell`], leaving out the portals held by the maze (c.f. [`crate::Maze::degree`]).
+    pub fn degree(self) -> usize {
+        self.openings().count_ones() as usize
+    }
+
+    /// Check if the [`Cell`] has a single open wall, leaving out the portals held by the maze (c.f. [`crate::Maze::is_dead_end`]).
+    pub fn is_dead_end(self) -> bool {
+        self.degree() == 1
+    }
+
+    /// Check if the [`Cell`] is the start of the [`Maze`].
+    pub fn is_start(self) -> bool {
+        self.0 & START != 0
+    }
+
+    /// Check if the [`Cell`] is the end of the [`Maze`].
+    pub fn is_end(self) -> bool {
+        self.0 & END != 0
+    }
+
+    /// Bit flags of the open directions.
+    pub(crate) fn openings(self) -> u16 {
+        self.0 & DIRECTIONS
+    }
+
+    /// Open the passage of bit flag `bit` (c.f. [`Direction::bit`] and [`crate::labyrinth::topology::HexDirection::bit`]).
+    pub(crate) fn open(&mut self, bit: u16) {
+        self.0 |= bit;
+    }
+
+    pub(crate) fn close(&mut self, bit: u16) {
+        self.0 &= !bit;
+    }
+
+    pub(crate) fn mark_start(&mut self) {
+        self.0 |= START;
+    }
+
+    pub(crate) fn mark_end(&mut self) {
+        self.0 |= END;
+    }
+
+    pub(crate) fn unmark(&mut self) {
+        self.0 &= DIRECTIONS;
+    }
+}
+
+/// Iterator over the positions of a [`Maze`] in row-major order, along with their [`Cell`] (c.f. [`Maze::iter`]).
+#[derive(Debug, Clone)]
+pub struct Cells<'m> {
+    maze: &'m Maze,
+    index: usize,
+}
+
+impl<'m> Cells<'m> {
+    pub(crate) fn new(maze: &'m Maze) -> Self {
+        Self { maze, index: 0 }
+    }
+
+    fn remaining(&self) -> usize {
+        let (width, height) = self.maze.size();
+        (width * height) as usize - self.index
+    }
+}
+
+impl Iterator for Cells<'_> {
+    type Item = (Pos, Cell);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining() == 0 {
+            return None;
+        }
+        let position = Pos::from_index(self.index, self.maze.width());
+        self.index += 1;
+        Some((position, self.maze[position]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining(), Some(self.remaining()))
+    }
+}
+
+impl ExactSizeIterator for Cells<'_> {}
+
+impl FusedIterator for Cells<'_> {}
+
+impl<'m> IntoIterator for &'m Maze {
+    type Item = (Pos, Cell);
+    type IntoIter = Cells<'m>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Cells::new(self)
+    }
+}
+
+#[test]
+fn cells() {
+    use alloc::{vec, vec::Vec};
+
+    let maze = Maze::new(
+        3,
+        2,
+        Pos::zero(),
+        Pos::new(2, 1),
+        vec![(Pos::zero(), vec![Pos::new(1, 0), Pos::new(0, 1)])],
+    );
+    let cell = maze[Pos::zero()];
+    assert!(cell.is_start() && !cell.is_end());
+    assert_eq!(cell.degree(), 2);
+    assert_eq!(
+        cell.open_directions().collect::<Vec<_>>(),
+        maze.paths_from(Pos::zero())
+            .iter()
+            .map(|&p| crate::path::direction_between(Pos::zero(), p).unwrap())
+            .collect::<Vec<_>>()
+    );
+    assert!(maze[Pos::new(1, 0)].is_dead_end());
+    assert!(maze[Pos::new(2, 1)].is_end());
+
+    let cells: Vec<_> = maze.iter().collect();
+    assert_eq!(cells.len(), 6);
+    assert_eq!(maze.iter().len(), 6);
+    assert_eq!(cells[4], (Pos::new(1, 1), maze[Pos::new(1, 1)]));
+    assert_eq!(
+        (&maze).into_iter().filter(|(_, c)| c.degree() == 0).count(),
+        3
+    );
+}
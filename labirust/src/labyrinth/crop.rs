@@ -0,0 +1,125 @@
+//! ## Crop
+//!
+//! This module contains the extraction of a rectangular region of a [`Maze`] into a standalone maze,
+//! to zoom into a region of a huge maze or to write focused tests.
+
+use alloc::vec::Vec;
+
+use crate::{Maze, MazeError, Pos};
+
+impl Maze {
+    /// Extract the region from `origin` (included) to `end` (excluded), panics if it is empty or out of bounds (c.f. [`Maze::try_crop`]).
+    pub fn crop(&self, origin: Pos, end: Pos) -> Maze {
+        match self.try_crop(origin, end) {
+            Ok(result) => result,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Extract the region from `origin` (included) to `end` (excluded) into a standalone [`Maze`], passages leaving it being clipped.
+    /// The start and the end are moved to the nearest cell of the region when outside of it,
+    /// the extra ends, portals, doors, keys and costs are kept when they lie entirely inside of it.
+    /// Fails if the region is empty or out of bounds, or if the maze is not a single level square maze.
+    pub fn try_crop(&self, origin: Pos, end: Pos) -> Result<Maze, MazeError> {
+        if !self.topology.is_square() || self.levels != 1 {
+            return Err(MazeError::UnsupportedTopology);
+        }
+        let (width, height) = (end - origin).decompose();
+        if width <= 0 || height <= 0 {
+            return Err(MazeError::InvalidSize { width, height });
+        }
+        for position in [origin, end - Pos::one()] {
+            if !self.is_inside(position) {
+                return Err(MazeError::OutOfBounds(position));
+            }
+        }
+
+        let clamp = |position: Pos| {
+            let (x, y) = position.decompose();
+            Pos::new(
+                x.clamp(origin.x(), end.x() - 1),
+                y.clamp(origin.y(), end.y() - 1),
+            ) - origin
+        };
+        let mut result = Maze::try_new(
+            width,
+            height,
+            clamp(self.start),
+            clamp(self.end),
+            Vec::new(),
+        )?;
+        let inside = |position: Pos| {
+            let (x, y) = position.decompose();
+            (origin.x()..end.x()).contains(&x) && (origin.y()..end.y()).contains(&y)
+        };
+
+        for y in origin.y()..end.y() {
+            for x in origin.x()..end.x() {
+                let position = Pos::new(x, y);
+                for next in self.adjascent(position) {
+                    if inside(next) && !self.is_walled(position, next) {
+                        result.open_wall(position - origin, next - origin)?;
+                    }
+                }
+            }
+        }
+        for &extra in self.extra_ends.iter().filter(|&&extra| inside(extra)) {
+            result.try_add_end(extra - origin)?;
+        }
+        for (position_a, position_b) in self.portals() {
+            if inside(position_a) && inside(position_b) {
+                result.try_add_portal(position_a - origin, position_b - origin)?;
+            }
+        }
+        for (position_a, position_b, key) in self.doors() {
+            if inside(position_a) && inside(position_b) {
+                result.try_add_door(position_a - origin, position_b - origin, key)?;
+            }
+        }
+        for (position, key) in self.keys().filter(|&(position, _)| inside(position)) {
+            result.try_add_key(position - origin, key)?;
+        }
+        if let Some(costs) = &self.costs {
+            for (position, &cost) in costs.iter().filter(|&(position, _)| inside(position)) {
+                result.try_set_cost(position - origin, cost)?;
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[test]
+fn crop() {
+    use alloc::vec;
+
+    // a corridor going around a wall, with a portal between its ends
+    //  a b
+    //  d c
+    let (a, b, c, d) = (Pos::zero(), Pos::new(1, 0), Pos::new(1, 1), Pos::new(0, 1));
+    let mut maze = Maze::new(2, 2, a, d, vec![(a, vec![b]), (b, vec![c]), (c, vec![d])]);
+    maze.add_portal(a, d);
+    maze.try_set_cost(c, 3).unwrap();
+
+    let right = maze.crop(b, Pos::new(2, 2));
+    assert_eq!(right.size(), (1, 2));
+    assert_eq!((right.start(), right.end()), (Pos::zero(), Pos::new(0, 1)));
+    assert!(!right.is_walled(Pos::zero(), Pos::new(0, 1)));
+    assert_eq!(right.cost(Pos::new(0, 1)), 3);
+    assert!(right.validate().is_perfect());
+
+    let left = maze.crop(a, Pos::new(1, 2));
+    assert!(left.is_walled(a, d) && left.is_portal(a, d));
+    assert!(maze.crop(a, Pos::new(2, 2)) == maze);
+
+    assert_eq!(
+        maze.try_crop(b, Pos::new(1, 2)),
+        Err(MazeError::InvalidSize {
+            width: 0,
+            height: 2
+        })
+    );
+    assert_eq!(
+        maze.try_crop(b, Pos::new(3, 2)),
+        Err(MazeError::OutOfBounds(Pos::new(2, 1)))
+    );
+}
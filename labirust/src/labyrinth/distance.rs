@@ -0,0 +1,326 @@
+//! ## Distance
+//!
+//! This module contains the [`DistanceField`] of a [`Maze`], the number of steps from an origin to every cell,
+//! computed by a flood fill operating on rows of cells packed as bits, and the shortest path between two cells.
+
+use alloc::{collections::VecDeque, vec, vec::Vec};
+
+use super::topology::Topology;
+use crate::{Direction, Grid, Maze, MazeError, Pos};
+
+const UNREACHED: u32 = u32::MAX;
+
+/// Length of the shortest path from an origin to each cell of a [`Maze`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DistanceField {
+    origin: Pos,
+    distances: Grid<u32>,
+    farthest: Pos,
+}
+
+impl DistanceField {
+    /// The position distances are measured from.
+    pub fn origin(&self) -> Pos {
+        self.origin
+    }
+
+    /// Number of steps from the origin to `position`, `None` if it is unreachable or out of bounds.
+    pub fn distance(&self, position: Pos) -> Option<usize> {
+        match self.distances.get(position) {
+            Some(&distance) if distance != UNREACHED => Some(distance as usize),
+            _ => None,
+        }
+    }
+
+    /// One of the reachable positions the farthest from the origin.
+    pub fn farthest(&self) -> Pos {
+        self.farthest
+    }
+
+    /// Distance from the origin to the farthest reachable position.
+    pub fn max_distance(&self) -> usize {
+        self.distances[self.farthest] as usize
+    }
+
+    /// Number of positions reachable from the origin, including itself.
+    pub fn reached(&self) -> usize {
+        self.distances
+            .iter()
+            .filter(|(_, &d)| d != UNREACHED)
+            .count()
+    }
+
+    /// Iterates over every position in row-major order, along with its number of steps from the origin if it is reachable.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, Option<usize>)> + '_ {
+        self.distances.iter().map(|(position, &distance)| {
+            (
+                position,
+                (distance != UNREACHED).then_some(distance as usize),
+            )
+        })
+    }
+}
+
+/// Open directions of every cell of a [`Maze`], one bit per cell, in rows of `words` 64 bits words.
+struct Masks {
+    words: usize,
+    east: Vec<u64>,
+    west: Vec<u64>,
+    south: Vec<u64>,
+    north: Vec<u64>,
+}
+
+impl Masks {
+    fn new(maze: &Maze) -> Self {
+        let (width, height) = maze.size();
+        let words = (width as usize).div_ceil(64);
+        let count = words * height as usize;
+        let mut result = Self {
+            words,
+            east: vec![0; count],
+            west: vec![0; count],
+            south: vec![0; count],
+            north: vec![0; count],
+        };
+        for (position, cell) in maze {
+            let (x, y) = (position.x() as usize, position.y() as usize);
+            let (word, bit) = (y * words + x / 64, 1 << (x % 64));
+            for (direction, mask) in [
+                (Direction::East, &mut result.east),
+                (Direction::West, &mut result.west),
+                (Direction::South, &mut result.south),
+                (Direction::North, &mut result.north),
+            ] {
+                if cell.is_open(direction) {
+                    mask[word] |= bit;
+                }
+            }
+        }
+        result
+    }
+}
+
+impl Maze {
+    /// Compute the breadth-first distance from `from` to every cell of the [`Maze`], an alias of [`Maze::distance_field`].
+    /// Panics if `from` is out of bounds.
+    pub fn distance_map(&self, from: Pos) -> DistanceField {
+        self.distance_field(from)
+    }
+
+    /// Compute the [`DistanceField`] of the [`Maze`] from `origin`, panics if it is out of bounds (c.f. [`Maze::try_distance_field`]).
+    pub fn distance_field(&self, origin: Pos) -> DistanceField {
+        match self.try_distance_field(origin) {
+            Ok(field) => field,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Compute the [`DistanceField`] of the [`Maze`] from `origin`, fails with [`MazeError::OutOfBounds`] if it is out of bounds.
+    /// Each step of the flood fill extends a whole word of 64 cells at once and only visits the words holding the frontier,
+    /// keeping the total work proportional to the size of the maze.
+    pub fn try_distance_field(&self, origin: Pos) -> Result<DistanceField, MazeError> {
+        if !self.is_inside(origin) {
+            return Err(MazeError::OutOfBounds(origin));
+        }
+        if self.has_portals() || self.topology() != Topology::Square {
+            return Ok(self.distance_field_through_portals(origin));
+        }
+        let width = self.width();
+        let masks = Masks::new(self);
+        let words = masks.words;
+        let count = masks.east.len();
+
+        let mut distances = Grid::new(width, self.height(), UNREACHED);
+        let mut visited = vec![0u64; count];
+        let mut frontier = vec![0u64; count];
+        let mut next = vec![0u64; count];
+        // step at which each word was last queued, so that it is queued once per step
+        let mut queued = vec![UNREACHED; count];
+        let mut active = Vec::new();
+        let mut next_active = Vec::new();
+
+        let (x, y) = (origin.x() as usize, origin.y() as usize);
+        let start = y * words + x / 64;
+        visited[start] = 1 << (x % 64);
+        frontier[start] = 1 << (x % 64);
+        active.push(start);
+        distances[origin] = 0;
+        let mut farthest = origin;
+
+        let mut step = 0;
+        while !active.is_empty() {
+            step += 1;
+            let mut push = |word: usize, bits: u64, next: &mut Vec<u64>| {
+                next[word] |= bits;
+                if queued[word] != step {
+                    queued[word] = step;
+                    next_active.push(word);
+                }
+            };
+
+            // spread the frontier through open walls, openings never lead out of the maze
+            for &word in &active {
+                let cells = core::mem::take(&mut frontier[word]);
+                let east = cells & masks.east[word];
+                if east != 0 {
+                    push(word, east << 1, &mut next);
+                    if east >> 63 != 0 {
+                        push(word + 1, 1, &mut next);
+                    }
+                }
+                let west = cells & masks.west[word];
+                if west != 0 {
+                    push(word, west >> 1, &mut next);
+                    if west & 1 != 0 {
+                        push(word - 1, 1 << 63, &mut next);
+                    }
+                }
+                let south = cells & masks.south[word];
+                if south != 0 {
+                    push(word + words, south, &mut next);
+                }
+                let north = cells & masks.north[word];
+                if north != 0 {
+                    push(word - words, north, &mut next);
+                }
+            }
+
+            // keep the newly reached cells as the next frontier
+            active.clear();
+            for word in next_active.drain(..) {
+                let reached = core::mem::take(&mut next[word]) & !visited[word];
+                if reached == 0 {
+                    continue;
+                }
+                visited[word] |= reached;
+                frontier[word] = reached;
+                active.push(word);
+
+                let (row, column) = (word / words, word % words);
+                let mut bits = reached;
+                while bits != 0 {
+                    let x = column * 64 + bits.trailing_zeros() as usize;
+                    let position = Pos::new(x as isize, row as isize);
+                    distances[position] = step;
+                    farthest = position;
+                    bits &= bits - 1;
+                }
+            }
+        }
+
+        Ok(DistanceField {
+            origin,
+            distances,
+            farthest,
+        })
+    }
+
+    /// Compute the [`DistanceField`] from `origin` with a plain breath-first flood,
+    /// following portals and hexagonal passages that the packed rows can not represent.
+    fn distance_field_through_portals(&self, origin: Pos) -> DistanceField {
+        let mut distances = Grid::new(self.width(), self.height(), UNREACHED);
+        let mut queue = VecDeque::from([origin]);
+        distances[origin] = 0;
+        let mut farthest = origin;
+        while let Some(position) = queue.pop_front() {
+            farthest = position;
+            let step = distances[position] + 1;
+            for next in self.paths_from_iter(position) {
+                if distances[next] == UNREACHED {
+                    distances[next] = step;
+                    queue.push_back(next);
+                }
+            }
+        }
+        DistanceField {
+            origin,
+            distances,
+            farthest,
+        }
+    }
+
+    /// Shortest path from `from` to `to`, both included, found by a breadth-first search ignoring doors (c.f. [`crate::labyrinth::lock`]).
+    /// `None` if either position is out of bounds or `to` can not be reached from `from`.
+    pub fn shortest_path(&self, from: Pos, to: Pos) -> Option<Vec<Pos>> {
+        if !self.is_inside(from) || !self.is_inside(to) {
+            return None;
+        }
+        let mut parents = Grid::new(self.width(), self.height(), None);
+        parents[from] = Some(from);
+        let mut queue = VecDeque::from([from]);
+        while let Some(position) = queue.pop_front() {
+            if position == to {
+                break;
+            }
+            for next in self.paths_from_iter(position) {
+                if parents[next].is_none() {
+                    parents[next] = Some(position);
+                    queue.push_back(next);
+                }
+            }
+        }
+        parents[to]?;
+
+        let mut result = vec![to];
+        let mut current = to;
+        while current != from {
+            current = parents[current]?;
+            result.push(current);
+        }
+        result.reverse();
+        Some(result)
+    }
+}
+
+#[cfg(all(feature = "std", feature = "generate"))]
+#[test]
+fn distance_field() {
+    use crate::{MazeGenerator, SimpleGenerator};
+
+    // wide enough for rows to span several words
+    let maze = SimpleGenerator::new(150, 40).generate().unwrap();
+    let field = maze.distance_field(maze.start());
+
+    let mut expected = Grid::new(maze.width(), maze.height(), None);
+    expected[maze.start()] = Some(0);
+    let mut queue = VecDeque::from([maze.start()]);
+    while let Some(current) = queue.pop_front() {
+        let distance = expected[current].unwrap();
+        for next in maze.paths_from_iter(current) {
+            if expected[next].is_none() {
+                expected[next] = Some(distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    for (position, &distance) in expected.iter() {
+        assert_eq!(field.distance(position), distance);
+    }
+    assert!(field
+        .iter()
+        .zip(expected.iter())
+        .all(|((a, distance), (b, &expected))| a == b && distance == expected));
+    assert_eq!(maze.distance_map(maze.start()), field);
+    assert_eq!(maze.try_distance_field(maze.start()), Ok(field.clone()));
+    assert_eq!(
+        maze.try_distance_field(Pos::new(150, 0)),
+        Err(MazeError::OutOfBounds(Pos::new(150, 0)))
+    );
+    assert_eq!(field.reached(), 150 * 40);
+    assert_eq!(
+        Some(field.max_distance()),
+        expected.iter().filter_map(|(_, &d)| d).max()
+    );
+
+    let path = maze.shortest_path(maze.start(), maze.end()).unwrap();
+    assert_eq!(path.len(), field.distance(maze.end()).unwrap() + 1);
+    assert_eq!((path[0], path[path.len() - 1]), (maze.start(), maze.end()));
+    assert!(crate::path::is_adjacent_chain(&path));
+    assert_eq!(
+        maze.shortest_path(maze.start(), maze.start()),
+        Some(vec![maze.start()])
+    );
+    assert_eq!(maze.shortest_path(maze.start(), Pos::new(150, 0)), None);
+}
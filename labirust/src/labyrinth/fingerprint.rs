@@ -0,0 +1,123 @@
+//! ## Fingerprint
+//!
+//! This module contains the fingerprint of a [`Maze`], a hash of its content stable across platforms and versions of the library,
+//! to deduplicate corpora of mazes or check that a record matches the maze it was made on.
+
+use alloc::vec::Vec;
+
+use super::topology::Topology;
+use crate::{Maze, Pos};
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 64 bits FNV-1a hasher, whose output does not depend on the platform nor on the version of the standard library.
+struct Fnv(u64);
+
+impl Fnv {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_isize(&mut self, value: isize) {
+        self.write(&(value as i64).to_le_bytes());
+    }
+
+    fn write_pos(&mut self, position: Pos) {
+        self.write_isize(position.x());
+        self.write_isize(position.y());
+    }
+}
+
+impl Maze {
+    /// Hash of the content of the [`Maze`]: its dimensions, topology, ends, passages, portals, doors, keys and costs.
+    /// Equal mazes have the same fingerprint whatever the order their passages and portals were added in,
+    /// and fingerprints are stable across platforms and versions of the library.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv(FNV_OFFSET);
+        hasher.write_isize(self.width);
+        hasher.write_isize(self.height);
+        hasher.write_isize(self.levels);
+        hasher.write(&[match self.topology {
+            Topology::Square => 0,
+            Topology::Hex => 1,
+            Topology::Torus => 2,
+            Topology::Diagonal => 3,
+        }]);
+        hasher.write_pos(self.start);
+        hasher.write_pos(self.end);
+
+        // sizes separate the variable length sections
+        hasher.write(&(self.extra_ends.len() as u64).to_le_bytes());
+        for &end in &self.extra_ends {
+            hasher.write_pos(end);
+        }
+        for (_, cell) in self.iter() {
+            hasher.write(&cell.openings().to_le_bytes());
+        }
+        let width = self.width;
+        let mut portals: Vec<_> = self
+            .portals()
+            .map(|(a, b)| match a.to_index(width) <= b.to_index(width) {
+                true => (a, b),
+                false => (b, a),
+            })
+            .collect();
+        portals.sort_unstable_by_key(|&(a, b)| (a.to_index(width), b.to_index(width)));
+        hasher.write(&(portals.len() as u64).to_le_bytes());
+        for (position_a, position_b) in portals {
+            hasher.write_pos(position_a);
+            hasher.write_pos(position_b);
+        }
+        hasher.write(&(self.doors.len() as u64).to_le_bytes());
+        for (position_a, position_b, key) in self.doors() {
+            hasher.write_pos(position_a);
+            hasher.write_pos(position_b);
+            hasher.write(&[key]);
+        }
+        hasher.write(&(self.keys.len() as u64).to_le_bytes());
+        for (position, key) in self.keys() {
+            hasher.write_pos(position);
+            hasher.write(&[key]);
+        }
+        // unset costs are all 1, hashed the same as a maze without costs
+        for (position, _) in self.iter() {
+            hasher.write(&self.cost(position).to_le_bytes());
+        }
+        hasher.0
+    }
+}
+
+#[test]
+fn fingerprint() {
+    use alloc::vec;
+
+    let (a, b, c, d) = (Pos::zero(), Pos::new(1, 0), Pos::new(1, 1), Pos::new(0, 1));
+    let forward = Maze::new(2, 2, a, d, vec![(a, vec![b]), (b, vec![c]), (c, vec![d])]);
+    let backward = Maze::new(2, 2, a, d, vec![(d, vec![c]), (c, vec![b]), (b, vec![a])]);
+    assert_eq!(forward.fingerprint(), backward.fingerprint());
+
+    let (mut forward, mut backward) = (forward, backward);
+    forward.add_portal(a, c);
+    forward.add_portal(b, d);
+    backward.add_portal(d, b);
+    backward.add_portal(c, a);
+    assert_eq!(forward, backward);
+    assert_eq!(forward.fingerprint(), backward.fingerprint());
+
+    let mut other = forward.clone();
+    other.try_set_endpoints(a, c).unwrap();
+    assert_ne!(other.fingerprint(), forward.fingerprint());
+    let mut other = forward.clone();
+    other.try_set_cost(b, 2).unwrap();
+    assert_ne!(other.fingerprint(), forward.fingerprint());
+    let mut other = forward.clone();
+    other.close_wall(a, b).unwrap();
+    assert_ne!(other.fingerprint(), forward.fingerprint());
+
+    // fixed across platforms and versions
+    let single = Maze::new(1, 1, a, a, vec![]);
+    assert_eq!(single.fingerprint(), 0x00a1_4217_d2de_fa3d);
+}
@@ -1,66 +1,157 @@
 //! ## Generator
 //!
-//! This module contains raw functions generating mazes.
+//! This module contains the [`MazeGenerator`] trait, implemented by types creating mazes, and with the `generate` feature the random generators themselves.
 
-use std::collections::HashSet;
+use core::fmt;
 
-use rand::{prelude::SliceRandom, thread_rng};
+use crate::{Maze, MazeError};
 
-use crate::{Maze, Pos};
+#[cfg(feature = "generate")]
+mod aldous_broder;
+#[cfg(feature = "generate")]
+mod braid;
+#[cfg(feature = "generate")]
+mod growing_tree;
+#[cfg(feature = "generate")]
+mod hex;
+#[cfg(feature = "generate")]
+mod hunt_and_kill;
+#[cfg(feature = "image")]
+mod image;
+#[cfg(feature = "generate")]
+mod imperfect;
+#[cfg(feature = "generate")]
+mod kruskal;
+#[cfg(feature = "generate")]
+mod layered;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "generate")]
+mod placement;
+#[cfg(feature = "generate")]
+mod seeded;
+#[cfg(feature = "generate")]
+mod simple;
+#[cfg(feature = "generate")]
+mod union_find;
+#[cfg(feature = "generate")]
+mod wilson;
+#[cfg(feature = "generate")]
+pub use aldous_broder::AldousBroderGenerator;
+#[cfg(feature = "generate")]
+pub use braid::braid;
+#[cfg(all(feature = "std", feature = "generate"))]
+pub use braid::Braider;
+#[cfg(feature = "generate")]
+pub use growing_tree::{CellSelection, GrowingTreeGenerator};
+#[cfg(feature = "generate")]
+pub use hex::HexGenerator;
+#[cfg(feature = "generate")]
+pub use hunt_and_kill::HuntAndKillGenerator;
+#[cfg(feature = "image")]
+pub use image::ImageGenerator;
+#[cfg(feature = "generate")]
+pub use imperfect::ImperfectGenerator;
+#[cfg(feature = "generate")]
+pub use kruskal::KruskalGenerator;
+#[cfg(feature = "generate")]
+pub use layered::LayeredGenerator;
+#[cfg(feature = "rayon")]
+pub use parallel::ParallelGenerator;
+#[cfg(feature = "generate")]
+pub(crate) use placement::add_random_ends;
+#[cfg(feature = "generate")]
+pub use placement::Placement;
+#[cfg(feature = "generate")]
+pub use seeded::SeededGenerator;
+#[cfg(feature = "generate")]
+pub(crate) use simple::carve_rectangle;
+#[cfg(feature = "generate")]
+pub use simple::SimpleGenerator;
+#[cfg(feature = "generate")]
+pub use wilson::WilsonGenerator;
 
-/// Trait encapsulating the behavior of a type capable to create mazes.
-pub trait MazeGenerator {
-    fn generate(&self) -> Maze;
+/// Reasons a [`MazeGenerator`] can fail to create a [`Maze`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum GenerationError {
+    /// The parameters of the generator do not describe a valid [`Maze`].
+    InvalidMaze(MazeError),
+    /// The generator can not be seeded.
+    Unseedable,
+    /// Some cells of the generated [`Maze`] can not be reached from its start.
+    Disconnected,
 }
 
-/// Most common maze generation technique, recursively creating paths to unvisited cells, each time choosing next direction at random.
-pub struct SimpleGenerator {
-    width: isize,
-    height: isize,
+impl fmt::Display for GenerationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMaze(error) => write!(f, "{error}"),
+            Self::Unseedable => write!(f, "the generator can not be seeded"),
+            Self::Disconnected => write!(f, "the generated maze is not connected"),
+        }
+    }
 }
 
-impl SimpleGenerator {
-    pub fn new(width: isize, height: isize) -> Self {
-        Self { height, width }
+#[cfg(feature = "std")]
+impl std::error::Error for GenerationError {}
+
+impl From<MazeError> for GenerationError {
+    fn from(error: MazeError) -> Self {
+        Self::InvalidMaze(error)
     }
 }
 
-impl MazeGenerator for SimpleGenerator {
-    fn generate(&self) -> Maze {
-        let Self { width, height } = *self;
-        let mut result = Maze::new(
-            width,
-            height,
-            Pos::zero(),
-            (width - 1, height - 1).into(),
-            Vec::new(),
-        );
+/// Trait encapsulating the behavior of a type capable to create mazes.
+pub trait MazeGenerator {
+    /// Generate a new [`Maze`], generators may update their own state, such as a random generator, along the way.
+    fn generate(&mut self) -> Result<Maze, GenerationError>;
 
-        fn recursive(current: Pos, result: &mut Maze, visited: &mut HashSet<Pos>) {
-            visited.insert(current);
-            let mut adjascent_positions = result.adjascent(current);
-            adjascent_positions.shuffle(&mut thread_rng());
-            for neighbor in adjascent_positions {
-                if visited.contains(&neighbor) {
-                    continue;
-                }
-                result.create_path(current, neighbor);
-                recursive(neighbor, result, visited);
-            }
-        }
+    /// Generate the [`Maze`] determined by `seed`, the same seed always giving the same maze.
+    /// Fails with [`GenerationError::Unseedable`] for generators that can not be seeded, which is the default.
+    fn generate_seeded(&mut self, seed: u64) -> Result<Maze, GenerationError> {
+        let _ = seed;
+        Err(GenerationError::Unseedable)
+    }
 
-        let mut visited = HashSet::new();
-        let current = Pos::zero();
-        recursive(current, &mut result, &mut visited);
+    /// Generate a new [`Maze`] like [`MazeGenerator::generate`], calling `progress` with the number of cells done and the total number of cells along the way.
+    /// By default, progress is only reported once the maze is complete.
+    fn generate_with_progress(
+        &mut self,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        let maze = self.generate()?;
+        let cells = (maze.width() * maze.height()) as usize;
+        progress(cells, cells);
+        Ok(maze)
+    }
 
-        result
+    /// Wrap the generator in a [`SeededGenerator`], so that the mazes it generates are determined by `seed`.
+    #[cfg(feature = "generate")]
+    fn with_seed(self, seed: u64) -> SeededGenerator<Self>
+    where
+        Self: Sized,
+    {
+        SeededGenerator::new(self, seed)
     }
 }
 
-#[test]
-fn generation() {
-    let generator = SimpleGenerator::new(10, 10);
-    let maze = generator.generate();
-    let text = maze.display(None);
-    println!("{text}");
+/// Check that `maze` is perfect: every cell can be reached from the start through exactly one path.
+#[cfg(all(test, feature = "generate"))]
+pub(crate) fn assert_perfect(maze: &Maze) {
+    let cells = maze.iter().len();
+    let passages: usize = maze.iter().map(|(_, cell)| cell.degree()).sum::<usize>() / 2;
+    assert_eq!(maze.distance_field(maze.start()).reached(), cells);
+    assert_eq!(passages, cells - 1);
+}
+
+/// Check that the four perfect 2x2 mazes are about equally likely among the mazes generated from 4000 seeds.
+#[cfg(all(test, feature = "std", feature = "generate"))]
+pub(crate) fn assert_uniform(mut generate: impl FnMut(u64) -> Maze) {
+    let mut counts = std::collections::HashMap::new();
+    for seed in 0..4000 {
+        *counts.entry(generate(seed)).or_insert(0) += 1;
+    }
+    assert_eq!(counts.len(), 4);
+    assert!(counts.values().all(|&count| (850..1150).contains(&count)));
 }
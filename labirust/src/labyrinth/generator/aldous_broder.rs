@@ -0,0 +1,119 @@
+//! ## Aldous-Broder
+//!
+//! This module contains the [`AldousBroderGenerator`], carving a random walk over the whole maze.
+
+use alloc::vec::Vec;
+
+use rand::{prelude::SliceRandom, Rng};
+#[cfg(feature = "std")]
+use rand::{rngs::StdRng, thread_rng, SeedableRng};
+
+use super::GenerationError;
+#[cfg(feature = "std")]
+use super::MazeGenerator;
+use crate::{Maze, Pos, VisitedSet};
+
+/// Generator building a uniform spanning tree with the Aldous-Broder algorithm.
+/// Walks randomly from cell to cell, carving a passage each time it enters a cell for the first time, until every cell is visited.
+/// Like the [`super::WilsonGenerator`], every perfect maze is equally likely, but the walk lingers much longer over visited cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AldousBroderGenerator {
+    width: isize,
+    height: isize,
+}
+
+impl AldousBroderGenerator {
+    /// Constructor.
+    pub fn new(width: isize, height: isize) -> Self {
+        Self { width, height }
+    }
+
+    /// Generate a [`Maze`], drawing random choices from the provided `rng`.
+    pub fn generate_with<R: Rng>(&self, rng: &mut R) -> Result<Maze, GenerationError> {
+        self.carve(rng, &mut |_, _| ())
+    }
+
+    /// Generate a [`Maze`], reporting the number of visited cells to `progress` as they are reached.
+    fn carve<R: Rng>(
+        &self,
+        rng: &mut R,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        let Self { width, height } = *self;
+        debug!("generating a {width}x{height} maze with the aldous-broder algorithm");
+        let mut result = Maze::try_new(
+            width,
+            height,
+            Pos::zero(),
+            (width - 1, height - 1).into(),
+            Vec::new(),
+        )?;
+
+        let total = (width * height) as usize;
+        let mut visited = VisitedSet::new(width, height);
+        let mut current = Pos::new(rng.gen_range(0..width), rng.gen_range(0..height));
+        visited.insert(current);
+        let mut done = 1;
+        progress(done, total);
+        while done < total {
+            let next = *result
+                .adjascent(current)
+                .choose(rng)
+                .expect("adjacent cell");
+            if visited.insert(next) {
+                result.create_path(current, next);
+                done += 1;
+                progress(done, total);
+            }
+            current = next;
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "std")]
+impl MazeGenerator for AldousBroderGenerator {
+    fn generate(&mut self) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut thread_rng())
+    }
+
+    fn generate_seeded(&mut self, seed: u64) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut StdRng::seed_from_u64(seed))
+    }
+
+    fn generate_with_progress(
+        &mut self,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        self.carve(&mut thread_rng(), progress)
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn generation() {
+    let mut generator = AldousBroderGenerator::new(12, 8);
+    let maze = generator.generate_seeded(0).unwrap();
+    super::assert_perfect(&maze);
+    assert_eq!(maze, generator.generate_seeded(0).unwrap());
+
+    let mut reports = Vec::new();
+    generator
+        .generate_with_progress(&mut |done, total| reports.push((done, total)))
+        .unwrap();
+    assert_eq!(reports.len(), 96);
+    assert_eq!(reports.last(), Some(&(96, 96)));
+
+    // the four perfect 2x2 mazes are equally likely
+    super::assert_uniform(|seed| {
+        AldousBroderGenerator::new(2, 2)
+            .generate_seeded(seed)
+            .unwrap()
+    });
+
+    // a single cell needs no walk
+    let maze = AldousBroderGenerator::new(1, 1).generate().unwrap();
+    assert_eq!(maze.degree(Pos::zero()), 0);
+}
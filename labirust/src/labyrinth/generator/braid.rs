@@ -0,0 +1,134 @@
+//! ## Braid
+//!
+//! This module contains [`braid`], removing dead ends from a [`Maze`] to create loops, and the [`Braider`] applying it to generated mazes.
+
+use alloc::vec::Vec;
+
+use rand::{prelude::SliceRandom, Rng};
+#[cfg(feature = "std")]
+use rand::{rngs::StdRng, thread_rng, SeedableRng};
+
+#[cfg(feature = "std")]
+use super::{GenerationError, MazeGenerator};
+use crate::{Maze, Pos};
+
+/// Remove a `ratio` of the dead ends of `maze`, between 0 and 1, by opening one of their walls, returning the number of walls opened.
+/// Walls leading to another dead end are opened first, so that both are removed at once.
+/// Dead ends are picked at random, using the provided `rng`, and the maze is no longer perfect afterward.
+/// Cells left by a portal are not dead ends (c.f. [`Maze::is_dead_end`]), so their walls stay closed.
+pub fn braid<R: Rng>(maze: &mut Maze, ratio: f64, rng: &mut R) -> usize {
+    let mut dead_ends: Vec<_> = maze.dead_ends().collect();
+    dead_ends.shuffle(rng);
+    // rounded to the nearest integer without floating point functions, unavailable in `no_std`
+    let per_mille = (ratio.clamp(0.0, 1.0) * 1000.0) as usize;
+    let count = (dead_ends.len() * per_mille + 500) / 1000;
+
+    let mut opened = 0;
+    for position in dead_ends.into_iter().take(count) {
+        // may already be removed along with a neighboring dead end
+        if !maze.is_dead_end(position) {
+            continue;
+        }
+        let mut walled = maze.adjascent(position);
+        walled.retain(|neighbor| maze.is_walled(position, *neighbor));
+        let dead_end_neighbors: Vec<Pos> = walled
+            .iter()
+            .copied()
+            .filter(|&neighbor| maze.is_dead_end(neighbor))
+            .collect();
+        let neighbor = match dead_end_neighbors.choose(rng) {
+            Some(&neighbor) => neighbor,
+            None => match walled.choose(rng) {
+                Some(&neighbor) => neighbor,
+                None => continue,
+            },
+        };
+        maze.create_path(position, neighbor);
+        opened += 1;
+    }
+    opened
+}
+
+/// Generator wrapping another one, removing a ratio of the dead ends of its mazes with [`braid`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Braider<G> {
+    generator: G,
+    ratio: f64,
+}
+
+#[cfg(feature = "std")]
+impl<G: MazeGenerator> Braider<G> {
+    /// Constructor, removing a `ratio` of the dead ends, between 0 and 1, of the mazes created by `generator`.
+    pub fn new(generator: G, ratio: f64) -> Self {
+        Self { generator, ratio }
+    }
+
+    /// Ratio of the dead ends removed.
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    /// The wrapped generator.
+    pub fn inner(&self) -> &G {
+        &self.generator
+    }
+}
+
+#[cfg(feature = "std")]
+impl<G: MazeGenerator> MazeGenerator for Braider<G> {
+    fn generate(&mut self) -> Result<Maze, GenerationError> {
+        let mut maze = self.generator.generate()?;
+        braid(&mut maze, self.ratio, &mut thread_rng());
+        Ok(maze)
+    }
+
+    fn generate_seeded(&mut self, seed: u64) -> Result<Maze, GenerationError> {
+        let mut maze = self.generator.generate_seeded(seed)?;
+        braid(&mut maze, self.ratio, &mut StdRng::seed_from_u64(seed));
+        Ok(maze)
+    }
+
+    fn generate_with_progress(
+        &mut self,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        let mut maze = self.generator.generate_with_progress(progress)?;
+        braid(&mut maze, self.ratio, &mut thread_rng());
+        Ok(maze)
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn braiding() {
+    use super::KruskalGenerator;
+
+    let mut generator = Braider::new(KruskalGenerator::new(12, 8), 1.0);
+    let maze = generator.generate_seeded(0).unwrap();
+    assert_eq!(maze.dead_ends().count(), 0);
+    assert_eq!(maze, generator.generate_seeded(0).unwrap());
+    assert_eq!(maze.distance_field(maze.start()).reached(), 96);
+
+    // half of the dead ends are removed, some of them in pairs
+    let mut maze = KruskalGenerator::new(12, 8).generate_seeded(0).unwrap();
+    let dead_ends = maze.dead_ends().count();
+    let opened = braid(&mut maze, 0.5, &mut StdRng::seed_from_u64(0));
+    assert!(opened <= dead_ends / 2 + 1);
+    assert!(maze.dead_ends().count() <= dead_ends - opened);
+    let passages: usize = maze.iter().map(|(_, cell)| cell.degree()).sum::<usize>() / 2;
+    assert_eq!(passages, 96 - 1 + opened);
+
+    let mut unchanged = KruskalGenerator::new(12, 8).generate_seeded(0).unwrap();
+    assert_eq!(braid(&mut unchanged, 0.0, &mut thread_rng()), 0);
+
+    // a corridor closed into a loop by a portal has no dead end to remove
+    //  a b
+    //  c d
+    let (a, b, c, d) = (Pos::zero(), Pos::new(1, 0), Pos::new(0, 1), Pos::one());
+    let mut looped = Maze::new(2, 2, a, d, vec![(a, vec![b]), (b, vec![d]), (d, vec![c])]);
+    looped.add_portal(a, c);
+    assert_eq!(braid(&mut looped, 1.0, &mut thread_rng()), 0);
+    assert!(looped.is_walled(a, c));
+}
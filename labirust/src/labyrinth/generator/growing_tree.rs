@@ -0,0 +1,178 @@
+//! ## Growing tree
+//!
+//! This module contains the [`GrowingTreeGenerator`], growing the maze from a list of active cells picked by a [`CellSelection`].
+
+use alloc::{vec, vec::Vec};
+
+use rand::{prelude::SliceRandom, Rng};
+#[cfg(feature = "std")]
+use rand::{rngs::StdRng, thread_rng, SeedableRng};
+
+use super::GenerationError;
+#[cfg(feature = "std")]
+use super::MazeGenerator;
+use crate::{Maze, Pos, VisitedSet};
+
+/// How a [`GrowingTreeGenerator`] picks the active cell to grow the maze from.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CellSelection {
+    /// The cell added last, behaving like a recursive backtracker, with long winding corridors.
+    #[default]
+    Newest,
+    /// The cell added first, producing long straight corridors spreading from the first cell.
+    Oldest,
+    /// Any cell, behaving like Prim's algorithm, with many short dead ends.
+    Random,
+    /// The newest cell `newest` percent of the time, any cell otherwise.
+    Mixed { newest: u8 },
+}
+
+impl CellSelection {
+    /// Index of the cell to grow from among `count` active cells.
+    fn pick<R: Rng>(self, count: usize, rng: &mut R) -> usize {
+        match self {
+            Self::Newest => count - 1,
+            Self::Oldest => 0,
+            Self::Random => rng.gen_range(0..count),
+            Self::Mixed { newest } => match rng.gen_range(0..100) < newest {
+                true => count - 1,
+                false => rng.gen_range(0..count),
+            },
+        }
+    }
+}
+
+/// Generator growing a random spanning tree from a list of active cells.
+/// The maze is grown from an active cell picked by the [`CellSelection`] to one of its unvisited neighbors, which becomes active in turn.
+/// Cells without any unvisited neighbor are no longer active, and the maze is complete once no cell is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GrowingTreeGenerator {
+    width: isize,
+    height: isize,
+    selection: CellSelection,
+}
+
+impl GrowingTreeGenerator {
+    /// Constructor.
+    pub fn new(width: isize, height: isize, selection: CellSelection) -> Self {
+        Self {
+            width,
+            height,
+            selection,
+        }
+    }
+
+    /// How the active cell to grow from is picked.
+    pub fn selection(&self) -> CellSelection {
+        self.selection
+    }
+
+    /// Generate a [`Maze`], drawing random choices from the provided `rng`.
+    pub fn generate_with<R: Rng>(&self, rng: &mut R) -> Result<Maze, GenerationError> {
+        self.carve(rng, &mut |_, _| ())
+    }
+
+    /// Generate a [`Maze`], reporting the number of visited cells to `progress` as they are reached.
+    fn carve<R: Rng>(
+        &self,
+        rng: &mut R,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        let Self {
+            width,
+            height,
+            selection,
+        } = *self;
+        debug!("generating a {width}x{height} maze with the growing tree algorithm, picking {selection:?} cells");
+        let mut result = Maze::try_new(
+            width,
+            height,
+            Pos::zero(),
+            (width - 1, height - 1).into(),
+            Vec::new(),
+        )?;
+
+        let total = (width * height) as usize;
+        let mut visited = VisitedSet::new(width, height);
+        let first = Pos::new(rng.gen_range(0..width), rng.gen_range(0..height));
+        visited.insert(first);
+        let mut done = 1;
+        progress(done, total);
+        let mut active = vec![first];
+        while !active.is_empty() {
+            let index = selection.pick(active.len(), rng);
+            let current = active[index];
+            let mut unvisited = result.adjascent(current);
+            unvisited.retain(|neighbor| !visited.contains(*neighbor));
+            let Some(&next) = unvisited.choose(rng) else {
+                active.remove(index);
+                continue;
+            };
+            result.create_path(current, next);
+            visited.insert(next);
+            done += 1;
+            progress(done, total);
+            active.push(next);
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "std")]
+impl MazeGenerator for GrowingTreeGenerator {
+    fn generate(&mut self) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut thread_rng())
+    }
+
+    fn generate_seeded(&mut self, seed: u64) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut StdRng::seed_from_u64(seed))
+    }
+
+    fn generate_with_progress(
+        &mut self,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        self.carve(&mut thread_rng(), progress)
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn generation() {
+    let selections = [
+        CellSelection::Newest,
+        CellSelection::Oldest,
+        CellSelection::Random,
+        CellSelection::Mixed { newest: 50 },
+    ];
+    for selection in selections {
+        let mut generator = GrowingTreeGenerator::new(12, 8, selection);
+        let maze = generator.generate_seeded(0).unwrap();
+        super::assert_perfect(&maze);
+        assert_eq!(maze, generator.generate_seeded(0).unwrap());
+    }
+
+    // picking the newest cell leaves fewer dead ends than picking any
+    let dead_ends = |selection| {
+        let mut generator = GrowingTreeGenerator::new(30, 30, selection);
+        let maze = generator.generate_seeded(0).unwrap();
+        maze.iter().filter(|(_, cell)| cell.degree() == 1).count()
+    };
+    assert!(dead_ends(CellSelection::Newest) < dead_ends(CellSelection::Random));
+
+    let mut generator = GrowingTreeGenerator::new(12, 8, CellSelection::Random);
+    let mut reports = Vec::new();
+    generator
+        .generate_with_progress(&mut |done, total| reports.push((done, total)))
+        .unwrap();
+    assert_eq!(reports.len(), 96);
+    assert_eq!(reports.last(), Some(&(96, 96)));
+
+    let error = GrowingTreeGenerator::new(0, 10, CellSelection::default())
+        .generate()
+        .unwrap_err();
+    assert!(matches!(error, GenerationError::InvalidMaze(_)));
+}
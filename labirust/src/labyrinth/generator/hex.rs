@@ -0,0 +1,118 @@
+//! ## Hex
+//!
+//! This module contains the [`HexGenerator`], carving hexagonal mazes (c.f. [`crate::labyrinth::topology`]).
+
+use alloc::vec::Vec;
+
+use rand::{prelude::SliceRandom, Rng};
+#[cfg(feature = "std")]
+use rand::{rngs::StdRng, thread_rng, SeedableRng};
+
+use super::GenerationError;
+#[cfg(feature = "std")]
+use super::MazeGenerator;
+use crate::{Maze, Pos, VisitedSet};
+
+/// Generator carving a perfect hexagonal maze with a randomized depth-first search, each cell having up to six neighbors.
+/// The start is at the top left of the maze and the end at its bottom right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HexGenerator {
+    width: isize,
+    height: isize,
+}
+
+impl HexGenerator {
+    /// Constructor.
+    pub fn new(width: isize, height: isize) -> Self {
+        Self { width, height }
+    }
+
+    /// Generate a [`Maze`], drawing random choices from the provided `rng`.
+    pub fn generate_with<R: Rng>(&self, rng: &mut R) -> Result<Maze, GenerationError> {
+        self.carve(rng, &mut |_, _| ())
+    }
+
+    /// Generate a [`Maze`], reporting the number of visited cells to `progress` as they are reached.
+    fn carve<R: Rng>(
+        &self,
+        rng: &mut R,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        let Self { width, height } = *self;
+        debug!("generating a {width}x{height} hexagonal maze");
+        let end = Pos::new(width - 1, height - 1);
+        let mut result = Maze::try_new_hex(width, height, Pos::zero(), end)?;
+
+        let total = (width * height) as usize;
+        let mut visited = VisitedSet::new(width, height);
+        let mut stack = Vec::from([Pos::zero()]);
+        visited.insert(Pos::zero());
+        let mut done = 1;
+        progress(done, total);
+        while let Some(&current) = stack.last() {
+            let mut unvisited = result.adjascent(current);
+            unvisited.retain(|neighbor| !visited.contains(*neighbor));
+            let Some(&next) = unvisited.choose(rng) else {
+                stack.pop();
+                continue;
+            };
+            result.create_path(current, next);
+            visited.insert(next);
+            done += 1;
+            progress(done, total);
+            stack.push(next);
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "std")]
+impl MazeGenerator for HexGenerator {
+    fn generate(&mut self) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut thread_rng())
+    }
+
+    fn generate_seeded(&mut self, seed: u64) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut StdRng::seed_from_u64(seed))
+    }
+
+    fn generate_with_progress(
+        &mut self,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        self.carve(&mut thread_rng(), progress)
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn generation() {
+    use crate::{
+        executor::{drive, Rules},
+        implementations::{BreathFirst, DepthFirst},
+        Topology,
+    };
+
+    let mut generator = HexGenerator::new(9, 7);
+    for seed in 0..20 {
+        let maze = generator.generate_seeded(seed).unwrap();
+        assert_eq!(maze.topology(), Topology::Hex);
+        super::assert_perfect(&maze);
+    }
+    // solvers only walking through paths work unchanged
+    let maze = generator.generate_seeded(0).unwrap();
+    assert_eq!(maze, generator.generate_seeded(0).unwrap());
+    let distance = maze.distance_field(maze.start()).distance(maze.end());
+    let mut last = Vec::new();
+    drive(&maze, &mut BreathFirst::new(), Rules::default(), |_, g| {
+        last = g
+    })
+    .unwrap();
+    assert_eq!(Some(last.len() - 1), distance);
+    drive(&maze, &mut DepthFirst::new(), Rules::default(), |_, _| ()).unwrap();
+
+    let error = HexGenerator::new(0, 10).generate().unwrap_err();
+    assert!(matches!(error, GenerationError::InvalidMaze(_)));
+}
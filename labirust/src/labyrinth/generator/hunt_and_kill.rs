@@ -0,0 +1,140 @@
+//! ## Hunt and kill
+//!
+//! This module contains the [`HuntAndKillGenerator`], alternating random walks and hunts for the next cell to carve from.
+
+use alloc::vec::Vec;
+
+use rand::{prelude::SliceRandom, Rng};
+#[cfg(feature = "std")]
+use rand::{rngs::StdRng, thread_rng, SeedableRng};
+
+use super::GenerationError;
+#[cfg(feature = "std")]
+use super::MazeGenerator;
+use crate::{Maze, Pos, VisitedSet};
+
+/// Generator walking randomly through unvisited cells until stuck, then hunting for the next walk.
+/// The hunt scans the maze row by row for an unvisited cell next to a visited one, joins them and walks again from there.
+/// Produces long winding corridors with few dead ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HuntAndKillGenerator {
+    width: isize,
+    height: isize,
+}
+
+impl HuntAndKillGenerator {
+    /// Constructor.
+    pub fn new(width: isize, height: isize) -> Self {
+        Self { width, height }
+    }
+
+    /// Generate a [`Maze`], drawing random choices from the provided `rng`.
+    pub fn generate_with<R: Rng>(&self, rng: &mut R) -> Result<Maze, GenerationError> {
+        self.carve(rng, &mut |_, _| ())
+    }
+
+    /// Generate a [`Maze`], reporting the number of visited cells to `progress` as they are reached.
+    fn carve<R: Rng>(
+        &self,
+        rng: &mut R,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        let Self { width, height } = *self;
+        debug!("generating a {width}x{height} maze with the hunt-and-kill algorithm");
+        let mut result = Maze::try_new(
+            width,
+            height,
+            Pos::zero(),
+            (width - 1, height - 1).into(),
+            Vec::new(),
+        )?;
+
+        let total = (width * height) as usize;
+        let mut visited = VisitedSet::new(width, height);
+        let mut current = Pos::new(rng.gen_range(0..width), rng.gen_range(0..height));
+        visited.insert(current);
+        let mut done = 1;
+        progress(done, total);
+        // cells before this index are all visited, no need to hunt there again
+        let mut hunted = 0;
+        loop {
+            let mut unvisited = result.adjascent(current);
+            unvisited.retain(|neighbor| !visited.contains(*neighbor));
+            let next = match unvisited.choose(rng) {
+                // kill, walking on
+                Some(&next) => {
+                    result.create_path(current, next);
+                    next
+                }
+                // hunt for a cell to walk from
+                None => {
+                    while hunted < total && visited.contains(Pos::from_index(hunted, width)) {
+                        hunted += 1;
+                    }
+                    let hunt = hunted..total;
+                    let found = hunt
+                        .map(|index| Pos::from_index(index, width))
+                        .find_map(|cell| {
+                            if visited.contains(cell) {
+                                return None;
+                            }
+                            let mut joined = result.adjascent(cell);
+                            joined.retain(|neighbor| visited.contains(*neighbor));
+                            joined.choose(rng).map(|&neighbor| (cell, neighbor))
+                        });
+                    let Some((cell, neighbor)) = found else {
+                        break;
+                    };
+                    result.create_path(neighbor, cell);
+                    cell
+                }
+            };
+            visited.insert(next);
+            done += 1;
+            progress(done, total);
+            current = next;
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "std")]
+impl MazeGenerator for HuntAndKillGenerator {
+    fn generate(&mut self) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut thread_rng())
+    }
+
+    fn generate_seeded(&mut self, seed: u64) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut StdRng::seed_from_u64(seed))
+    }
+
+    fn generate_with_progress(
+        &mut self,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        self.carve(&mut thread_rng(), progress)
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn generation() {
+    let mut generator = HuntAndKillGenerator::new(12, 8);
+    for seed in 0..50 {
+        super::assert_perfect(&generator.generate_seeded(seed).unwrap());
+    }
+    let maze = generator.generate_seeded(0).unwrap();
+    assert_eq!(maze, generator.generate_seeded(0).unwrap());
+
+    let mut reports = Vec::new();
+    generator
+        .generate_with_progress(&mut |done, total| reports.push((done, total)))
+        .unwrap();
+    assert_eq!(reports.len(), 96);
+    assert_eq!(reports.last(), Some(&(96, 96)));
+
+    let error = HuntAndKillGenerator::new(0, 10).generate().unwrap_err();
+    assert!(matches!(error, GenerationError::InvalidMaze(_)));
+}
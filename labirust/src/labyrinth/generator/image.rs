@@ -0,0 +1,94 @@
+//! ## Image
+//!
+//! This module contains the [`ImageGenerator`], reading mazes from image files.
+
+use std::path::Path;
+
+use ::image::RgbaImage;
+
+use super::{GenerationError, MazeGenerator};
+use crate::Maze;
+
+/// Generator reading a [`Maze`] from an image laid out like [`Maze::to_rgba_image`], such as a PNG or BMP file.
+/// Dark pixels are walls and light pixels are passages, the start being marked by a green cell and the end by a red one,
+/// defaulting to the top left and bottom right cells (c.f. [`Maze::from_rgba_image`]).
+/// Images where each cell and wall takes a square of pixels are read by sampling the center of each square (c.f. [`ImageGenerator::scale`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageGenerator {
+    image: RgbaImage,
+    scale: u32,
+}
+
+impl ImageGenerator {
+    /// Constructor, reading the maze from `image`.
+    pub fn new(image: RgbaImage) -> Self {
+        Self { image, scale: 1 }
+    }
+
+    /// Constructor, loading the image from the file at `path`, in any format enabled on the `image` crate.
+    pub fn open(path: impl AsRef<Path>) -> crate::Result<Self> {
+        let image = ::image::open(path)?.to_rgba8();
+        Ok(Self::new(image))
+    }
+
+    /// Sets the side length, in pixels, of the square taken by each cell and wall of the image, default is 1.
+    pub fn scale(self, scale: u32) -> Self {
+        let scale = scale.max(1);
+        Self { scale, ..self }
+    }
+
+    /// The image the maze is read from.
+    pub fn image(&self) -> &RgbaImage {
+        &self.image
+    }
+}
+
+impl MazeGenerator for ImageGenerator {
+    fn generate(&mut self) -> Result<Maze, GenerationError> {
+        let Self { image, scale } = self;
+        if *scale == 1 {
+            return Ok(Maze::from_rgba_image(image)?);
+        }
+        let (width, height) = image.dimensions();
+        let center = *scale / 2;
+        let sampled = RgbaImage::from_fn(width / *scale, height / *scale, |x, y| {
+            *image.get_pixel(x * *scale + center, y * *scale + center)
+        });
+        Ok(Maze::from_rgba_image(&sampled)?)
+    }
+
+    /// Always the same maze, read from the image whatever the seed.
+    fn generate_seeded(&mut self, _seed: u64) -> Result<Maze, GenerationError> {
+        self.generate()
+    }
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn image_generation() {
+    use ::image::imageops::{resize, FilterType};
+
+    use crate::{MazeError, SimpleGenerator};
+
+    let maze = SimpleGenerator::new(9, 6).generate_seeded(0).unwrap();
+    let image = maze.to_rgba_image();
+    let path = std::env::temp_dir().join("labirust-image-generation.bmp");
+    image.save(&path).unwrap();
+    let mut generator = ImageGenerator::open(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(generator.generate().unwrap(), maze);
+
+    let (width, height) = image.dimensions();
+    let scaled = resize(&image, width * 4, height * 4, FilterType::Nearest);
+    let mut generator = ImageGenerator::new(scaled).scale(4);
+    assert_eq!(generator.generate_seeded(1).unwrap(), maze);
+
+    let mut generator = ImageGenerator::new(RgbaImage::new(4, 4));
+    let error = generator.generate().unwrap_err();
+    let size = MazeError::InvalidSize {
+        width: 4,
+        height: 4,
+    };
+    assert_eq!(error, GenerationError::InvalidMaze(size));
+    assert!(ImageGenerator::open(std::env::temp_dir().join("labirust-missing.png")).is_err());
+}
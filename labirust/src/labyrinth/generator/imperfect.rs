@@ -0,0 +1,143 @@
+//! ## Imperfect
+//!
+//! This module contains the [`ImperfectGenerator`], carving mazes with loops.
+
+use alloc::{vec, vec::Vec};
+
+use rand::{prelude::SliceRandom, Rng};
+#[cfg(feature = "std")]
+use rand::{rngs::StdRng, thread_rng, SeedableRng};
+
+use super::GenerationError;
+#[cfg(feature = "std")]
+use super::MazeGenerator;
+use crate::{Maze, Pos, VisitedSet};
+
+/// Generator carving like the [`super::SimpleGenerator`], occasionally opening extra passages to create loops.
+/// When backtracking from a cell, each wall separating it from an already visited cell is opened with the loop probability,
+/// so that a probability of 0 creates perfect mazes, and a probability of 1 opens every wall.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImperfectGenerator {
+    width: isize,
+    height: isize,
+    loop_probability: f64,
+}
+
+impl ImperfectGenerator {
+    /// Constructor, opening extra passages with `loop_probability`, between 0 and 1.
+    pub fn new(width: isize, height: isize, loop_probability: f64) -> Self {
+        let loop_probability = loop_probability.clamp(0.0, 1.0);
+        Self {
+            width,
+            height,
+            loop_probability,
+        }
+    }
+
+    /// Probability of opening each wall between a cell and a visited neighbor when backtracking.
+    pub fn loop_probability(&self) -> f64 {
+        self.loop_probability
+    }
+
+    /// Generate a [`Maze`], drawing random choices from the provided `rng`.
+    pub fn generate_with<R: Rng>(&self, rng: &mut R) -> Result<Maze, GenerationError> {
+        self.carve(rng, &mut |_, _| ())
+    }
+
+    /// Generate a [`Maze`], reporting the number of visited cells to `progress` as they are reached.
+    fn carve<R: Rng>(
+        &self,
+        rng: &mut R,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        let Self {
+            width,
+            height,
+            loop_probability,
+        } = *self;
+        debug!("generating a {width}x{height} maze with a loop probability of {loop_probability}");
+        let mut result = Maze::try_new(
+            width,
+            height,
+            Pos::zero(),
+            (width - 1, height - 1).into(),
+            Vec::new(),
+        )?;
+
+        let total = (width * height) as usize;
+        let mut visited = VisitedSet::new(width, height);
+        visited.insert(Pos::zero());
+        let mut done = 1;
+        progress(done, total);
+        let mut stack = vec![Pos::zero()];
+        while let Some(&current) = stack.last() {
+            let mut neighbors = result.adjascent(current);
+            neighbors.shuffle(rng);
+            if let Some(&next) = neighbors.iter().find(|&&p| !visited.contains(p)) {
+                result.create_path(current, next);
+                visited.insert(next);
+                done += 1;
+                progress(done, total);
+                stack.push(next);
+                continue;
+            }
+            for neighbor in neighbors {
+                if result.is_walled(current, neighbor) && rng.gen_bool(loop_probability) {
+                    result.create_path(current, neighbor);
+                }
+            }
+            stack.pop();
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "std")]
+impl MazeGenerator for ImperfectGenerator {
+    fn generate(&mut self) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut thread_rng())
+    }
+
+    fn generate_seeded(&mut self, seed: u64) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut StdRng::seed_from_u64(seed))
+    }
+
+    fn generate_with_progress(
+        &mut self,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        self.carve(&mut thread_rng(), progress)
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn generation() {
+    let passages = |maze: &Maze| maze.iter().map(|(_, cell)| cell.degree()).sum::<usize>() / 2;
+
+    let mut generator = ImperfectGenerator::new(12, 8, 0.0);
+    let maze = generator.generate_seeded(0).unwrap();
+    super::assert_perfect(&maze);
+
+    let mut generator = ImperfectGenerator::new(12, 8, 0.2);
+    let maze = generator.generate_seeded(0).unwrap();
+    assert_eq!(maze, generator.generate_seeded(0).unwrap());
+    assert_eq!(maze.distance_field(maze.start()).reached(), 96);
+    assert!(passages(&maze) > 95);
+
+    // every wall is opened
+    let maze = ImperfectGenerator::new(12, 8, 1.0).generate().unwrap();
+    assert_eq!(passages(&maze), 12 * 7 + 8 * 11);
+
+    let mut reports = Vec::new();
+    generator
+        .generate_with_progress(&mut |done, total| reports.push((done, total)))
+        .unwrap();
+    assert_eq!(reports.len(), 96);
+    assert_eq!(reports.last(), Some(&(96, 96)));
+
+    let error = ImperfectGenerator::new(0, 10, 0.2).generate().unwrap_err();
+    assert!(matches!(error, GenerationError::InvalidMaze(_)));
+}
@@ -0,0 +1,114 @@
+//! ## Kruskal
+//!
+//! This module contains the [`KruskalGenerator`], joining cells through walls taken in random order.
+
+use alloc::vec::Vec;
+
+use rand::{prelude::SliceRandom, Rng};
+#[cfg(feature = "std")]
+use rand::{rngs::StdRng, thread_rng, SeedableRng};
+
+#[cfg(feature = "std")]
+use super::MazeGenerator;
+use super::{union_find::UnionFind, GenerationError};
+use crate::{Direction, Maze, Pos};
+
+/// Generator building a random spanning tree with Kruskal's algorithm.
+/// Every wall is considered once in random order, and opened when the cells it separates are not joined yet.
+/// Produces mazes with many short dead ends, the branching being spread evenly over the maze.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KruskalGenerator {
+    width: isize,
+    height: isize,
+}
+
+impl KruskalGenerator {
+    /// Constructor.
+    pub fn new(width: isize, height: isize) -> Self {
+        Self { width, height }
+    }
+
+    /// Generate a [`Maze`], drawing random choices from the provided `rng`.
+    pub fn generate_with<R: Rng>(&self, rng: &mut R) -> Result<Maze, GenerationError> {
+        self.carve(rng, &mut |_, _| ())
+    }
+
+    /// Generate a [`Maze`], reporting the number of joined cells to `progress` as walls are opened.
+    fn carve<R: Rng>(
+        &self,
+        rng: &mut R,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        let Self { width, height } = *self;
+        debug!("generating a {width}x{height} maze with kruskal's algorithm");
+        let mut result = Maze::try_new(
+            width,
+            height,
+            Pos::zero(),
+            (width - 1, height - 1).into(),
+            Vec::new(),
+        )?;
+
+        let mut walls: Vec<_> = result
+            .iter()
+            .flat_map(|(position, _)| {
+                [Direction::East, Direction::South]
+                    .map(|direction| (position, position + direction))
+            })
+            .filter(|&(_, neighbor)| result.is_inside(neighbor))
+            .collect();
+        walls.shuffle(rng);
+
+        let total = (width * height) as usize;
+        let mut sets = UnionFind::new(total);
+        let mut joined = 1;
+        progress(joined, total);
+        for (position, neighbor) in walls {
+            if sets.union(position.to_index(width), neighbor.to_index(width)) {
+                result.create_path(position, neighbor);
+                joined += 1;
+                progress(joined, total);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "std")]
+impl MazeGenerator for KruskalGenerator {
+    fn generate(&mut self) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut thread_rng())
+    }
+
+    fn generate_seeded(&mut self, seed: u64) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut StdRng::seed_from_u64(seed))
+    }
+
+    fn generate_with_progress(
+        &mut self,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        self.carve(&mut thread_rng(), progress)
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn generation() {
+    let mut generator = KruskalGenerator::new(12, 8);
+    let maze = generator.generate_seeded(0).unwrap();
+    super::assert_perfect(&maze);
+    assert_eq!(maze, generator.generate_seeded(0).unwrap());
+
+    let mut reports = Vec::new();
+    generator
+        .generate_with_progress(&mut |done, total| reports.push((done, total)))
+        .unwrap();
+    assert_eq!(reports.len(), 96);
+    assert_eq!(reports.last(), Some(&(96, 96)));
+
+    let error = KruskalGenerator::new(0, 10).generate().unwrap_err();
+    assert!(matches!(error, GenerationError::InvalidMaze(_)));
+}
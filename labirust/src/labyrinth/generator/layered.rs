@@ -0,0 +1,141 @@
+//! ## Layered
+//!
+//! This module contains the [`LayeredGenerator`], carving mazes of several levels linked by stairs (c.f. [`crate::labyrinth::level`]).
+
+use alloc::vec::Vec;
+
+use rand::{prelude::SliceRandom, Rng};
+#[cfg(feature = "std")]
+use rand::{rngs::StdRng, thread_rng, SeedableRng};
+
+use super::GenerationError;
+#[cfg(feature = "std")]
+use super::MazeGenerator;
+use crate::{labyrinth::level::Pos3, Maze, Pos, VisitedSet};
+
+/// Generator carving a perfect maze through several levels with a randomized depth-first search,
+/// climbing up or down stairs as one of the moves available from each cell.
+/// The start is at the top left of the first level and the end at the bottom right of the last one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayeredGenerator {
+    width: isize,
+    height: isize,
+    levels: isize,
+}
+
+impl LayeredGenerator {
+    /// Constructor, for mazes of `levels` levels of `width` by `height` cells.
+    pub fn new(width: isize, height: isize, levels: isize) -> Self {
+        Self {
+            width,
+            height,
+            levels,
+        }
+    }
+
+    pub fn levels(&self) -> isize {
+        self.levels
+    }
+
+    /// Generate a [`Maze`], drawing random choices from the provided `rng`.
+    pub fn generate_with<R: Rng>(&self, rng: &mut R) -> Result<Maze, GenerationError> {
+        self.carve(rng, &mut |_, _| ())
+    }
+
+    /// Generate a [`Maze`], reporting the number of visited cells to `progress` as they are reached.
+    fn carve<R: Rng>(
+        &self,
+        rng: &mut R,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        let Self {
+            width,
+            height,
+            levels,
+        } = *self;
+        debug!("generating a {width}x{height} maze of {levels} levels");
+        let start = Pos3::new(0, 0, 0);
+        let end = Pos3::new(width - 1, height - 1, levels - 1);
+        let mut result = Maze::try_new_levels(width, height, levels, start, end)?;
+
+        let total = (width * height * levels) as usize;
+        let mut visited = VisitedSet::new(width, height * levels);
+        let mut stack = Vec::from([result.start()]);
+        visited.insert(result.start());
+        let mut done = 1;
+        progress(done, total);
+        while let Some(&current) = stack.last() {
+            let mut unvisited = result.adjascent(current);
+            unvisited.extend(Self::stairs(&result, current));
+            unvisited.retain(|neighbor| !visited.contains(*neighbor));
+            let Some(&next) = unvisited.choose(rng) else {
+                stack.pop();
+                continue;
+            };
+            if result.is_same_level(current, next) {
+                result.create_path(current, next);
+            } else {
+                let lower = if current.y() < next.y() {
+                    current
+                } else {
+                    next
+                };
+                result.try_add_stairs(result.to_pos3(lower))?;
+            }
+            visited.insert(next);
+            done += 1;
+            progress(done, total);
+            stack.push(next);
+        }
+
+        Ok(result)
+    }
+
+    /// Positions at the same coordinates as `position` on the previous and next levels.
+    fn stairs(maze: &Maze, position: Pos) -> impl Iterator<Item = Pos> + '_ {
+        let level_height = maze.level_height();
+        [-level_height, level_height]
+            .into_iter()
+            .map(move |offset| Pos::new(position.x(), position.y() + offset))
+            .filter(|&other| maze.is_inside(other))
+    }
+}
+
+#[cfg(feature = "std")]
+impl MazeGenerator for LayeredGenerator {
+    fn generate(&mut self) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut thread_rng())
+    }
+
+    fn generate_seeded(&mut self, seed: u64) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut StdRng::seed_from_u64(seed))
+    }
+
+    fn generate_with_progress(
+        &mut self,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        self.carve(&mut thread_rng(), progress)
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn generation() {
+    let mut generator = LayeredGenerator::new(6, 4, 3);
+    for seed in 0..20 {
+        let maze = generator.generate_seeded(seed).unwrap();
+        let cells = maze.iter().len();
+        let passages: usize = maze.iter().map(|(_, cell)| cell.degree()).sum::<usize>() / 2;
+        assert_eq!(maze.distance_field(maze.start()).reached(), cells);
+        assert_eq!(passages + maze.portals().count(), cells - 1);
+        assert_eq!(maze.to_pos3(maze.end()), Pos3::new(5, 3, 2));
+    }
+    let maze = generator.generate_seeded(0).unwrap();
+    assert_eq!(maze, generator.generate_seeded(0).unwrap());
+    assert_eq!(maze.levels(), 3);
+
+    let error = LayeredGenerator::new(4, 4, 0).generate().unwrap_err();
+    assert!(matches!(error, GenerationError::InvalidMaze(_)));
+}
@@ -0,0 +1,217 @@
+//! ## Parallel
+//!
+//! This module contains a generator carving large mazes on several threads at once.
+
+use std::{sync::mpsc, thread};
+
+use rand::{prelude::SliceRandom, rngs::StdRng, thread_rng, Rng, SeedableRng};
+use rayon::prelude::*;
+
+use super::{carve_rectangle, GenerationError};
+use crate::{Direction, Grid, Maze, MazeGenerator, Pos};
+
+/// Generator splitting the maze in square chunks, carving each of them concurrently with the same technique as [`super::SimpleGenerator`].
+/// The chunks are then connected through a random spanning tree, one passage per connection, so that the resulting maze stays perfect.
+/// Each chunk draws from its own random generator, seeded from the main one beforehand, so that a seed gives the same maze whatever the scheduling of the threads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParallelGenerator {
+    width: isize,
+    height: isize,
+    chunk_size: isize,
+}
+
+impl ParallelGenerator {
+    /// Constructor.
+    pub fn new(width: isize, height: isize) -> Self {
+        Self {
+            width,
+            height,
+            chunk_size: 64,
+        }
+    }
+
+    /// Sets the side length of the chunks carved independently, default is 64.
+    pub fn chunk_size(self, chunk_size: isize) -> Self {
+        let chunk_size = chunk_size.max(1);
+        Self { chunk_size, ..self }
+    }
+
+    /// Generate a [`Maze`], drawing random choices from the provided `rng`.
+    pub fn generate_with<R: Rng>(&self, rng: &mut R) -> Result<Maze, GenerationError> {
+        self.carve(rng, &mut |_, _| ())
+    }
+
+    /// Generate a [`Maze`], reporting the number of cells of the chunks carved so far to `progress`.
+    /// Chunks are carved on a separate thread, so that `progress` is called from the current one as they complete.
+    fn carve<R: Rng>(
+        &self,
+        rng: &mut R,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        let Self {
+            width,
+            height,
+            chunk_size,
+        } = *self;
+        let mut result = Maze::try_new(
+            width,
+            height,
+            Pos::zero(),
+            (width - 1, height - 1).into(),
+            Vec::new(),
+        )?;
+        let chunks_width = (width + chunk_size - 1) / chunk_size;
+        let chunks_height = (height + chunk_size - 1) / chunk_size;
+        debug!("generating a {width}x{height} maze in {chunks_width}x{chunks_height} chunks");
+
+        let seeds: Vec<u64> = (0..chunks_width * chunks_height)
+            .map(|_| rng.gen())
+            .collect();
+        let (sender, receiver) = mpsc::channel();
+        let chunks: Vec<_> = thread::scope(|scope| {
+            let carving = scope.spawn(move || {
+                seeds
+                    .into_par_iter()
+                    .enumerate()
+                    .map_with(sender, |sender, (index, seed)| {
+                        let origin = Pos::from_index(index, chunks_width) * chunk_size;
+                        let end = Pos::new(
+                            (origin.x() + chunk_size).min(width),
+                            (origin.y() + chunk_size).min(height),
+                        );
+                        let passages =
+                            carve_rectangle(origin, end, &mut StdRng::seed_from_u64(seed));
+                        let (chunk_width, chunk_height) = (end - origin).decompose();
+                        let _ = sender.send((chunk_width * chunk_height) as usize);
+                        passages
+                    })
+                    .collect()
+            });
+            // the channel closes once every chunk is carved
+            let total = (width * height) as usize;
+            let mut done = 0;
+            for cells in receiver {
+                done += cells;
+                progress(done, total);
+            }
+            carving.join().expect("carving does not panic")
+        });
+
+        for (position_a, position_b) in chunks.into_iter().flatten() {
+            result.create_path(position_a, position_b);
+        }
+        for (position_a, position_b) in connect_chunks(width, height, chunk_size, rng) {
+            result.create_path(position_a, position_b);
+        }
+
+        Ok(result)
+    }
+}
+
+impl MazeGenerator for ParallelGenerator {
+    fn generate(&mut self) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut thread_rng())
+    }
+
+    fn generate_seeded(&mut self, seed: u64) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut StdRng::seed_from_u64(seed))
+    }
+
+    fn generate_with_progress(
+        &mut self,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        self.carve(&mut thread_rng(), progress)
+    }
+}
+
+/// Pick the passages linking chunks together, following a random spanning tree of the chunk grid.
+fn connect_chunks<R: Rng>(
+    width: isize,
+    height: isize,
+    chunk_size: isize,
+    rng: &mut R,
+) -> Vec<(Pos, Pos)> {
+    let chunks_width = (width + chunk_size - 1) / chunk_size;
+    let chunks_height = (height + chunk_size - 1) / chunk_size;
+    let mut visited = Grid::new(chunks_width, chunks_height, false);
+    let mut passages = Vec::new();
+    let mut stack = vec![Pos::zero()];
+    visited[Pos::zero()] = true;
+
+    while let Some(&current) = stack.last() {
+        let mut directions = Direction::ALL;
+        directions.shuffle(rng);
+        let next = directions
+            .into_iter()
+            .find(|&d| visited.get(current + d) == Some(&false));
+        let Some(direction) = next else {
+            stack.pop();
+            continue;
+        };
+        let next = current + direction;
+        visited[next] = true;
+        stack.push(next);
+
+        // a random cell along the border shared by both chunks
+        let origin = current * chunk_size;
+        let offset = match direction {
+            Direction::North | Direction::South => {
+                let span = (width - origin.x()).min(chunk_size);
+                let y = if direction == Direction::North {
+                    0
+                } else {
+                    chunk_size - 1
+                };
+                Pos::new(rng.gen_range(0..span), y)
+            }
+            Direction::East | Direction::West => {
+                let span = (height - origin.y()).min(chunk_size);
+                let x = if direction == Direction::West {
+                    0
+                } else {
+                    chunk_size - 1
+                };
+                Pos::new(x, rng.gen_range(0..span))
+            }
+        };
+        let cell = origin + offset;
+        passages.push((cell, cell + direction));
+    }
+
+    passages
+}
+
+#[test]
+fn parallel_generation() {
+    let mut generator = ParallelGenerator::new(37, 23).chunk_size(8);
+    let maze = generator.generate().unwrap();
+    let mut reached = Grid::new(maze.width(), maze.height(), false);
+    let mut stack = vec![maze.start()];
+    reached[maze.start()] = true;
+    let mut passages = 0;
+    while let Some(current) = stack.pop() {
+        for next in maze.paths_from_iter(current) {
+            passages += 1;
+            if !reached[next] {
+                reached[next] = true;
+                stack.push(next);
+            }
+        }
+    }
+    assert!(reached.iter().all(|(_, &r)| r));
+    // a perfect maze is a tree, each passage being counted from both ends
+    assert_eq!(passages / 2, 37 * 23 - 1);
+
+    let seeded = generator.generate_seeded(0).unwrap();
+    assert_eq!(seeded, generator.generate_seeded(0).unwrap());
+
+    // one report per chunk, 5x3 of them
+    let mut reports = Vec::new();
+    generator
+        .generate_with_progress(&mut |done, total| reports.push((done, total)))
+        .unwrap();
+    assert_eq!(reports.len(), 15);
+    assert_eq!(reports.last(), Some(&(37 * 23, 37 * 23)));
+}
@@ -0,0 +1,114 @@
+//! ## Placement
+//!
+//! This module contains the [`Placement`] of the start and the end of generated mazes.
+
+use alloc::vec::Vec;
+
+use rand::{prelude::SliceRandom, Rng};
+
+use crate::{Maze, MazeError, Pos};
+
+/// Where the start and the end of a [`Maze`] are placed once it is carved.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Placement {
+    /// The start at the top left corner and the end at the bottom right one.
+    #[default]
+    Corners,
+    /// The given positions.
+    Explicit { start: Pos, end: Pos },
+    /// Two distinct random positions, the same one only for a single cell maze.
+    Random,
+    /// Two positions among the farthest apart from each other through the passages, found by two breath-first floods,
+    /// which is exact for perfect mazes and an approximation otherwise.
+    FarthestApart,
+}
+
+impl Placement {
+    /// Move the start and the end of `maze` according to the [`Placement`], drawing random positions from the provided `rng`.
+    /// Fails without modifying the maze if explicit positions are out of bounds.
+    pub fn apply<R: Rng>(self, maze: &mut Maze, rng: &mut R) -> Result<(), MazeError> {
+        let (width, height) = maze.size();
+        let (start, end) = match self {
+            Self::Corners => (Pos::zero(), Pos::new(width - 1, height - 1)),
+            Self::Explicit { start, end } => (start, end),
+            Self::Random => {
+                let mut random = || Pos::new(rng.gen_range(0..width), rng.gen_range(0..height));
+                let start = random();
+                let mut end = random();
+                // redrawn until it differs, which always happens but for a single cell
+                while end == start && width * height > 1 {
+                    end = random();
+                }
+                (start, end)
+            }
+            Self::FarthestApart => {
+                let start = maze.distance_field(Pos::zero()).farthest();
+                let end = maze.distance_field(start).farthest();
+                (start, end)
+            }
+        };
+        debug!("placing the start at {start:?} and the end at {end:?}");
+        maze.try_set_endpoints(start, end)
+    }
+}
+
+/// Add random ends to `maze` until it has `count` of them, or every cell but the start is an end.
+pub(crate) fn add_random_ends<R: Rng>(maze: &mut Maze, count: usize, rng: &mut R) {
+    let mut candidates: Vec<Pos> = maze
+        .iter()
+        .map(|(position, _)| position)
+        .filter(|&position| !maze.is_start(position) && !maze.is_end(position))
+        .collect();
+    let missing = count.saturating_sub(maze.ends().count());
+    let (chosen, _) = candidates.partial_shuffle(rng, missing);
+    for &end in chosen.iter() {
+        maze.try_add_end(end).expect("inside the maze");
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn placement() {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{MazeGenerator, SimpleGenerator};
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut maze = SimpleGenerator::new(12, 8).generate_seeded(0).unwrap();
+    let diameter = maze
+        .iter()
+        .map(|(position, _)| maze.distance_field(position).max_distance())
+        .max();
+    Placement::FarthestApart.apply(&mut maze, &mut rng).unwrap();
+    let field = maze.distance_field(maze.start());
+    assert_eq!(field.distance(maze.end()), diameter);
+
+    let (start, end) = (Pos::new(3, 2), Pos::new(5, 7));
+    Placement::Explicit { start, end }
+        .apply(&mut maze, &mut rng)
+        .unwrap();
+    assert_eq!((maze.start(), maze.end()), (start, end));
+    let outside = Placement::Explicit {
+        start,
+        end: Pos::new(12, 0),
+    };
+    assert!(outside.apply(&mut maze, &mut rng).is_err());
+    assert_eq!(maze.iter().filter(|(_, cell)| cell.is_start()).count(), 1);
+
+    Placement::Random.apply(&mut maze, &mut rng).unwrap();
+    assert!(maze.is_inside(maze.start()) && maze.is_inside(maze.end()));
+    Placement::Corners.apply(&mut maze, &mut rng).unwrap();
+    assert_eq!(maze.end(), Pos::new(11, 7));
+
+    add_random_ends(&mut maze, 5, &mut rng);
+    assert_eq!(maze.ends().count(), 5);
+    assert!(!maze.is_end(maze.start()));
+    let mut tiny = Maze::new(2, 1, Pos::zero(), Pos::new(1, 0), Vec::new());
+    add_random_ends(&mut tiny, 5, &mut rng);
+    assert_eq!(tiny.ends().count(), 1);
+    for _ in 0..20 {
+        Placement::Random.apply(&mut tiny, &mut rng).unwrap();
+        assert_ne!(tiny.start(), tiny.end());
+    }
+}
@@ -0,0 +1,65 @@
+//! ## Seeded
+//!
+//! This module contains the [`SeededGenerator`], making any seedable generator reproducible.
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{GenerationError, MazeGenerator};
+use crate::Maze;
+
+/// Generator wrapping another one, generating each [`Maze`] from a seed drawn from its own seeded random generator.
+/// The sequence of mazes generated is then the same for a given seed, which makes them reproducible in tests, benchmarks or bug reports.
+/// The wrapped generator must support [`MazeGenerator::generate_seeded`], or generation fails with [`GenerationError::Unseedable`].
+#[derive(Debug, Clone)]
+pub struct SeededGenerator<G> {
+    generator: G,
+    seed: u64,
+    rng: StdRng,
+}
+
+impl<G: MazeGenerator> SeededGenerator<G> {
+    /// Constructor, the sequence of mazes generated being determined by `seed`.
+    pub fn new(generator: G, seed: u64) -> Self {
+        let rng = StdRng::seed_from_u64(seed);
+        Self {
+            generator,
+            seed,
+            rng,
+        }
+    }
+
+    /// The seed determining the sequence of mazes generated.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The wrapped generator.
+    pub fn inner(&self) -> &G {
+        &self.generator
+    }
+}
+
+impl<G: MazeGenerator> MazeGenerator for SeededGenerator<G> {
+    fn generate(&mut self) -> Result<Maze, GenerationError> {
+        let seed = self.rng.gen();
+        self.generator.generate_seeded(seed)
+    }
+
+    fn generate_seeded(&mut self, seed: u64) -> Result<Maze, GenerationError> {
+        self.generator.generate_seeded(seed)
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn seeded_generation() {
+    use super::SimpleGenerator;
+
+    let mut generator = SimpleGenerator::new(12, 8).with_seed(3);
+    let mut again = SeededGenerator::new(SimpleGenerator::new(12, 8), 3);
+    let first = generator.generate().unwrap();
+    assert_eq!(first, again.generate().unwrap());
+    assert_eq!(generator.generate().unwrap(), again.generate().unwrap());
+    assert_ne!(first, generator.generate().unwrap());
+    assert_eq!(generator.seed(), 3);
+}
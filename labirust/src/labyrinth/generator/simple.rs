@@ -0,0 +1,234 @@
+//! ## Simple
+//!
+//! This module contains the [`SimpleGenerator`], and the carving technique shared with the other random generators.
+
+use alloc::{vec, vec::Vec};
+
+use rand::{prelude::SliceRandom, Rng};
+#[cfg(feature = "std")]
+use rand::{rngs::StdRng, thread_rng, SeedableRng};
+
+#[cfg(feature = "std")]
+use super::MazeGenerator;
+use super::{add_random_ends, GenerationError, Placement};
+use crate::{Direction, Grid, Maze, Neighbors, Pos, Topology, VisitedSet};
+
+/// Most common maze generation technique, recursively creating paths to unvisited cells, each time choosing next direction at random.
+/// The recursion is driven by an explicit stack, so that large mazes do not overflow the call stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimpleGenerator {
+    width: isize,
+    height: isize,
+    placement: Placement,
+    ends: usize,
+    topology: Topology,
+}
+
+impl SimpleGenerator {
+    pub fn new(width: isize, height: isize) -> Self {
+        Self {
+            height,
+            width,
+            placement: Placement::Corners,
+            ends: 1,
+            topology: Topology::Square,
+        }
+    }
+
+    /// Sets where the start and the end are placed once the maze is carved, default is [`Placement::Corners`].
+    pub fn placement(self, placement: Placement) -> Self {
+        Self { placement, ..self }
+    }
+
+    /// Sets the number of ends of the maze, the ones besides the placed end being spread at random, default is 1.
+    pub fn ends(self, ends: usize) -> Self {
+        Self { ends, ..self }
+    }
+
+    /// Sets how the cells of the maze neighbor each other, default is [`Topology::Square`].
+    pub fn topology(self, topology: Topology) -> Self {
+        Self { topology, ..self }
+    }
+
+    /// Sets whether the maze wraps at its edges (c.f. [`Topology::Torus`]), needing at least 3 cells in each dimension, default is `false`.
+    pub fn wrapping(self, wrapping: bool) -> Self {
+        let topology = if wrapping {
+            Topology::Torus
+        } else {
+            Topology::Square
+        };
+        self.topology(topology)
+    }
+
+    /// Generate a [`Maze`], drawing random choices from the provided `rng`.
+    pub fn generate_with<R: Rng>(&self, rng: &mut R) -> Result<Maze, GenerationError> {
+        self.carve(rng, &mut |_, _| ())
+    }
+
+    /// Generate a [`Maze`], reporting the number of visited cells to `progress` as they are reached.
+    fn carve<R: Rng>(
+        &self,
+        rng: &mut R,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        let Self {
+            width,
+            height,
+            placement,
+            ends,
+            topology,
+        } = *self;
+        debug!("generating a {width}x{height} maze");
+        let end = Pos::new(width - 1, height - 1);
+        let mut result = Maze::try_new_topology(width, height, Pos::zero(), end, topology)?;
+
+        let total = (width * height) as usize;
+        let mut visited = VisitedSet::new(width, height);
+        let mut done = 0;
+        // each frame holds a cell and its shuffled neighbors, in the order a recursive walk would try them
+        let mut stack: Vec<(Pos, Neighbors)> = Vec::new();
+        let mut entered = Some(Pos::zero());
+        loop {
+            if let Some(current) = entered.take() {
+                visited.insert(current);
+                done += 1;
+                progress(done, total);
+                let mut adjascent_positions = result.adjascent(current);
+                adjascent_positions.shuffle(rng);
+                adjascent_positions.reverse();
+                stack.push((current, adjascent_positions));
+            }
+            let Some((current, remaining)) = stack.last_mut() else {
+                break;
+            };
+            let current = *current;
+            match remaining.pop() {
+                Some(neighbor) if !visited.contains(neighbor) => {
+                    result.create_path(current, neighbor);
+                    entered = Some(neighbor);
+                }
+                Some(_) => (),
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+        placement.apply(&mut result, rng)?;
+        add_random_ends(&mut result, ends, rng);
+
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "std")]
+impl MazeGenerator for SimpleGenerator {
+    fn generate(&mut self) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut thread_rng())
+    }
+
+    fn generate_seeded(&mut self, seed: u64) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut StdRng::seed_from_u64(seed))
+    }
+
+    fn generate_with_progress(
+        &mut self,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        self.carve(&mut thread_rng(), progress)
+    }
+}
+
+/// Carve a perfect maze on the rectangle from `origin` (included) to `end` (excluded), returning its passages.
+pub(crate) fn carve_rectangle<R: Rng>(origin: Pos, end: Pos, rng: &mut R) -> Vec<(Pos, Pos)> {
+    let size = end - origin;
+    let mut visited = Grid::new(size.x(), size.y(), false);
+    let mut passages = Vec::new();
+    let mut stack = vec![Pos::zero()];
+    visited[Pos::zero()] = true;
+
+    while let Some(&current) = stack.last() {
+        let mut directions = Direction::ALL;
+        directions.shuffle(rng);
+        let next = directions
+            .into_iter()
+            .map(|d| current + d)
+            .find(|&p| visited.get(p) == Some(&false));
+        match next {
+            Some(next) => {
+                visited[next] = true;
+                passages.push((origin + current, origin + next));
+                stack.push(next);
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+
+    passages
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn generation() {
+    use crate::{
+        executor::{drive, Rules},
+        implementations::BreathFirst,
+    };
+
+    let mut generator = SimpleGenerator::new(10, 10);
+    let maze = generator.generate().unwrap();
+    let text = maze.display(None);
+    println!("{text}");
+
+    let mut reports = Vec::new();
+    let maze = generator
+        .generate_with_progress(&mut |done, total| reports.push((done, total)))
+        .unwrap();
+    assert_eq!(maze.size(), (10, 10));
+    assert_eq!(reports.len(), 100);
+    assert_eq!(reports.last(), Some(&(100, 100)));
+
+    let error = SimpleGenerator::new(0, 10).generate().unwrap_err();
+    assert!(matches!(error, GenerationError::InvalidMaze(_)));
+
+    // deep enough to overflow the call stack of a test thread when recursing
+    let maze = SimpleGenerator::new(500, 500).generate_seeded(0).unwrap();
+    super::assert_perfect(&maze);
+
+    let mut generator = SimpleGenerator::new(10, 10)
+        .placement(Placement::Random)
+        .ends(3);
+    let maze = generator.generate_seeded(0).unwrap();
+    assert_eq!(maze, generator.generate_seeded(0).unwrap());
+    assert_eq!(maze.ends().count(), 3);
+
+    // passages cross the edges
+    let mut generator = SimpleGenerator::new(8, 6).wrapping(true);
+    for seed in 0..20 {
+        let maze = generator.generate_seeded(seed).unwrap();
+        assert_eq!(maze.topology(), crate::Topology::Torus);
+        super::assert_perfect(&maze);
+    }
+    let wraps = |maze: &Maze| {
+        let edge = (0..6).map(|y| Pos::new(0, y));
+        edge.filter(|&p| !maze.is_walled(p, Pos::new(7, p.y())))
+            .count()
+    };
+    assert!((0..20).any(|seed| wraps(&generator.generate_seeded(seed).unwrap()) > 0));
+
+    // diagonal passages shorten the way to the opposite corner
+    let mut generator = SimpleGenerator::new(8, 6).topology(Topology::Diagonal);
+    let maze = generator.generate_seeded(0).unwrap();
+    super::assert_perfect(&maze);
+    let mut last = Vec::new();
+    drive(&maze, &mut BreathFirst::new(), Rules::default(), |_, g| {
+        last = g
+    })
+    .unwrap();
+    assert!(last.windows(2).any(|step| {
+        let (x, y) = (step[1] - step[0]).decompose();
+        x != 0 && y != 0
+    }));
+}
@@ -0,0 +1,62 @@
+//! ## Union find
+//!
+//! This module contains the [`UnionFind`] structure, tracking which cells have already been joined while carving a maze.
+
+use alloc::{vec, vec::Vec};
+
+/// Disjoint sets over the indices `0..len`, merged with [`UnionFind::union`].
+/// Uses path halving and union by rank, so that operations run in quasi-constant time.
+#[derive(Debug, Clone)]
+pub(crate) struct UnionFind {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+}
+
+impl UnionFind {
+    /// Constructor, each index being its own set.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            ranks: vec![0; len],
+        }
+    }
+
+    /// Representative of the set holding `index`.
+    pub fn find(&mut self, mut index: usize) -> usize {
+        while self.parents[index] != index {
+            self.parents[index] = self.parents[self.parents[index]];
+            index = self.parents[index];
+        }
+        index
+    }
+
+    /// Merge the sets holding `a` and `b`, returns `false` if they already were the same.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (child, parent) = match self.ranks[a] < self.ranks[b] {
+            true => (a, b),
+            false => (b, a),
+        };
+        self.parents[child] = parent;
+        if self.ranks[child] == self.ranks[parent] {
+            self.ranks[parent] += 1;
+        }
+        true
+    }
+}
+
+#[test]
+fn union_find() {
+    let mut sets = UnionFind::new(5);
+    assert!(sets.union(0, 1));
+    assert!(sets.union(3, 4));
+    assert!(!sets.union(1, 0));
+    assert_eq!(sets.find(0), sets.find(1));
+    assert_ne!(sets.find(1), sets.find(3));
+    assert!(sets.union(1, 4));
+    assert_eq!(sets.find(0), sets.find(3));
+    assert_ne!(sets.find(2), sets.find(0));
+}
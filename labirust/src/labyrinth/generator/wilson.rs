@@ -0,0 +1,125 @@
+//! ## Wilson
+//!
+//! This module contains the [`WilsonGenerator`], carving loop-erased random walks.
+
+use alloc::vec::Vec;
+
+use rand::{prelude::SliceRandom, Rng};
+#[cfg(feature = "std")]
+use rand::{rngs::StdRng, thread_rng, SeedableRng};
+
+use super::GenerationError;
+#[cfg(feature = "std")]
+use super::MazeGenerator;
+use crate::{Grid, Maze, Pos, VisitedSet};
+
+/// Generator building a uniform spanning tree with Wilson's algorithm.
+/// From each cell out of the maze, walks randomly until reaching the maze, then carves the walk with its loops erased.
+/// Every perfect maze of the given size is equally likely, which avoids biasing benchmarks towards some layouts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WilsonGenerator {
+    width: isize,
+    height: isize,
+}
+
+impl WilsonGenerator {
+    /// Constructor.
+    pub fn new(width: isize, height: isize) -> Self {
+        Self { width, height }
+    }
+
+    /// Generate a [`Maze`], drawing random choices from the provided `rng`.
+    pub fn generate_with<R: Rng>(&self, rng: &mut R) -> Result<Maze, GenerationError> {
+        self.carve(rng, &mut |_, _| ())
+    }
+
+    /// Generate a [`Maze`], reporting the number of cells joined to the maze to `progress` as walks are carved.
+    fn carve<R: Rng>(
+        &self,
+        rng: &mut R,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        let Self { width, height } = *self;
+        debug!("generating a {width}x{height} maze with wilson's algorithm");
+        let mut result = Maze::try_new(
+            width,
+            height,
+            Pos::zero(),
+            (width - 1, height - 1).into(),
+            Vec::new(),
+        )?;
+
+        let total = (width * height) as usize;
+        let mut joined = VisitedSet::new(width, height);
+        joined.insert(Pos::new(rng.gen_range(0..width), rng.gen_range(0..height)));
+        let mut done = 1;
+        progress(done, total);
+        // the direction last taken from each cell of the current walk, overwriting loops
+        let mut walk = Grid::new(width, height, Pos::zero());
+        for index in 0..total {
+            let origin = Pos::from_index(index, width);
+            if joined.contains(origin) {
+                continue;
+            }
+            let mut current = origin;
+            while !joined.contains(current) {
+                let next = *result
+                    .adjascent(current)
+                    .choose(rng)
+                    .expect("adjacent cell");
+                walk[current] = next;
+                current = next;
+            }
+            let mut current = origin;
+            while !joined.contains(current) {
+                joined.insert(current);
+                done += 1;
+                result.create_path(current, walk[current]);
+                current = walk[current];
+            }
+            progress(done, total);
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "std")]
+impl MazeGenerator for WilsonGenerator {
+    fn generate(&mut self) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut thread_rng())
+    }
+
+    fn generate_seeded(&mut self, seed: u64) -> Result<Maze, GenerationError> {
+        self.generate_with(&mut StdRng::seed_from_u64(seed))
+    }
+
+    fn generate_with_progress(
+        &mut self,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Maze, GenerationError> {
+        self.carve(&mut thread_rng(), progress)
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn generation() {
+    let mut generator = WilsonGenerator::new(12, 8);
+    let maze = generator.generate_seeded(0).unwrap();
+    super::assert_perfect(&maze);
+    assert_eq!(maze, generator.generate_seeded(0).unwrap());
+
+    let mut reports = Vec::new();
+    generator
+        .generate_with_progress(&mut |done, total| reports.push((done, total)))
+        .unwrap();
+    assert_eq!(reports.last(), Some(&(96, 96)));
+
+    // the four perfect 2x2 mazes are equally likely
+    super::assert_uniform(|seed| WilsonGenerator::new(2, 2).generate_seeded(seed).unwrap());
+
+    let error = WilsonGenerator::new(0, 10).generate().unwrap_err();
+    assert!(matches!(error, GenerationError::InvalidMaze(_)));
+}
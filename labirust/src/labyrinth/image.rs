@@ -0,0 +1,341 @@
+//! ## Image
+//!
+//! This module contains conversions between a [`Maze`] and the images of the [`image`](https://docs.rs/image) crate.
+//! Mazes are drawn with one pixel per cell and per wall, laid out like the characters of [`Maze::display`],
+//! so that a `width` by `height` maze takes `2 * width + 1` by `2 * height + 1` pixels.
+//! The images can then be scaled, saved or loaded in any format enabled on the `image` crate, such as PNG.
+//! Larger renderings, with cells and walls several pixels wide and an optional solution, are drawn by [`Maze::to_image`].
+//! Only square and toroidal mazes are drawn (c.f. [`Maze::try_tiles`]), color images also showing their keys, doors and portals.
+
+use std::{collections::HashSet, path::Path};
+
+use ::image::{GrayImage, Luma, Rgba, RgbaImage};
+
+use crate::{Maze, MazeError, Pos, TileKind};
+
+const WALL: [u8; 3] = [0, 0, 0];
+const PASSAGE: [u8; 3] = [255, 255, 255];
+const START: [u8; 3] = [0, 200, 0];
+const END: [u8; 3] = [200, 0, 0];
+const SOLUTION: [u8; 3] = [60, 110, 230];
+const KEY: [u8; 3] = [230, 190, 0];
+const DOOR: [u8; 3] = [230, 150, 60];
+const PORTAL: [u8; 3] = [190, 110, 240];
+
+/// Dimensions of the renderings of [`Maze::to_image`], in pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImageStyle {
+    cell_size: u32,
+    wall_thickness: u32,
+}
+
+impl ImageStyle {
+    /// Constructor, with cells 8 pixels wide and walls 2 pixels thick.
+    pub fn new() -> Self {
+        Self {
+            cell_size: 8,
+            wall_thickness: 2,
+        }
+    }
+
+    /// Sets the width and height of the cells, at least 1 pixel.
+    pub fn cell_size(self, cell_size: u32) -> Self {
+        let cell_size = cell_size.max(1);
+        Self { cell_size, ..self }
+    }
+
+    /// Sets the thickness of the walls, including the corners between them, walls being left out when 0.
+    pub fn wall_thickness(self, wall_thickness: u32) -> Self {
+        Self {
+            wall_thickness,
+            ..self
+        }
+    }
+
+    /// Index along the layout of [`Maze::to_rgba_image`] of the pixel at `offset` along a rendering.
+    fn layout_index(self, offset: u32) -> u32 {
+        let period = self.cell_size + self.wall_thickness;
+        let (index, rest) = (offset / period, offset % period);
+        match rest < self.wall_thickness {
+            true => 2 * index,
+            false => 2 * index + 1,
+        }
+    }
+
+    /// Length of a rendering of `cells` cells along one dimension.
+    fn length(self, cells: isize) -> u32 {
+        let cells = cells as u32;
+        cells * self.cell_size + (cells + 1) * self.wall_thickness
+    }
+}
+
+impl Default for ImageStyle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Maze {
+    /// Draw the [`Maze`] in a grayscale image, walls in black and passages in white.
+    /// Panics if it is neither square nor toroidal (c.f. [`Maze::try_to_gray_image`]).
+    pub fn to_gray_image(&self) -> GrayImage {
+        match self.try_to_gray_image() {
+            Ok(image) => image,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Draw the [`Maze`] in a grayscale image, walls in black and passages in white,
+    /// fails with [`MazeError::UnsupportedTopology`] if it is neither square nor toroidal.
+    pub fn try_to_gray_image(&self) -> Result<GrayImage, MazeError> {
+        let [wall, passage] = [WALL[0], PASSAGE[0]];
+        let (width, height) = self.image_size()?;
+        let image = GrayImage::from_fn(width, height, |x, y| match self.is_wall_pixel(x, y) {
+            true => Luma([wall]),
+            false => Luma([passage]),
+        });
+        Ok(image)
+    }
+
+    /// Draw the [`Maze`] in a color image, walls in black, passages in white, the start in green and the end in red,
+    /// keys in yellow, doors in orange and the cells linked by portals in purple.
+    /// Panics if it is neither square nor toroidal (c.f. [`Maze::try_to_rgba_image`]).
+    pub fn to_rgba_image(&self) -> RgbaImage {
+        match self.try_to_rgba_image() {
+            Ok(image) => image,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Draw the [`Maze`] in a color image like [`Maze::to_rgba_image`],
+    /// fails with [`MazeError::UnsupportedTopology`] if it is neither square nor toroidal.
+    pub fn try_to_rgba_image(&self) -> Result<RgbaImage, MazeError> {
+        let (width, height) = self.image_size()?;
+        let image = RgbaImage::from_fn(width, height, |x, y| {
+            let [r, g, b] = self.pixel_color(x, y).unwrap_or(PASSAGE);
+            Rgba([r, g, b, 255])
+        });
+        Ok(image)
+    }
+
+    /// Draw the [`Maze`] in a color image with the dimensions of `style`, colored like [`Maze::to_rgba_image`],
+    /// along with the cells of the `solution` and the passages between them in blue, if any.
+    /// Panics if it is neither square nor toroidal (c.f. [`Maze::try_to_image`]).
+    pub fn to_image(&self, style: ImageStyle, solution: Option<&[Pos]>) -> RgbaImage {
+        match self.try_to_image(style, solution) {
+            Ok(image) => image,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Draw the [`Maze`] in a color image like [`Maze::to_image`],
+    /// fails with [`MazeError::UnsupportedTopology`] if it is neither square nor toroidal.
+    pub fn try_to_image(
+        &self,
+        style: ImageStyle,
+        solution: Option<&[Pos]>,
+    ) -> Result<RgbaImage, MazeError> {
+        self.try_tiles()?;
+        let layout = |position: Pos| (position.x() as u32 * 2 + 1, position.y() as u32 * 2 + 1);
+        let mut solved = HashSet::new();
+        for step in solution.unwrap_or_default().windows(2) {
+            let ((ax, ay), (bx, by)) = (layout(step[0]), layout(step[1]));
+            if self.is_walled(step[0], step[1]) {
+                continue;
+            }
+            // passages crossing a single wall, others such as portals only showing their ends
+            if ax.abs_diff(bx) + ay.abs_diff(by) == 2 {
+                solved.insert(((ax + bx) / 2, (ay + by) / 2));
+            }
+        }
+        solved.extend(
+            solution
+                .unwrap_or_default()
+                .iter()
+                .map(|&cell| layout(cell)),
+        );
+
+        let (width, height) = self.size();
+        let (pixels_x, pixels_y) = (style.length(width), style.length(height));
+        let image = RgbaImage::from_fn(pixels_x, pixels_y, |x, y| {
+            let (x, y) = (style.layout_index(x), style.layout_index(y));
+            let solved = solved.contains(&(x, y)).then_some(SOLUTION);
+            let [r, g, b] = self.pixel_color(x, y).or(solved).unwrap_or(PASSAGE);
+            Rgba([r, g, b, 255])
+        });
+        Ok(image)
+    }
+
+    /// Draw the [`Maze`] like [`Maze::to_image`] and save it at `path`, in the format given by its extension such as PNG.
+    pub fn save_image(
+        &self,
+        path: impl AsRef<Path>,
+        style: ImageStyle,
+        solution: Option<&[Pos]>,
+    ) -> crate::Result<()> {
+        self.try_to_image(style, solution)?.save(path)?;
+        Ok(())
+    }
+
+    /// Read a [`Maze`] from a grayscale image laid out like [`Maze::to_gray_image`], pixels darker than mid-gray being walls.
+    /// The start is set to the top left cell and the end to the bottom right one.
+    pub fn from_gray_image(image: &GrayImage) -> Result<Self, MazeError> {
+        let is_wall = |x, y| image.get_pixel(x, y).0[0] < 128;
+        Self::from_pixels(image.dimensions(), is_wall, |_| None)
+    }
+
+    /// Read a [`Maze`] from a color image laid out like [`Maze::to_rgba_image`], pixels darker than mid-gray being walls.
+    /// The start is the first green cell and the ends are the red ones, defaulting to the top left and bottom right cells.
+    /// Keys, doors and portals are not read back.
+    pub fn from_rgba_image(image: &RgbaImage) -> Result<Self, MazeError> {
+        let is_wall = |x, y| {
+            let Rgba([r, g, b, _]) = *image.get_pixel(x, y);
+            (r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000 < 128
+        };
+        let marker = |(x, y)| {
+            let Rgba([r, g, b, _]) = *image.get_pixel(x, y);
+            match (r >= 128, g >= 128, b >= 128) {
+                (false, true, false) => Some(true),
+                (true, false, false) => Some(false),
+                _ => None,
+            }
+        };
+        Self::from_pixels(image.dimensions(), is_wall, marker)
+    }
+
+    /// Size of the images drawn from the [`Maze`], one pixel per tile (c.f. [`Maze::tiles`]).
+    fn image_size(&self) -> Result<(u32, u32), MazeError> {
+        let (width, height) = self.try_tiles()?;
+        Ok((width as u32, height as u32))
+    }
+
+    /// Whether the pixel at (`x`, `y`) of an image of the [`Maze`] is part of a wall.
+    fn is_wall_pixel(&self, x: u32, y: u32) -> bool {
+        TileKind::of(self, Pos::new(x as isize, y as isize)) == TileKind::Wall
+    }
+
+    /// Color of the pixel at (`x`, `y`) of a color image of the [`Maze`], `None` for plain passages.
+    fn pixel_color(&self, x: u32, y: u32) -> Option<[u8; 3]> {
+        match TileKind::of(self, Pos::new(x as isize, y as isize)) {
+            TileKind::Wall => Some(WALL),
+            TileKind::Cell(cell) if self.is_start(cell) => Some(START),
+            TileKind::Cell(cell) if self.is_end(cell) => Some(END),
+            TileKind::Cell(cell) if self.key_at(cell).is_some() => Some(KEY),
+            TileKind::Cell(cell) if self.portals_from(cell).next().is_some() => Some(PORTAL),
+            TileKind::Opening(a, b) if self.door(a, b).is_some() => Some(DOOR),
+            _ => None,
+        }
+    }
+
+    /// Build a [`Maze`] from the walls of an image of `dimensions` pixels.
+    /// `marker` tells whether the pixel of a cell marks the start (`Some(true)`), the end (`Some(false)`) or neither.
+    fn from_pixels(
+        dimensions: (u32, u32),
+        is_wall: impl Fn(u32, u32) -> bool,
+        marker: impl Fn((u32, u32)) -> Option<bool>,
+    ) -> Result<Self, MazeError> {
+        let (pixels_x, pixels_y) = dimensions;
+        if pixels_x < 3 || pixels_y < 3 || pixels_x % 2 == 0 || pixels_y % 2 == 0 {
+            let (width, height) = (pixels_x as isize, pixels_y as isize);
+            return Err(MazeError::InvalidSize { width, height });
+        }
+        let (width, height) = ((pixels_x as isize - 1) / 2, (pixels_y as isize - 1) / 2);
+        let pixel = |position: Pos| (position.x() as u32 * 2 + 1, position.y() as u32 * 2 + 1);
+        let cells = || (0..height).flat_map(|y| (0..width).map(move |x| Pos::new(x, y)));
+        let start = cells().find(|&cell| marker(pixel(cell)) == Some(true));
+        let end = cells().find(|&cell| marker(pixel(cell)) == Some(false));
+        let start = start.unwrap_or(Pos::zero());
+        let end = end.unwrap_or(Pos::new(width - 1, height - 1));
+
+        let mut result = Self::try_new(width, height, start, end, Vec::new())?;
+        for cell in cells() {
+            let (x, y) = pixel(cell);
+            if marker((x, y)) == Some(false) {
+                result.try_add_end(cell)?;
+            }
+            if cell.x() + 1 < width && !is_wall(x + 1, y) {
+                result.try_create_path(cell, Pos::new(cell.x() + 1, cell.y()))?;
+            }
+            if cell.y() + 1 < height && !is_wall(x, y + 1) {
+                result.try_create_path(cell, Pos::new(cell.x(), cell.y() + 1))?;
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn images() {
+    use crate::{
+        executor::{drive, Rules},
+        implementations::BreathFirst,
+        MazeGenerator, SimpleGenerator,
+    };
+
+    let maze = SimpleGenerator::new(9, 6).generate().unwrap();
+    let gray = maze.to_gray_image();
+    assert_eq!(gray.dimensions(), (19, 13));
+    let display = maze.display(None);
+    let walls = display.chars().filter(|c| !" \n".contains(*c));
+    assert_eq!(walls.count(), gray.pixels().filter(|p| p.0[0] == 0).count());
+
+    let read = Maze::from_gray_image(&gray).unwrap();
+    assert_eq!(read.size(), maze.size());
+    let directions = |(_, cell): (Pos, crate::Cell)| cell.open_directions().count();
+    assert!(read.iter().map(directions).eq(maze.iter().map(directions)));
+    assert_eq!(Maze::from_rgba_image(&maze.to_rgba_image()).unwrap(), maze);
+
+    // a larger rendering, the solution leading from the start to the end
+    let mut solution = Vec::new();
+    drive(&maze, &mut BreathFirst::new(), Rules::default(), |_, g| {
+        solution = g
+    })
+    .unwrap();
+    let style = ImageStyle::new().cell_size(3).wall_thickness(1);
+    let image = maze.to_image(style, Some(&solution));
+    assert_eq!(image.dimensions(), (9 * 3 + 10, 6 * 3 + 7));
+    let blue = image.pixels().filter(|p| p.0[..3] == SOLUTION).count();
+    // cells are 3 by 3 pixels and the walls between them 3 by 1
+    assert_eq!(blue, (solution.len() - 2) * 9 + (solution.len() - 1) * 3);
+    let plain = maze.to_image(ImageStyle::new().cell_size(1).wall_thickness(1), None);
+    assert_eq!(plain, maze.to_rgba_image());
+
+    // a torus opening its outer walls where passages wrap around, with a key, a door and portals
+    //  a . c
+    //  d . .
+    let (a, c, d, end) = (Pos::zero(), Pos::new(2, 0), Pos::new(0, 1), Pos::new(2, 2));
+    let mut maze = Maze::try_new_torus(3, 3, a, end).unwrap();
+    maze.try_open_path(a, c).unwrap();
+    maze.try_open_path(a, d).unwrap();
+    maze.try_add_door(a, c, 0).unwrap();
+    maze.try_add_key(d, 0).unwrap();
+    maze.try_add_portal(c, Pos::new(1, 1)).unwrap();
+    let image = maze.to_rgba_image();
+    assert_eq!(image.dimensions(), (7, 7));
+    let color = |x, y| -> [u8; 3] { image.get_pixel(x, y).0[..3].try_into().unwrap() };
+    assert_eq!(color(0, 1), DOOR);
+    assert_eq!(color(6, 1), DOOR);
+    assert_eq!(color(0, 3), WALL);
+    assert_eq!(color(1, 3), KEY);
+    assert_eq!(color(5, 1), PORTAL);
+    assert_eq!(color(3, 3), PORTAL);
+    assert_eq!(color(1, 2), PASSAGE);
+
+    let maze = Maze::try_new_hex(3, 3, a, end).unwrap();
+    assert_eq!(
+        maze.try_to_rgba_image(),
+        Err(MazeError::UnsupportedTopology)
+    );
+    assert!(maze.try_to_image(style, None).is_err());
+    assert!(maze.save_image("unused.png", style, None).is_err());
+
+    assert_eq!(
+        Maze::from_gray_image(&GrayImage::new(4, 5)).unwrap_err(),
+        MazeError::InvalidSize {
+            width: 4,
+            height: 5
+        }
+    );
+}
@@ -0,0 +1,191 @@
+//! ## Infinite
+//!
+//! This module contains the [`InfiniteMaze`], a maze without bounds generated lazily, chunk by chunk.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::cell::RefCell;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::generator::carve_rectangle;
+use crate::{path::direction_between, Direction, Grid, Maze, Neighbors, Pos};
+
+/// A maze extending infinitely in every direction.
+/// It is divided in square chunks, each one carved on first access from a random generator seeded by the seed of the maze and the coordinates of the chunk,
+/// so that a given seed always describes the same maze regardless of the order of the queries.
+/// Each chunk is a perfect maze and is connected to each of its four neighbors by a single passage.
+#[derive(Debug, Clone)]
+pub struct InfiniteMaze {
+    seed: u64,
+    chunk_size: isize,
+    chunks: RefCell<BTreeMap<Pos, Grid<u8>>>,
+}
+
+impl InfiniteMaze {
+    /// Constructor.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            chunk_size: 16,
+            chunks: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    /// Sets the side length of the chunks, default is 16.
+    pub fn chunk_size(self, chunk_size: isize) -> Self {
+        let chunk_size = chunk_size.max(1);
+        Self { chunk_size, ..self }
+    }
+
+    /// The seed describing the [`InfiniteMaze`].
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Number of chunks generated so far.
+    pub fn generated_chunks(&self) -> usize {
+        self.chunks.borrow().len()
+    }
+
+    /// Returns all positions directly accessible from a position in the [`InfiniteMaze`].
+    pub fn paths_from(&self, position: Pos) -> Neighbors {
+        let openings = self.openings(position);
+        Direction::ALL
+            .into_iter()
+            .filter(|d| openings & d.bit() != 0)
+            .map(|d| position + d)
+            .collect()
+    }
+
+    /// Returns adjascent positions of `position`, always four of them.
+    pub fn adjascent(&self, position: Pos) -> Neighbors {
+        Direction::ALL.into_iter().map(|d| position + d).collect()
+    }
+
+    /// Check if there is a wall between two adjascent positions in the [`InfiniteMaze`].
+    pub fn is_walled(&self, position_a: Pos, position_b: Pos) -> bool {
+        match direction_between(position_a, position_b) {
+            Some(direction) => self.openings(position_a) & direction.bit() == 0,
+            None => true,
+        }
+    }
+
+    /// Extract a finite [`Maze`] from the rectangle of `width` by `height` cells starting at `origin`.
+    /// Passages crossing the border of the rectangle are clipped, so that only windows aligned on chunks are guaranteed to be connected.
+    /// The start and end of the resulting maze are its top-left and bottom-right corners.
+    pub fn window(&self, origin: Pos, width: isize, height: isize) -> Maze {
+        let mut result = Maze::new(
+            width,
+            height,
+            Pos::zero(),
+            (width - 1, height - 1).into(),
+            Vec::new(),
+        );
+        for y in 0..height {
+            for x in 0..width {
+                let local = Pos::new(x, y);
+                let openings = self.openings(origin + local);
+                for direction in [Direction::East, Direction::South] {
+                    let next = local + direction;
+                    if openings & direction.bit() != 0 && result.is_inside(next) {
+                        result.create_path(local, next);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// Bit flags of the open directions of a cell, generating its chunk if needed.
+    fn openings(&self, position: Pos) -> u8 {
+        let size = self.chunk_size;
+        let chunk = Pos::new(position.x().div_euclid(size), position.y().div_euclid(size));
+        let local = Pos::new(position.x().rem_euclid(size), position.y().rem_euclid(size));
+
+        let mut openings = self
+            .chunks
+            .borrow_mut()
+            .entry(chunk)
+            .or_insert_with(|| self.carve_chunk(chunk))[local];
+
+        // passages between chunks
+        if local.x() == size - 1 && self.door(chunk, Direction::East) == local.y() {
+            openings |= Direction::East.bit();
+        }
+        if local.x() == 0 && self.door(chunk + Direction::West, Direction::East) == local.y() {
+            openings |= Direction::West.bit();
+        }
+        if local.y() == size - 1 && self.door(chunk, Direction::South) == local.x() {
+            openings |= Direction::South.bit();
+        }
+        if local.y() == 0 && self.door(chunk + Direction::North, Direction::South) == local.x() {
+            openings |= Direction::North.bit();
+        }
+
+        openings
+    }
+
+    /// Carve the perfect maze contained in a chunk.
+    fn carve_chunk(&self, chunk: Pos) -> Grid<u8> {
+        let size = self.chunk_size;
+        trace!("carving chunk {chunk:?} of infinite maze {}", self.seed);
+        let mut rng = StdRng::seed_from_u64(mix(self.seed, chunk, 0));
+        let mut result = Grid::new(size, size, 0);
+        for (position_a, position_b) in carve_rectangle(Pos::zero(), Pos::sized(size), &mut rng) {
+            let direction =
+                direction_between(position_a, position_b).expect("carved non-adjacent cells");
+            result[position_a] |= direction.bit();
+            result[position_b] |= direction.opposite().bit();
+        }
+        result
+    }
+
+    /// Offset along the border of the passage leading from a chunk to its neighbor in `direction`, either east or south.
+    fn door(&self, chunk: Pos, direction: Direction) -> isize {
+        let salt = direction.bit() as u64;
+        let mut rng = StdRng::seed_from_u64(mix(self.seed, chunk, salt));
+        rng.gen_range(0..self.chunk_size)
+    }
+}
+
+/// Combine a seed, chunk coordinates and a salt into a single well distributed seed (splitmix64 finalizer).
+fn mix(seed: u64, chunk: Pos, salt: u64) -> u64 {
+    let mut value = seed
+        ^ (chunk.x() as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (chunk.y() as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F)
+        ^ salt.wrapping_mul(0x1656_67B1_9E37_79F9);
+    value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^ (value >> 31)
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn infinite() {
+    let maze = InfiniteMaze::new(42).chunk_size(8);
+    let far = Pos::new(-1000, 5000);
+    for direction in Direction::ALL {
+        let next = far + direction;
+        assert_eq!(maze.is_walled(far, next), maze.is_walled(next, far));
+    }
+
+    // same seed, same maze, whatever the access order
+    let other = InfiniteMaze::new(42).chunk_size(8);
+    let window = maze.window(Pos::new(-16, -16), 32, 32);
+    let _ = other.paths_from(far);
+    let other_window = other.window(Pos::new(-16, -16), 32, 32);
+    assert_eq!(window.display(None), other_window.display(None));
+
+    // chunk aligned windows are connected
+    let mut reached = crate::VisitedSet::new(32, 32);
+    let mut stack = vec![Pos::zero()];
+    reached.insert(Pos::zero());
+    while let Some(current) = stack.pop() {
+        for next in window.paths_from_iter(current) {
+            if reached.insert(next) {
+                stack.push(next);
+            }
+        }
+    }
+    assert_eq!(reached.len(), 32 * 32);
+}
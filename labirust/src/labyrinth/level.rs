@@ -0,0 +1,295 @@
+//! ## Level
+//!
+//! This module contains the levels of a [`Maze`], for multi-level mazes.
+//! Levels are stacked from top to bottom in the same grid, each one being `height` cells high, and are only linked by stairs,
+//! portals (c.f. [`super::portal`]) between a cell and the one at the same coordinates on the next level.
+//! Positions on a level are described by a [`Pos3`], converted from and to the [`Pos`] of the underlying grid.
+
+#[cfg(feature = "std")]
+use std::{collections::HashMap, string::String};
+
+use alloc::vec::Vec;
+
+use crate::{Maze, MazeError, Pos};
+
+/// Character marking the cells holding stairs to the next level in [`Maze::display`].
+#[cfg(feature = "std")]
+const STAIRS_UP: char = '▲';
+/// Character marking the cells holding stairs to the previous level in [`Maze::display`].
+#[cfg(feature = "std")]
+const STAIRS_DOWN: char = '▼';
+/// Character marking the cells holding stairs to both neighboring levels in [`Maze::display`].
+#[cfg(feature = "std")]
+const STAIRS_BOTH: char = '↕';
+
+/// Position of a cell within a level of a multi-level [`Maze`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pos3 {
+    x: isize,
+    y: isize,
+    level: isize,
+}
+
+impl Pos3 {
+    /// Constructor.
+    pub fn new(x: isize, y: isize, level: isize) -> Self {
+        Self { x, y, level }
+    }
+
+    pub fn x(self) -> isize {
+        self.x
+    }
+
+    pub fn y(self) -> isize {
+        self.y
+    }
+
+    pub fn level(self) -> isize {
+        self.level
+    }
+
+    /// Coordinates of the position within its level.
+    pub fn planar(self) -> Pos {
+        Pos::new(self.x, self.y)
+    }
+}
+
+/// Default number of levels of a [`Maze`], when deserializing one without any.
+#[cfg(feature = "serde")]
+pub(crate) fn single() -> isize {
+    1
+}
+
+#[cfg(feature = "serde")]
+pub(crate) fn is_single(levels: &isize) -> bool {
+    *levels == 1
+}
+
+impl Maze {
+    /// Fallible constructor of a [`Maze`] of `levels` levels of `width` by `height` cells, with every wall closed and no stairs.
+    /// Fails when a dimension is not positive or when the start or end is out of bounds.
+    pub fn try_new_levels(
+        width: isize,
+        height: isize,
+        levels: isize,
+        start: Pos3,
+        end: Pos3,
+    ) -> Result<Self, MazeError> {
+        if width <= 0 || height <= 0 || levels <= 0 {
+            return Err(MazeError::InvalidSize { width, height });
+        }
+        let to_planar = |position: Pos3| Pos::new(position.x, position.level * height + position.y);
+        for position in [start, end] {
+            if !(0..height).contains(&position.y) {
+                return Err(MazeError::OutOfBounds(to_planar(position)));
+            }
+        }
+        let mut result = Self::try_new(
+            width,
+            height * levels,
+            to_planar(start),
+            to_planar(end),
+            Vec::new(),
+        )?;
+        result.levels = levels;
+        Ok(result)
+    }
+
+    /// Number of levels of the [`Maze`], 1 unless built with [`Maze::try_new_levels`].
+    pub fn levels(&self) -> isize {
+        self.levels
+    }
+
+    /// Height of each level of the [`Maze`], its whole height when it has a single level.
+    pub fn level_height(&self) -> isize {
+        self.height() / self.levels
+    }
+
+    /// Level holding `position`.
+    pub fn level_of(&self, position: Pos) -> isize {
+        position.y().div_euclid(self.level_height())
+    }
+
+    /// Check if two positions are on the same level.
+    pub fn is_same_level(&self, position_a: Pos, position_b: Pos) -> bool {
+        self.levels == 1 || self.level_of(position_a) == self.level_of(position_b)
+    }
+
+    /// Position within its level of a position of the underlying grid.
+    pub fn to_pos3(&self, position: Pos) -> Pos3 {
+        let (x, y) = position.decompose();
+        let level_height = self.level_height();
+        Pos3::new(x, y.rem_euclid(level_height), y.div_euclid(level_height))
+    }
+
+    /// Position in the underlying grid of a position within a level.
+    pub fn to_planar(&self, position: Pos3) -> Pos {
+        Pos::new(
+            position.x,
+            position.level * self.level_height() + position.y,
+        )
+    }
+
+    /// Add stairs between `lower`, and the position at the same coordinates on the next level.
+    /// Fails without modifying the [`Maze`] if either is out of bounds.
+    pub fn try_add_stairs(&mut self, lower: Pos3) -> Result<(), MazeError> {
+        let upper = Pos3::new(lower.x, lower.y, lower.level + 1);
+        for position in [lower, upper] {
+            if !(0..self.level_height()).contains(&position.y) {
+                return Err(MazeError::OutOfBounds(self.to_planar(position)));
+            }
+        }
+        self.try_add_portal(self.to_planar(lower), self.to_planar(upper))
+    }
+
+    /// Check if stairs lead from `position` to the next level.
+    pub fn has_stairs_up(&self, position: Pos) -> bool {
+        let above = Pos::new(position.x(), position.y() + self.level_height());
+        self.is_portal(position, above)
+    }
+
+    /// Check if stairs lead from `position` to the previous level.
+    pub fn has_stairs_down(&self, position: Pos) -> bool {
+        let below = Pos::new(position.x(), position.y() - self.level_height());
+        self.is_portal(position, below)
+    }
+
+    /// Character marking the stairs held by the cell at `position`, if any.
+    #[cfg(feature = "std")]
+    pub(crate) fn stairs(&self, position: Pos) -> Option<char> {
+        match (self.has_stairs_up(position), self.has_stairs_down(position)) {
+            (true, true) => Some(STAIRS_BOTH),
+            (true, false) => Some(STAIRS_UP),
+            (false, true) => Some(STAIRS_DOWN),
+            (false, false) => None,
+        }
+    }
+
+    /// return a string representing a single level of the [`Maze`], laid out like [`Maze::display`],
+    /// stairs being marked with `▲` when leading up, `▼` when leading down and `↕` when leading both ways.
+    /// Panics if the level is not one of the maze (c.f. [`Maze::try_display_level`]).
+    #[cfg(feature = "std")]
+    pub fn display_level(&self, level: isize, overlay: Option<HashMap<Pos, char>>) -> String {
+        match self.try_display_level(level, overlay) {
+            Ok(out) => out,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Representation of a level returned by [`Maze::display_level`],
+    /// fails with the first position of the level if it is not one of the [`Maze`].
+    #[cfg(feature = "std")]
+    pub fn try_display_level(
+        &self,
+        level: isize,
+        overlay: Option<HashMap<Pos, char>>,
+    ) -> Result<String, MazeError> {
+        let mut out = String::new();
+        self.try_display_level_into(level, overlay.as_ref(), &mut out)?;
+        Ok(out)
+    }
+
+    /// Write the representation of a level returned by [`Maze::display_level`] into `out`, replacing its content.
+    /// Panics if the level is not one of the maze (c.f. [`Maze::try_display_level_into`]).
+    #[cfg(feature = "std")]
+    pub fn display_level_into(
+        &self,
+        level: isize,
+        overlay: Option<&HashMap<Pos, char>>,
+        out: &mut String,
+    ) {
+        if let Err(error) = self.try_display_level_into(level, overlay, out) {
+            panic!("{error}");
+        }
+    }
+
+    /// Write the representation of a level returned by [`Maze::display_level`] into `out`, replacing its content.
+    /// Fails without modifying `out` with the first position of the level if it is not one of the [`Maze`].
+    #[cfg(feature = "std")]
+    pub fn try_display_level_into(
+        &self,
+        level: isize,
+        overlay: Option<&HashMap<Pos, char>>,
+        out: &mut String,
+    ) -> Result<(), MazeError> {
+        if !(0..self.levels).contains(&level) {
+            return Err(MazeError::OutOfBounds(Pos::new(
+                0,
+                level * self.level_height(),
+            )));
+        }
+        self.display_into(overlay, out);
+        // levels only share the wall line between them, as every wall crossing it is closed
+        let lines = (self.level_height() * 2) as usize;
+        let first = level as usize * lines;
+        let skipped: usize = out.split('\n').take(first).map(|line| line.len() + 1).sum();
+        let kept: usize = out
+            .split('\n')
+            .skip(first)
+            .take(lines + 1)
+            .map(|line| line.len() + 1)
+            .sum();
+        out.truncate(skipped + kept - 1);
+        out.replace_range(..skipped, "");
+        Ok(())
+    }
+}
+
+#[test]
+fn levels() {
+    let (start, end) = (Pos3::new(0, 0, 0), Pos3::new(1, 1, 1));
+    let mut maze = Maze::try_new_levels(2, 2, 2, start, end).unwrap();
+    assert_eq!(
+        (maze.size(), maze.levels(), maze.level_height()),
+        ((2, 4), 2, 2)
+    );
+    assert_eq!(maze.end(), Pos::new(1, 3));
+    assert_eq!(maze.to_pos3(Pos::new(1, 3)), end);
+    assert_eq!(maze.to_planar(end), Pos::new(1, 3));
+    assert_eq!(maze.level_of(Pos::new(0, 2)), 1);
+
+    // levels are only linked by stairs
+    let (a, b, c) = (Pos::new(0, 1), Pos::new(0, 2), Pos::new(0, 3));
+    assert_eq!(maze.try_open_path(a, b), Err(MazeError::NotAdjacent(a, b)));
+    assert_eq!(maze.adjascent(a).len(), 2);
+    maze.try_add_stairs(Pos3::new(0, 1, 0)).unwrap();
+    assert!(maze.has_stairs_up(a) && maze.has_stairs_down(c));
+    assert!(!maze.is_walled(a, c));
+    let top = Pos3::new(0, 0, 1);
+    assert_eq!(
+        maze.try_add_stairs(top),
+        Err(MazeError::OutOfBounds(Pos::new(0, 4)))
+    );
+    maze.try_open_path(Pos::zero(), a).unwrap();
+    maze.try_open_path(c, maze.end()).unwrap();
+    assert_eq!(
+        maze.distance_field(maze.start()).distance(maze.end()),
+        Some(3)
+    );
+
+    #[cfg(feature = "std")]
+    {
+        assert_eq!(
+            maze.display_level(0, None),
+            "•─•─•\n│ │ │\n• •─•\n│▲│ │\n•─•─•"
+        );
+        assert_eq!(
+            maze.display_level(1, None),
+            "•─•─•\n│ │ │\n•─•─•\n│▼  │\n•─•─•"
+        );
+        for level in [-1, 2] {
+            assert_eq!(
+                maze.try_display_level(level, None),
+                Err(MazeError::OutOfBounds(Pos::new(0, level * 2)))
+            );
+        }
+    }
+
+    assert!(Maze::try_new_levels(2, 2, 0, start, end).is_err());
+    let outside = Pos3::new(0, 2, 0);
+    assert_eq!(
+        Maze::try_new_levels(2, 2, 2, outside, end),
+        Err(MazeError::OutOfBounds(Pos::new(0, 2)))
+    );
+}
@@ -0,0 +1,175 @@
+//! ## Lock
+//!
+//! This module contains the doors and keys of a [`Maze`], for puzzle-like mazes.
+//! A door locks an open passage until the path leading to it went through a cell holding a key with the same id.
+//! [`Maze::display`] draws doors as uppercase letters and keys as lowercase ones, the id 0 being `A` and `a`, ids past 25 wrapping around.
+
+use alloc::vec::Vec;
+
+use crate::{Maze, MazeError, Pos};
+
+/// Identifier matching a door with the keys opening it.
+pub type KeyId = u8;
+
+/// Letter representing a key, or the door it opens when `uppercase`, in [`Maze::display`].
+#[cfg(feature = "std")]
+pub(crate) fn glyph(key: KeyId, uppercase: bool) -> char {
+    let base = if uppercase { b'A' } else { b'a' };
+    (base + key % 26) as char
+}
+
+impl Maze {
+    /// Lock the open passage between two adjacent positions with a door, opened by the keys with the id `key`.
+    /// Replaces any door already locking it, fails without modifying the [`Maze`] if a position is out of bounds,
+    /// if they are not adjacent or if the passage is walled.
+    pub fn try_add_door(
+        &mut self,
+        position_a: Pos,
+        position_b: Pos,
+        key: KeyId,
+    ) -> Result<(), MazeError> {
+        self.try_bits(position_a, position_b)?;
+        if self.is_walled(position_a, position_b) {
+            return Err(MazeError::AlreadyClosed(position_a, position_b));
+        }
+        self.remove_door(position_a, position_b);
+        let width = self.width();
+        let (first, second) = match position_a.to_index(width) < position_b.to_index(width) {
+            true => (position_a, position_b),
+            false => (position_b, position_a),
+        };
+        self.doors.push((first, second, key));
+        self.doors
+            .sort_unstable_by_key(|&(a, b, _)| (a.to_index(width), b.to_index(width)));
+        Ok(())
+    }
+
+    /// Remove the door locking the passage between two positions, if any.
+    pub(crate) fn remove_door(&mut self, position_a: Pos, position_b: Pos) {
+        self.doors
+            .retain(|&(a, b, _)| !is_pair(a, b, position_a, position_b));
+    }
+
+    /// Place a key with the id `key` at `position`, replacing any key already there.
+    /// Fails without modifying the [`Maze`] if it is out of bounds.
+    pub fn try_add_key(&mut self, position: Pos, key: KeyId) -> Result<(), MazeError> {
+        if !self.is_inside(position) {
+            return Err(MazeError::OutOfBounds(position));
+        }
+        self.keys.retain(|&(held, _)| held != position);
+        self.keys.push((position, key));
+        let width = self.width();
+        self.keys
+            .sort_unstable_by_key(|&(held, _)| held.to_index(width));
+        Ok(())
+    }
+
+    /// Id of the keys opening the door between two positions, `None` if no door locks the passage.
+    pub fn door(&self, position_a: Pos, position_b: Pos) -> Option<KeyId> {
+        let mut doors = self.doors.iter();
+        let door = doors.find(|&&(a, b, _)| is_pair(a, b, position_a, position_b));
+        door.map(|&(_, _, key)| key)
+    }
+
+    /// Id of the key held by the cell at `position`, if any.
+    pub fn key_at(&self, position: Pos) -> Option<KeyId> {
+        let mut keys = self.keys.iter();
+        keys.find(|&&(held, _)| held == position)
+            .map(|&(_, key)| key)
+    }
+
+    /// Iterates over the doors of the [`Maze`] in row-major order, with the positions they separate and the id of the keys opening them.
+    pub fn doors(&self) -> impl Iterator<Item = (Pos, Pos, KeyId)> + '_ {
+        self.doors.iter().copied()
+    }
+
+    /// Iterates over the keys of the [`Maze`] in row-major order, with their position and id.
+    pub fn keys(&self) -> impl Iterator<Item = (Pos, KeyId)> + '_ {
+        self.keys.iter().copied()
+    }
+
+    /// Ids of the keys collected by following `path`, sorted and without duplicates.
+    pub fn keys_along(&self, path: &[Pos]) -> Vec<KeyId> {
+        let mut keys: Vec<_> = path.iter().filter_map(|&p| self.key_at(p)).collect();
+        keys.sort_unstable();
+        keys.dedup();
+        keys
+    }
+
+    /// Check if a door the `keys` do not open locks the passage between two positions.
+    pub fn is_locked(&self, position_a: Pos, position_b: Pos, keys: &[KeyId]) -> bool {
+        self.door(position_a, position_b)
+            .is_some_and(|key| !keys.contains(&key))
+    }
+
+    /// Index of the first step of `path` going through a locked door, keys being collected along the way.
+    pub(crate) fn first_locked_step(&self, path: &[Pos]) -> Option<usize> {
+        if self.doors.is_empty() {
+            return None;
+        }
+        let mut keys = Vec::new();
+        for (index, step) in path.windows(2).enumerate() {
+            keys.extend(self.key_at(step[0]));
+            if self.is_locked(step[0], step[1], &keys) {
+                return Some(index);
+            }
+        }
+        None
+    }
+}
+
+/// Check if two pairs of positions hold the same positions, in any order.
+fn is_pair(a: Pos, b: Pos, position_a: Pos, position_b: Pos) -> bool {
+    (a, b) == (position_a, position_b) || (a, b) == (position_b, position_a)
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn doors_and_keys() {
+    use alloc::vec;
+
+    use crate::executor::{drive, Insight, Rules};
+    use crate::implementations::BreathFirst;
+
+    // a corridor locked halfway, the key lying in a side passage
+    //  a b c
+    //    k
+    let (a, b, c, k) = (Pos::zero(), Pos::new(1, 0), Pos::new(2, 0), Pos::new(1, 1));
+    let paths = vec![(a, vec![b]), (b, vec![c, k])];
+    let mut maze = Maze::new(3, 2, a, c, paths);
+    maze.try_add_door(c, b, 2).unwrap();
+    maze.try_add_key(k, 2).unwrap();
+    assert_eq!(maze.door(b, c), Some(2));
+    assert_eq!(maze.key_at(k), Some(2));
+    assert!(maze.is_locked(b, c, &[]) && !maze.is_locked(b, c, &[2]));
+    assert_eq!(maze.first_locked_step(&[a, b, c]), Some(1));
+    assert_eq!(maze.first_locked_step(&[a, b, k, b, c]), None);
+    assert_eq!(maze.keys_along(&[a, b, k]), vec![2]);
+    assert_eq!(
+        maze.display(None),
+        "•─•─•─•\n│   C │\n•─• •─•\n│ │c│ │\n•─•─•─•"
+    );
+
+    let insight = Insight::from_path(&[a, b], &maze);
+    assert_eq!(insight.paths(), &[k, a]);
+    let insight = Insight::from_path(&[a, b, k, b], &maze);
+    assert_eq!(insight.keys(), &[2]);
+    assert_eq!(insight.paths().len(), 3);
+
+    let mut last = Vec::new();
+    let result = drive(&maze, &mut BreathFirst::new(), Rules::default(), |_, g| {
+        last = g
+    });
+    // breath-first search does not go back once the key is found
+    assert!(result.is_err());
+    assert_eq!(last, vec![a, b, k]);
+
+    assert_eq!(
+        maze.try_add_door(a, Pos::new(0, 1), 0),
+        Err(MazeError::AlreadyClosed(a, Pos::new(0, 1)))
+    );
+    assert_eq!(
+        maze.try_add_door(a, c, 0),
+        Err(MazeError::NotAdjacent(a, c))
+    );
+}
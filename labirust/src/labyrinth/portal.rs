@@ -0,0 +1,122 @@
+//! ## Portal
+//!
+//! This module contains the portals of a [`Maze`], passages linking two cells that need not be adjacent.
+//! Portals are followed like any other passage, through [`Maze::paths_from`], at the cost of a single step.
+//! They are indexed by each of their ends, in the order of positions, so that the order they were added in does not matter.
+
+use alloc::collections::BTreeMap;
+
+use smallvec::SmallVec;
+
+use crate::{Maze, MazeError, Pos};
+
+/// Portals of a [`Maze`], indexed by each of their ends, the positions they lead to being kept sorted.
+pub(crate) type Portals = BTreeMap<Pos, SmallVec<[Pos; 1]>>;
+
+/// Character marking the cells holding a portal in [`Maze::display`].
+#[cfg(feature = "std")]
+pub(crate) const PORTAL: char = '◊';
+
+impl Maze {
+    /// Add a portal between two positions of the [`Maze`], panics if either is out of bounds (c.f. [`Maze::try_add_portal`]).
+    pub fn add_portal(&mut self, position_a: Pos, position_b: Pos) {
+        if let Err(error) = self.try_add_portal(position_a, position_b) {
+            panic!("{error}");
+        }
+    }
+
+    /// Add a portal between two positions of the [`Maze`], leading both ways.
+    /// Does nothing if they are the same position or already linked, fails without modifying the maze if either is out of bounds.
+    pub fn try_add_portal(&mut self, position_a: Pos, position_b: Pos) -> Result<(), MazeError> {
+        for position in [position_a, position_b] {
+            if !self.is_inside(position) {
+                return Err(MazeError::OutOfBounds(position));
+            }
+        }
+        if position_a == position_b {
+            return Ok(());
+        }
+        for (from, to) in [(position_a, position_b), (position_b, position_a)] {
+            let linked = self.portals.entry(from).or_default();
+            if let Err(index) = linked.binary_search(&to) {
+                linked.insert(index, to);
+            }
+        }
+        Ok(())
+    }
+
+    /// Iterates over the pairs of positions linked by a portal, each once, in order.
+    pub fn portals(&self) -> impl Iterator<Item = (Pos, Pos)> + '_ {
+        pairs(&self.portals)
+    }
+
+    /// Iterates over the positions linked to `position` by a portal, in order.
+    pub fn portals_from(&self, position: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.portals.get(&position).into_iter().flatten().copied()
+    }
+
+    /// Check if a portal links two positions, in either direction.
+    pub fn is_portal(&self, position_a: Pos, position_b: Pos) -> bool {
+        let linked = self.portals.get(&position_a);
+        linked.is_some_and(|linked| linked.binary_search(&position_b).is_ok())
+    }
+
+    /// Check if any portal was added to the [`Maze`].
+    pub fn has_portals(&self) -> bool {
+        !self.portals.is_empty()
+    }
+}
+
+/// Pairs of positions linked by a portal, each once, the lowest position first.
+fn pairs(portals: &Portals) -> impl Iterator<Item = (Pos, Pos)> + '_ {
+    portals.iter().flat_map(|(&from, linked)| {
+        let after = linked.iter().filter(move |&&to| from < to);
+        after.map(move |&to| (from, to))
+    })
+}
+
+/// Serialize the portals as the list of pairs of [`Maze::portals`], as deserialized by [`super::RawMaze`].
+#[cfg(feature = "serde")]
+pub(crate) fn serialize<S: serde::Serializer>(
+    portals: &Portals,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(pairs(portals))
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn portals() {
+    use alloc::vec;
+
+    use crate::executor::{drive, Rules};
+    use crate::implementations::BreathFirst;
+
+    // two corridors, only linked through a portal
+    //  a b
+    //  c d
+    let (a, b) = (Pos::zero(), Pos::new(1, 0));
+    let (c, d) = (Pos::new(0, 1), Pos::new(1, 1));
+    let mut maze = Maze::new(2, 2, a, d, vec![(a, vec![b]), (c, vec![d])]);
+    assert_eq!(maze.distance_field(a).distance(d), None);
+
+    maze.add_portal(b, c);
+    maze.add_portal(c, b);
+    assert_eq!(maze.portals().count(), 1);
+    assert!(maze.is_portal(c, b) && !maze.is_walled(b, c));
+    assert_eq!(maze.paths_from(b).as_slice(), &[a, c]);
+    assert_eq!(maze.distance_field(a).distance(d), Some(3));
+    assert_eq!(maze.display(None), "•─•─•\n│  ◊│\n•─•─•\n│◊  │\n•─•─•");
+
+    let mut last = Vec::new();
+    let ticks = drive(&maze, &mut BreathFirst::new(), Rules::default(), |_, g| {
+        last = g
+    });
+    assert_eq!(ticks.unwrap(), 3);
+    assert_eq!(last, vec![a, b, c, d]);
+
+    assert_eq!(
+        maze.try_add_portal(a, Pos::new(2, 0)),
+        Err(MazeError::OutOfBounds(Pos::new(2, 0)))
+    );
+}
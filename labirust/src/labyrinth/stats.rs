@@ -0,0 +1,121 @@
+//! ## Stats
+//!
+//! This module contains the [`MazeStats`] of a [`Maze`], measures of its shape to compare generators quantitatively.
+
+use crate::{Grid, Maze};
+
+/// Measures of the shape of a [`Maze`] (c.f. [`Maze::stats`]).
+/// Passages are counted through walls and portals alike.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MazeStats {
+    dead_ends: usize,
+    junctions: usize,
+    average_branching: f64,
+    longest_corridor: usize,
+    solution_length: Option<usize>,
+}
+
+impl MazeStats {
+    /// Number of cells with a single passage leaving them.
+    pub fn dead_ends(&self) -> usize {
+        self.dead_ends
+    }
+
+    /// Number of cells with three passages or more leaving them.
+    pub fn junctions(&self) -> usize {
+        self.junctions
+    }
+
+    /// Mean number of passages leaving a cell.
+    pub fn average_branching(&self) -> f64 {
+        self.average_branching
+    }
+
+    /// Number of cells of the longest run of cells with exactly two passages leaving them.
+    pub fn longest_corridor(&self) -> usize {
+        self.longest_corridor
+    }
+
+    /// Number of steps of the shortest path from the start to the nearest end, `None` if no end is reachable.
+    pub fn solution_length(&self) -> Option<usize> {
+        self.solution_length
+    }
+}
+
+impl Maze {
+    /// Measure the shape of the [`Maze`], c.f. [`MazeStats`].
+    pub fn stats(&self) -> MazeStats {
+        let degrees = Grid::from_fn(self.width, self.height, |position| self.degree(position));
+        let count = |predicate: fn(usize) -> bool| {
+            degrees
+                .iter()
+                .filter(|(_, &degree)| predicate(degree))
+                .count()
+        };
+        let passages: usize = degrees.iter().map(|(_, &degree)| degree).sum();
+        let cells = (self.width * self.height) as usize;
+
+        let mut longest_corridor = 0;
+        let mut walked = Grid::new(self.width, self.height, false);
+        for (position, &degree) in degrees.iter() {
+            if degree != 2 || walked[position] {
+                continue;
+            }
+            walked[position] = true;
+            let mut length = 1;
+            // walk both ways along the corridor until leaving it
+            for mut next in self.paths_from_iter(position) {
+                let mut previous = position;
+                while degrees[next] == 2 && !walked[next] {
+                    walked[next] = true;
+                    length += 1;
+                    let Some(after) = self.paths_from_iter(next).find(|&p| p != previous) else {
+                        break;
+                    };
+                    (previous, next) = (next, after);
+                }
+            }
+            longest_corridor = longest_corridor.max(length);
+        }
+
+        let distances = self.distance_field(self.start);
+        let solution_length = self.ends().filter_map(|end| distances.distance(end)).min();
+
+        MazeStats {
+            dead_ends: count(|degree| degree == 1),
+            junctions: count(|degree| degree >= 3),
+            average_branching: passages as f64 / cells as f64,
+            longest_corridor,
+            solution_length,
+        }
+    }
+}
+
+#[test]
+fn stats() {
+    use alloc::vec;
+
+    use crate::Pos;
+
+    // a corridor going around a wall
+    //  a b
+    //  d c
+    let (a, b, c, d) = (Pos::zero(), Pos::new(1, 0), Pos::new(1, 1), Pos::new(0, 1));
+    let maze = Maze::new(2, 2, a, d, vec![(a, vec![b]), (b, vec![c]), (c, vec![d])]);
+    let stats = maze.stats();
+    assert_eq!((stats.dead_ends(), stats.junctions()), (2, 0));
+    assert_eq!(stats.average_branching(), 1.5);
+    assert_eq!(stats.longest_corridor(), 2);
+    assert_eq!(stats.solution_length(), Some(3));
+
+    // a junction below the middle of a row
+    //  a b c
+    //    e
+    let (a, b, c, e) = (Pos::zero(), Pos::new(1, 0), Pos::new(2, 0), Pos::new(1, 1));
+    let maze = Maze::new(3, 2, a, Pos::new(0, 1), vec![(b, vec![a, c, e])]);
+    let stats = maze.stats();
+    assert_eq!((stats.dead_ends(), stats.junctions()), (3, 1));
+    assert_eq!(stats.longest_corridor(), 0);
+    assert_eq!(stats.solution_length(), None);
+}
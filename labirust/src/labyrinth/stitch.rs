@@ -0,0 +1,178 @@
+//! ## Stitch
+//!
+//! This module contains the composition of [`Maze`]s, joining them side by side or in a grid into a larger maze,
+//! connected by passages carved through the walls along each seam.
+
+use alloc::vec::Vec;
+
+use crate::{Direction, Maze, MazeError, Pos};
+
+impl Maze {
+    /// Join `other` on the `side` of the [`Maze`], carving `passages` passages evenly spread along the seam.
+    /// The result runs from the start of the maze to the end of `other`, keeping the extra ends, portals, doors, keys and costs of both.
+    /// Fails if either maze is not a single level square maze or if their sides along the seam differ in length.
+    pub fn stitch(
+        &self,
+        other: &Maze,
+        side: Direction,
+        passages: usize,
+    ) -> Result<Maze, MazeError> {
+        for maze in [self, other] {
+            if !maze.topology.is_square() || maze.levels != 1 {
+                return Err(MazeError::UnsupportedTopology);
+            }
+        }
+        // the maze at the north west of the seam comes first
+        let swapped = matches!(side, Direction::West | Direction::North);
+        let (first, second) = match swapped {
+            false => (self, other),
+            true => (other, self),
+        };
+        let horizontal = matches!(side, Direction::East | Direction::West);
+        let (seam, found, offset, width, height) = match horizontal {
+            true => (
+                first.height,
+                second.height,
+                Pos::new(first.width, 0),
+                first.width + second.width,
+                first.height,
+            ),
+            false => (
+                first.width,
+                second.width,
+                Pos::new(0, first.height),
+                first.width,
+                first.height + second.height,
+            ),
+        };
+        if seam != found {
+            return Err(MazeError::MismatchedSeam {
+                expected: seam,
+                found,
+            });
+        }
+        let (self_offset, other_offset) = match swapped {
+            false => (Pos::zero(), offset),
+            true => (offset, Pos::zero()),
+        };
+
+        let start = self.start + self_offset;
+        let end = other.end + other_offset;
+        let mut result = Maze::try_new(width, height, start, end, Vec::new())?;
+        result.paste(self, self_offset)?;
+        result.paste(other, other_offset)?;
+
+        let passages = passages.min(seam as usize) as isize;
+        for index in 0..passages {
+            let along = (2 * index + 1) * seam / (2 * passages);
+            let (before, after) = match horizontal {
+                true => (Pos::new(offset.x() - 1, along), Pos::new(offset.x(), along)),
+                false => (Pos::new(along, offset.y() - 1), Pos::new(along, offset.y())),
+            };
+            result.open_wall(before, after)?;
+        }
+        Ok(result)
+    }
+
+    /// Join the [`Maze`]s of `rows` in a grid, each row from west to east and the rows from north to south,
+    /// carving `passages` passages along each seam (c.f. [`Maze::stitch`]).
+    /// The result runs from the start of the first maze to the end of the last one, fails if `rows` is empty.
+    pub fn stitch_grid(rows: &[Vec<Maze>], passages: usize) -> Result<Maze, MazeError> {
+        let stitch_row = |row: &Vec<Maze>| {
+            let (first, rest) = row.split_first().ok_or(MazeError::InvalidSize {
+                width: 0,
+                height: 1,
+            })?;
+            rest.iter().try_fold(first.clone(), |joined, maze| {
+                joined.stitch(maze, Direction::East, passages)
+            })
+        };
+        let (first, rest) = rows.split_first().ok_or(MazeError::InvalidSize {
+            width: 0,
+            height: 0,
+        })?;
+        rest.iter().try_fold(stitch_row(first)?, |joined, row| {
+            joined.stitch(&stitch_row(row)?, Direction::South, passages)
+        })
+    }
+
+    /// Copy the passages, extra ends, portals, doors, keys and costs of `other` into the [`Maze`], moved by `offset`.
+    fn paste(&mut self, other: &Maze, offset: Pos) -> Result<(), MazeError> {
+        for (position, cell) in other.iter() {
+            if !self.is_inside(position + offset) {
+                return Err(MazeError::OutOfBounds(position + offset));
+            }
+            self.cells[position + offset].open(cell.openings());
+        }
+        for &end in &other.extra_ends {
+            self.try_add_end(end + offset)?;
+        }
+        for (position_a, position_b) in other.portals() {
+            self.try_add_portal(position_a + offset, position_b + offset)?;
+        }
+        for (position_a, position_b, key) in other.doors() {
+            self.try_add_door(position_a + offset, position_b + offset, key)?;
+        }
+        for (position, key) in other.keys() {
+            self.try_add_key(position + offset, key)?;
+        }
+        if let Some(costs) = &other.costs {
+            for (position, &cost) in costs.iter() {
+                self.try_set_cost(position + offset, cost)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn stitch() {
+    use alloc::vec;
+
+    // two corridors of two cells, from west to east
+    let corridor = || {
+        let (a, b) = (Pos::zero(), Pos::new(1, 0));
+        Maze::new(2, 1, a, b, vec![(a, vec![b])])
+    };
+    let joined = corridor().stitch(&corridor(), Direction::East, 1).unwrap();
+    assert_eq!(joined.size(), (4, 1));
+    assert_eq!(
+        (joined.start(), joined.end()),
+        (Pos::zero(), Pos::new(3, 0))
+    );
+    assert!(joined.validate().is_perfect());
+
+    let mut placed = corridor();
+    placed
+        .try_set_endpoints(Pos::new(1, 0), Pos::zero())
+        .unwrap();
+    let joined = placed.stitch(&corridor(), Direction::North, 2).unwrap();
+    assert_eq!(joined.size(), (2, 2));
+    assert_eq!(
+        (joined.start(), joined.end()),
+        (Pos::new(1, 1), Pos::new(1, 0))
+    );
+    assert!(!joined.is_walled(Pos::zero(), Pos::new(0, 1)));
+    assert!(!joined.is_walled(Pos::new(1, 0), Pos::one()));
+
+    assert_eq!(
+        corridor().stitch(
+            &Maze::new(2, 2, Pos::zero(), Pos::one(), vec![]),
+            Direction::West,
+            1
+        ),
+        Err(MazeError::MismatchedSeam {
+            expected: 2,
+            found: 1
+        })
+    );
+
+    let grid = Maze::stitch_grid(
+        &[vec![corridor(), corridor()], vec![corridor(), corridor()]],
+        1,
+    )
+    .unwrap();
+    assert_eq!(grid.size(), (4, 2));
+    assert_eq!((grid.start(), grid.end()), (Pos::zero(), Pos::new(3, 1)));
+    assert!(grid.validate().disconnected().is_empty());
+}
@@ -0,0 +1,244 @@
+//! ## Svg
+//!
+//! This module contains the SVG rendering of a [`Maze`], scaling to mazes too large for the terminal and fit to embed in documents.
+//! Figures layer the visited positions, the path, the start and the ends, the walls, then the portals, doors and keys,
+//! in groups identified by `id` attributes for styling.
+//! Only square and toroidal mazes are drawn, the passages wrapping around the latter opening their outer walls.
+
+use std::{fmt::Write, path::Path, string::String, vec::Vec};
+
+use crate::{labyrinth::lock::glyph, Direction, Maze, MazeError, Pos, TileKind, VisitedSet};
+
+/// Side of a cell in the SVG figures, in pixels.
+const CELL: isize = 12;
+const VISITED: &str = "#c8d4ec";
+const PATH: &str = "#e92";
+const START: &str = "#2c2";
+const END: &str = "#c22";
+const PORTAL: &str = "#a6e";
+const DOOR: &str = "#e82";
+
+impl Maze {
+    /// Draw the [`Maze`] as an SVG figure, with the `visited` positions shaded and `path` drawn as a line.
+    /// The walls are drawn as a single path, merging the walls in line with each other.
+    /// Panics if the [`Maze`] is neither square nor toroidal (c.f. [`Maze::try_to_svg`]).
+    pub fn to_svg(&self, visited: Option<&VisitedSet>, path: &[Pos]) -> String {
+        match self.try_to_svg(visited, path) {
+            Ok(figure) => figure,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Draw the [`Maze`] as an SVG figure like [`Maze::to_svg`],
+    /// fails with [`MazeError::UnsupportedTopology`] if it is neither square nor toroidal.
+    pub fn try_to_svg(
+        &self,
+        visited: Option<&VisitedSet>,
+        path: &[Pos],
+    ) -> Result<String, MazeError> {
+        self.try_tiles()?;
+        let (width, height) = self.size();
+        let center = |position: Pos| {
+            (
+                position.x() * CELL + CELL / 2,
+                position.y() * CELL + CELL / 2,
+            )
+        };
+        let mut out = String::new();
+        let (pixels_x, pixels_y) = (width * CELL + 2, height * CELL + 2);
+        let _ = write!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{pixels_x}" height="{pixels_y}" viewBox="-1 -1 {pixels_x} {pixels_y}">"#
+        );
+        let _ = write!(
+            out,
+            r#"<rect x="0" y="0" width="{}" height="{}" fill="white"/>"#,
+            width * CELL,
+            height * CELL
+        );
+
+        out.push_str(r#"<g id="visited">"#);
+        for position in visited.into_iter().flat_map(VisitedSet::iter) {
+            let (x, y) = (position.x() * CELL, position.y() * CELL);
+            let _ = write!(
+                out,
+                r#"<rect x="{x}" y="{y}" width="{CELL}" height="{CELL}" fill="{VISITED}"/>"#
+            );
+        }
+        out.push_str("</g>");
+        if !path.is_empty() {
+            let points: Vec<_> = path
+                .iter()
+                .map(|&position| {
+                    let (x, y) = center(position);
+                    format!("{x},{y}")
+                })
+                .collect();
+            let _ = write!(
+                out,
+                r#"<polyline id="path" points="{}" fill="none" stroke="{PATH}" stroke-width="{}"/>"#,
+                points.join(" "),
+                CELL / 4
+            );
+        }
+        let ends = self.ends().map(|end| (end, END));
+        for (position, color) in core::iter::once((self.start(), START)).chain(ends) {
+            let (x, y) = center(position);
+            let _ = write!(
+                out,
+                r#"<circle cx="{x}" cy="{y}" r="{}" fill="{color}"/>"#,
+                CELL / 3
+            );
+        }
+
+        let _ = write!(
+            out,
+            r#"<path id="walls" d="{}" stroke="black" stroke-width="2" stroke-linecap="square"/>"#,
+            self.svg_walls()
+        );
+
+        out.push_str(r#"<g id="portals">"#);
+        for (a, b) in self.portals() {
+            let ((ax, ay), (bx, by)) = (center(a), center(b));
+            let _ = write!(
+                out,
+                r#"<line x1="{ax}" y1="{ay}" x2="{bx}" y2="{by}" stroke="{PORTAL}" stroke-width="2" stroke-dasharray="3 3"/>"#
+            );
+        }
+        out.push_str("</g>");
+        out.push_str(r#"<g id="doors">"#);
+        for (a, b, key) in self.doors() {
+            let mut directions = Direction::ALL.into_iter();
+            let Some(direction) = directions.find(|&direction| self.wrap(a + direction) == b)
+            else {
+                continue;
+            };
+            let (x, y) = (a.x() * CELL, a.y() * CELL);
+            let (x1, y1, x2, y2) = match direction {
+                Direction::North => (x, y, x + CELL, y),
+                Direction::East => (x + CELL, y, x + CELL, y + CELL),
+                Direction::South => (x, y + CELL, x + CELL, y + CELL),
+                Direction::West => (x, y, x, y + CELL),
+            };
+            let _ = write!(
+                out,
+                r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="{DOOR}" stroke-width="3"><title>{}</title></line>"#,
+                glyph(key, true)
+            );
+        }
+        out.push_str("</g>");
+        out.push_str(r#"<g id="keys">"#);
+        for (position, key) in self.keys() {
+            let (x, y) = center(position);
+            let _ = write!(
+                out,
+                r#"<text x="{x}" y="{y}" font-size="{CELL}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                glyph(key, false)
+            );
+        }
+        out.push_str("</g>");
+        out.push_str("</svg>");
+        Ok(out)
+    }
+
+    /// Draw the [`Maze`] like [`Maze::to_svg`] and save the figure at `path`.
+    pub fn save_svg(
+        &self,
+        path: impl AsRef<Path>,
+        visited: Option<&VisitedSet>,
+        solution: &[Pos],
+    ) -> crate::Result<()> {
+        std::fs::write(path, self.try_to_svg(visited, solution)?)?;
+        Ok(())
+    }
+
+    /// Commands of the path drawing the walls, one move and one line per run of walls,
+    /// following the tiles of the map of the [`Maze`] (c.f. [`TileKind`]).
+    fn svg_walls(&self) -> String {
+        let (width, height) = self.size();
+        let is_wall = |x, y| TileKind::of(self, Pos::new(x, y)) == TileKind::Wall;
+        let mut out = String::new();
+        // the walls above each row, the last one being the outer wall below the maze
+        for y in 0..=height {
+            let walled = |x| is_wall(2 * x + 1, 2 * y);
+            for (from, to) in runs(width, walled) {
+                let _ = write!(out, "M{} {}H{}", from * CELL, y * CELL, to * CELL);
+            }
+        }
+        // the walls left of each column, the last one being the outer wall right of the maze
+        for x in 0..=width {
+            let walled = |y| is_wall(2 * x, 2 * y + 1);
+            for (from, to) in runs(height, walled) {
+                let _ = write!(out, "M{} {}V{}", x * CELL, from * CELL, to * CELL);
+            }
+        }
+        out
+    }
+}
+
+/// Ranges of consecutive indices below `length` that are `walled`, as their start and end, excluded.
+fn runs(length: isize, walled: impl Fn(isize) -> bool) -> Vec<(isize, isize)> {
+    let mut result = Vec::new();
+    let mut start = None;
+    for index in 0..=length {
+        match (start, index < length && walled(index)) {
+            (None, true) => start = Some(index),
+            (Some(from), false) => {
+                result.push((from, index));
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    result
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn figures() {
+    use crate::{
+        implementations::DepthFirst, Algorithm, Execution, MazeGenerator, SimpleGenerator,
+    };
+
+    // a corridor going around a wall
+    //  a b
+    //  d c
+    let (a, b, c, d) = (Pos::zero(), Pos::new(1, 0), Pos::new(1, 1), Pos::new(0, 1));
+    let maze = Maze::new(2, 2, a, d, vec![(a, vec![b]), (b, vec![c]), (c, vec![d])]);
+    let walls = "M0 0H24M0 12H12M0 24H24M0 0V24M24 0V24";
+    assert!(maze.to_svg(None, &[]).contains(&format!(r#"d="{walls}""#)));
+
+    let maze = SimpleGenerator::new(7, 5).generate().unwrap();
+    let mut execution = Execution::new(maze.clone(), DepthFirst::new().boxed());
+    while !execution.step().unwrap() {}
+    let figure = maze.to_svg(Some(execution.tried()), execution.path());
+    assert!(figure.starts_with("<svg") && figure.ends_with("</svg>"));
+    assert_eq!(figure.matches("<rect").count(), execution.tried().len() + 1);
+    assert_eq!(figure.matches("<polyline").count(), 1);
+    assert_eq!(figure.matches("<path").count(), 1);
+
+    // a torus opening its outer walls where passages wrap around, with a door, a key and a portal
+    //  a . c
+    //  d . .
+    let (c, end) = (Pos::new(2, 0), Pos::new(2, 2));
+    let mut maze = Maze::try_new_torus(3, 3, a, end).unwrap();
+    maze.try_open_path(a, c).unwrap();
+    maze.try_open_path(a, d).unwrap();
+    maze.try_add_door(a, c, 0).unwrap();
+    maze.try_add_key(d, 0).unwrap();
+    maze.try_add_portal(Pos::one(), end).unwrap();
+    let figure = maze.to_svg(None, &[]);
+    let walls = "M0 0H36M12 12H36M0 24H36M0 36H36M0 12V36M12 0V36M24 0V36M36 12V36";
+    assert!(figure.contains(&format!(r#"d="{walls}""#)));
+    let portal = format!(r#"<line x1="18" y1="18" x2="30" y2="30" stroke="{PORTAL}""#);
+    assert!(figure.contains(&portal));
+    let door = format!(r#"<line x1="0" y1="0" x2="0" y2="12" stroke="{DOOR}""#);
+    assert!(figure.contains(&door));
+    assert!(figure.contains(">a</text>"));
+
+    let maze = Maze::try_new_hex(3, 3, a, end).unwrap();
+    assert_eq!(
+        maze.try_to_svg(None, &[]),
+        Err(MazeError::UnsupportedTopology)
+    );
+}
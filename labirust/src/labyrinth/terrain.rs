@@ -0,0 +1,95 @@
+//! ## Terrain
+//!
+//! This module contains the costs of the cells of a [`Maze`], for algorithms minimizing the cost of their path rather than its length.
+//! Entering a cell costs 1 unless set otherwise, so that the cost of a path through a maze without terrain is its number of steps.
+
+use crate::{Grid, Maze, MazeError, Pos};
+
+/// Characters shading the cells of [`Maze::display`] that cost 2, 3 to 4, and more to enter.
+#[cfg(feature = "std")]
+const SHADES: [char; 3] = ['░', '▒', '▓'];
+
+impl Maze {
+    /// Cost of entering `position`, 1 unless set otherwise or if it is out of bounds.
+    pub fn cost(&self, position: Pos) -> u32 {
+        let costs = self.costs.as_ref();
+        costs
+            .and_then(|costs| costs.get(position))
+            .copied()
+            .unwrap_or(1)
+    }
+
+    /// Set the cost of entering `position`, fails without modifying the [`Maze`] if it is out of bounds.
+    pub fn try_set_cost(&mut self, position: Pos, cost: u32) -> Result<(), MazeError> {
+        if !self.is_inside(position) {
+            return Err(MazeError::OutOfBounds(position));
+        }
+        let (width, height) = self.size();
+        let costs = self
+            .costs
+            .get_or_insert_with(|| Grid::new(width, height, 1));
+        costs[position] = cost;
+        Ok(())
+    }
+
+    /// Check if any cell of the [`Maze`] was given a cost.
+    pub fn is_weighted(&self) -> bool {
+        self.costs.is_some()
+    }
+
+    /// Lowest cost of entering a cell of the [`Maze`], 1 without terrain.
+    pub fn min_cost(&self) -> u32 {
+        let costs = self.costs.iter().flat_map(|costs| costs.iter());
+        costs.map(|(_, &cost)| cost).min().unwrap_or(1)
+    }
+
+    /// Iterates over all positions directly accessible from a position in the [`Maze`], along with the cost of entering them.
+    pub fn paths_from_costed(&self, position: Pos) -> impl Iterator<Item = (Pos, u32)> + '_ {
+        self.paths_from_iter(position)
+            .map(|neighbor| (neighbor, self.cost(neighbor)))
+    }
+
+    /// Total cost of following `path`, entering each of its positions but the first one.
+    pub fn path_cost(&self, path: &[Pos]) -> u64 {
+        let entered = path.iter().skip(1);
+        entered.map(|&position| self.cost(position) as u64).sum()
+    }
+
+    /// Character shading a cell of [`Maze::display`] according to its cost, `None` for the default cost or less.
+    #[cfg(feature = "std")]
+    pub(crate) fn shade(&self, position: Pos) -> Option<char> {
+        match self.cost(position) {
+            0..=1 => None,
+            2 => Some(SHADES[0]),
+            3..=4 => Some(SHADES[1]),
+            _ => Some(SHADES[2]),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn terrain() {
+    use alloc::vec;
+
+    let (a, b, c) = (Pos::zero(), Pos::new(1, 0), Pos::new(2, 0));
+    let mut maze = Maze::new(3, 1, a, c, vec![(a, vec![b]), (b, vec![c])]);
+    assert!(!maze.is_weighted());
+    assert_eq!(maze.min_cost(), 1);
+    assert_eq!(maze.path_cost(&[a, b, c]), 2);
+
+    maze.try_set_cost(b, 5).unwrap();
+    assert!(maze.is_weighted());
+    assert_eq!(maze.cost(b), 5);
+    assert_eq!(maze.min_cost(), 1);
+    assert_eq!(maze.cost(Pos::new(3, 0)), 1);
+    assert_eq!(maze.path_cost(&[a, b, c]), 6);
+    assert_eq!(maze.paths_from_costed(a).collect::<Vec<_>>(), vec![(b, 5)]);
+    assert_eq!(
+        maze.try_set_cost(Pos::new(0, 1), 2),
+        Err(MazeError::OutOfBounds(Pos::new(0, 1)))
+    );
+
+    maze.try_set_cost(c, 2).unwrap();
+    assert_eq!(maze.display(None), "•─•─•─•\n│  ▓ ░│\n•─•─•─•");
+}
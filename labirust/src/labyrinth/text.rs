@@ -0,0 +1,382 @@
+//! ## Text
+//!
+//! This module contains the parsing of a [`Maze`] from text laid out like [`Maze::display`],
+//! with one character per cell and per wall, so that a `width` by `height` maze takes `2 * width + 1` by `2 * height + 1` characters.
+//! Doors and keys (c.f. [`super::lock`]) are read from uppercase letters in wall slots and lowercase letters in cells.
+//! Mazes of other topologies are preceded by a line naming their topology, such as `topology: hex`.
+//! [`Maze::to_text`] writes the canonical text of a maze, read back by [`Maze::from_text`] into an identical maze.
+
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use alloc::{format, string::String, vec::Vec};
+use core::str::FromStr;
+
+use super::lock::KeyId;
+use super::topology::{WRAP_HORIZONTAL, WRAP_VERTICAL};
+use crate::{Error, HexDirection, Maze, Pos, Result, Topology};
+
+/// Characters of wall slots standing for an open passage, any other character being a wall.
+const OPEN: [char; 2] = [' ', '.'];
+/// Character of the cell marking the start.
+const START: char = 'S';
+/// Character of the cell marking the end.
+const END: char = 'E';
+/// Character of the cells marking the ends besides the first one, written by [`Maze::to_text`].
+const EXTRA_END: char = '+';
+/// Start of the line naming the topology of mazes that are not square.
+const HEADER: &str = "topology: ";
+/// Names of the topologies in the header line.
+const TOPOLOGIES: [(Topology, &str); 4] = [
+    (Topology::Square, "square"),
+    (Topology::Hex, "hex"),
+    (Topology::Torus, "torus"),
+    (Topology::Diagonal, "diagonal"),
+];
+
+/// Id of the key matching a letter, lowercase for keys and uppercase for doors.
+fn key_id(character: char, uppercase: bool) -> Option<KeyId> {
+    let base = if uppercase { b'A' } else { b'a' };
+    let is_letter = match uppercase {
+        true => character.is_ascii_uppercase(),
+        false => character.is_ascii_lowercase(),
+    };
+    is_letter.then(|| character as u8 - base)
+}
+
+impl Maze {
+    /// Parse a [`Maze`] from text laid out like [`Maze::display`], hand-crafted mazes being written with any characters,
+    /// such as `#` for walls and `.` or spaces for passages.
+    /// The start is the cell marked `S` and the ends are the cells marked `E`, defaulting to the top left and bottom right cells,
+    /// the first of them in row-major order being the [`Maze::end`]. Cells marked `+` are additional ends.
+    /// Empty lines before and after the maze are ignored, every other line must have the same length.
+    ///
+    /// Mazes of another [`Topology`] than [`Topology::Square`] start with a `topology: <name>` line,
+    /// the name being `square`, `hex`, `torus` or `diagonal`.
+    /// Toroidal mazes are opened across their edges by `┄` or `┆` walls, diagonal mazes across the corners by `╲`, `╱` or `╳`,
+    /// and hexagonal mazes are laid out with offset rows, lines being as long as their content.
+    pub fn parse(text: &str) -> Result<Self> {
+        let lines: Vec<(usize, Vec<char>)> = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.chars().collect()))
+            .skip_while(|(_, line): &(_, Vec<_>)| line.is_empty())
+            .collect();
+        let trailing = lines.iter().rev().take_while(|(_, line)| line.is_empty());
+        let lines = &lines[..lines.len() - trailing.count()];
+
+        let error = |line: usize, message: String| Error::Parse { line, message };
+        let (topology, lines) = match lines.split_first() {
+            Some(((index, first), rest)) if String::from_iter(first).starts_with(HEADER) => {
+                let name = String::from_iter(&first[HEADER.len()..]);
+                let name = name.trim();
+                let Some(&(topology, _)) = TOPOLOGIES.iter().find(|(_, known)| *known == name)
+                else {
+                    return Err(error(*index, format!("unknown topology {name:?}")));
+                };
+                let empty = rest.iter().take_while(|(_, line)| line.is_empty()).count();
+                (topology, &rest[empty..])
+            }
+            _ => (Topology::Square, lines),
+        };
+        match topology {
+            Topology::Hex => Self::parse_hex(lines),
+            Topology::Square | Topology::Torus | Topology::Diagonal => {
+                Self::parse_grid(lines, topology)
+            }
+        }
+    }
+
+    /// Parse the `lines` of a [`Maze`] laid out like the characters of [`Maze::display`], for every topology but [`Topology::Hex`].
+    fn parse_grid(lines: &[(usize, Vec<char>)], topology: Topology) -> Result<Self> {
+        let error = |line: usize, message: String| Error::Parse { line, message };
+        let Some((first, columns)) = lines.first().map(|(index, line)| (*index, line.len())) else {
+            return Err(error(1, "empty maze".into()));
+        };
+        if columns < 3 || columns.is_multiple_of(2) {
+            return Err(error(
+                first,
+                format!("expected an odd width of at least 3 characters, found {columns}"),
+            ));
+        }
+        if lines.len() < 3 || lines.len().is_multiple_of(2) {
+            let rows = lines.len();
+            return Err(error(
+                first,
+                format!("expected an odd height of at least 3 lines, found {rows}"),
+            ));
+        }
+        if let Some((index, line)) = lines.iter().find(|(_, line)| line.len() != columns) {
+            let length = line.len();
+            return Err(error(
+                *index,
+                format!("expected {columns} characters, found {length}"),
+            ));
+        }
+
+        let (width, height) = ((columns as isize - 1) / 2, (lines.len() as isize - 1) / 2);
+        let character = |x: isize, y: isize| lines[y as usize].1[x as usize];
+        let mut result = Self::from_cells(width, height, topology, |cell| {
+            character(cell.x() * 2 + 1, cell.y() * 2 + 1)
+        })?;
+        for cell in cells(width, height) {
+            let (x, y) = (cell.x() * 2 + 1, cell.y() * 2 + 1);
+            let neighbors = [
+                (
+                    cell.x() + 1 == width,
+                    (x + 1, y),
+                    Pos::new(cell.x() + 1, cell.y()),
+                ),
+                (
+                    cell.y() + 1 == height,
+                    (x, y + 1),
+                    Pos::new(cell.x(), cell.y() + 1),
+                ),
+            ];
+            for (outer, (wall_x, wall_y), neighbor) in neighbors {
+                let wall = character(wall_x, wall_y);
+                if outer {
+                    let wrapped =
+                        OPEN.contains(&wall) || [WRAP_HORIZONTAL, WRAP_VERTICAL].contains(&wall);
+                    if topology == Topology::Torus && wrapped {
+                        result.try_open_path(cell, result.wrap(neighbor))?;
+                    }
+                    continue;
+                }
+                let door = key_id(wall, true);
+                if !(OPEN.contains(&wall) || door.is_some()) {
+                    continue;
+                }
+                result.try_open_path(cell, neighbor)?;
+                if let Some(key) = door {
+                    result.try_add_door(cell, neighbor, key)?;
+                }
+            }
+
+            // the corner at the bottom right of the cell, crossed by diagonal passages
+            if topology != Topology::Diagonal || cell.x() + 1 == width || cell.y() + 1 == height {
+                continue;
+            }
+            let corner = character(x + 1, y + 1);
+            if ['╲', '╳'].contains(&corner) {
+                result.try_open_path(cell, Pos::new(cell.x() + 1, cell.y() + 1))?;
+            }
+            if ['╱', '╳'].contains(&corner) {
+                let (below, right) = (
+                    Pos::new(cell.x(), cell.y() + 1),
+                    Pos::new(cell.x() + 1, cell.y()),
+                );
+                result.try_open_path(below, right)?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Parse the `lines` of a hexagonal [`Maze`] laid out like [`Maze::display`],
+    /// cells being on every other line, every fourth character, odd rows being shifted by two characters.
+    fn parse_hex(lines: &[(usize, Vec<char>)]) -> Result<Self> {
+        let error = |line: usize, message: String| Error::Parse { line, message };
+        let Some((first, columns)) = lines.first().map(|(index, line)| (*index, line.len())) else {
+            return Err(error(1, "empty maze".into()));
+        };
+        if columns % 4 != 1 {
+            return Err(error(
+                first,
+                format!("expected a first row of 4 * width - 3 characters, found {columns}"),
+            ));
+        }
+        if lines.len().is_multiple_of(2) {
+            let rows = lines.len();
+            return Err(error(
+                first,
+                format!("expected an odd number of lines, found {rows}"),
+            ));
+        }
+
+        let (width, height) = ((columns as isize + 3) / 4, (lines.len() as isize + 1) / 2);
+        let character = |column: usize, line: usize| {
+            let characters = lines.get(line).map(|(_, characters)| characters);
+            characters
+                .and_then(|characters| characters.get(column).copied())
+                .unwrap_or(' ')
+        };
+        let coordinates = |cell: Pos| {
+            let (x, y) = (cell.x() as usize, cell.y() as usize);
+            (4 * x + 2 * (y % 2), 2 * y)
+        };
+        if let Some(cell) = cells(width, height).find(|&cell| {
+            let (column, line) = coordinates(cell);
+            character(column, line) == ' '
+        }) {
+            let (column, line) = coordinates(cell);
+            let index = lines[line].0;
+            return Err(error(index, format!("expected a cell at column {column}")));
+        }
+
+        let mut result = Self::from_cells(width, height, Topology::Hex, |cell| {
+            let (column, line) = coordinates(cell);
+            character(column, line)
+        })?;
+        for cell in cells(width, height) {
+            let (column, line) = coordinates(cell);
+            let passages = [
+                (HexDirection::East, character(column + 1, line) == '─'),
+                (
+                    HexDirection::SouthEast,
+                    character(column + 1, line + 1) == '╲',
+                ),
+                (
+                    HexDirection::SouthWest,
+                    column > 0 && character(column - 1, line + 1) == '╱',
+                ),
+            ];
+            for (direction, open) in passages {
+                let neighbor = direction.neighbor(cell);
+                if open && result.is_inside(neighbor) {
+                    result.try_open_path(cell, neighbor)?;
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Build a [`Maze`] with every wall closed, its start, ends and keys being read from the `character` of each cell.
+    fn from_cells(
+        width: isize,
+        height: isize,
+        topology: Topology,
+        character: impl Fn(Pos) -> char,
+    ) -> Result<Self> {
+        let marked = |marker| cells(width, height).find(|&cell| character(cell) == marker);
+        let start = marked(START).unwrap_or(Pos::zero());
+        let end = marked(END).unwrap_or(Pos::new(width - 1, height - 1));
+
+        let mut result = Self::try_new_topology(width, height, start, end, topology)?;
+        for cell in cells(width, height) {
+            if [END, EXTRA_END].contains(&character(cell)) {
+                result.try_add_end(cell)?;
+            }
+            if let Some(key) = key_id(character(cell), false) {
+                result.try_add_key(cell, key)?;
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Positions of the cells of a `width` by `height` maze, in row-major order.
+fn cells(width: isize, height: isize) -> impl Iterator<Item = Pos> {
+    (0..height).flat_map(move |y| (0..width).map(move |x| Pos::new(x, y)))
+}
+
+impl Maze {
+    /// Write the [`Maze`] as text, laid out like [`Maze::display`] with the start marked `S`, the end marked `E` and the other ends marked `+`,
+    /// after a line naming its topology unless it is square.
+    /// Reading it back with [`Maze::from_text`] gives an identical maze,
+    /// as long as the start and ends do not hold keys and it has neither portals nor costs nor several levels,
+    /// nor doors across the edges of a toroidal maze, across diagonals or in a hexagonal maze.
+    #[cfg(feature = "std")]
+    pub fn to_text(&self) -> String {
+        let mut overlay = HashMap::from([(self.start(), START)]);
+        overlay.extend(self.ends().map(|end| (end, EXTRA_END)));
+        overlay.insert(self.end(), END);
+        let text = self.display(Some(overlay));
+        match TOPOLOGIES
+            .iter()
+            .find(|(topology, _)| *topology == self.topology())
+        {
+            Some((Topology::Square, _)) | None => text,
+            Some((_, name)) => format!("{HEADER}{name}\n{text}"),
+        }
+    }
+
+    /// Read a [`Maze`] written by [`Maze::to_text`], or any text accepted by [`Maze::parse`].
+    pub fn from_text(text: &str) -> Result<Self> {
+        Self::parse(text)
+    }
+}
+
+impl FromStr for Maze {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        Self::parse(text)
+    }
+}
+
+#[test]
+fn parse() {
+    let maze = Maze::parse(
+        "
+#######
+#S    #
+# ### #
+#E  #E#
+#######
+",
+    )
+    .unwrap();
+    assert_eq!(maze.size(), (3, 2));
+    assert_eq!(maze.start(), Pos::zero());
+    assert_eq!(maze.end(), Pos::new(0, 1));
+    assert!(maze.is_end(Pos::new(2, 1)));
+    assert_eq!(maze.paths_from(Pos::new(1, 0)).len(), 2);
+    assert!(maze.is_walled(Pos::new(1, 1), Pos::new(2, 1)));
+    assert_eq!(maze.distance_field(maze.start()).reached(), 6);
+
+    let text = "•─•─•─•\n│   │ │\n• • •─•\n│ │ │ │\n•─• •─•\n│ │   │\n•─•─•─•";
+    let maze: Maze = text.parse().unwrap();
+    assert_eq!((maze.start(), maze.end()), (Pos::zero(), Pos::new(2, 2)));
+    #[cfg(feature = "std")]
+    assert_eq!(maze.display(None), text);
+
+    let maze = Maze::parse("#######\n#b  B #\n#######").unwrap();
+    assert_eq!(maze.key_at(Pos::zero()), Some(1));
+    assert_eq!(maze.door(Pos::new(1, 0), Pos::new(2, 0)), Some(1));
+
+    let error = Maze::parse("###\n# #\n##\n").unwrap_err();
+    assert!(matches!(error, Error::Parse { line: 3, .. }));
+    let error = Maze::parse("####\n#  #\n####").unwrap_err();
+    assert!(matches!(error, Error::Parse { line: 1, .. }));
+    assert!(matches!(Maze::parse("\n\n"), Err(Error::Parse { .. })));
+}
+
+#[cfg(all(feature = "std", feature = "generate"))]
+#[test]
+fn round_trip() {
+    use crate::{labyrinth::generator::MazeGenerator, SimpleGenerator};
+
+    // doors, keys and several ends
+    let mut maze = SimpleGenerator::new(6, 6).generate_seeded(0).unwrap();
+    let (a, b) = (Pos::new(2, 2), Pos::new(4, 1));
+    for (position, key) in [(b, 25), (a, 0)] {
+        maze.try_add_key(position, key).unwrap();
+        let neighbor = maze.paths_from(position)[0];
+        maze.try_add_door(neighbor, position, key).unwrap();
+    }
+    maze.try_add_end(Pos::new(5, 2)).unwrap();
+    maze.try_add_end(Pos::new(0, 5)).unwrap();
+    let text = maze.to_text();
+    assert_eq!(Maze::from_text(&text).unwrap(), maze, "{text}");
+
+    let maze = SimpleGenerator::new(3, 3)
+        .topology(Topology::Torus)
+        .generate_seeded(0)
+        .unwrap();
+    assert!(maze.to_text().starts_with("topology: torus\n•"));
+    let error = Maze::parse("topology: cube\n###\n# #\n###").unwrap_err();
+    assert!(matches!(error, Error::Parse { line: 1, .. }));
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn round_trips() {
+    use proptest::prelude::*;
+
+    use crate::testing::{mazes_of, topologies};
+
+    proptest!(|(maze in topologies().prop_flat_map(|topology| mazes_of(topology, 16, 16)))| {
+        let text = maze.to_text();
+        prop_assert_eq!(Maze::from_text(&text).unwrap(), maze, "{}", text);
+    });
+}
@@ -0,0 +1,117 @@
+//! ## Tile
+//!
+//! This module contains the [`TileKind`] classifying the tiles of a map of a [`Maze`],
+//! laid out like the characters of [`Maze::display`], shared by the frontends drawing mazes tile by tile.
+//! Only square and toroidal mazes are laid out on such maps, the passages wrapping around the latter opening their outer walls.
+
+use crate::{Maze, MazeError, Pos, Topology};
+
+/// Part of a [`Maze`] shown by a tile of a map of `2 * width + 1` by `2 * height + 1` tiles (c.f. [`Maze::tiles`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TileKind {
+    /// A wall, or a corner between walls.
+    Wall,
+    /// A cell of the maze.
+    Cell(Pos),
+    /// An open wall between two adjacent cells.
+    Opening(Pos, Pos),
+}
+
+impl TileKind {
+    /// Kind of the tile at `tile` in the map of `maze`, which must be square or toroidal (c.f. [`Maze::try_tiles`]).
+    pub fn of(maze: &Maze, tile: Pos) -> Self {
+        let (x, y) = tile.decompose();
+        let (columns, rows) = maze.tiles();
+        if !(0..columns).contains(&x) || !(0..rows).contains(&y) {
+            return Self::Wall;
+        }
+        let (column, row) = (x / 2, y / 2);
+        match (x % 2, y % 2) {
+            (1, 1) => Self::Cell(Pos::new(column, row)),
+            (0, 1) => Self::between(maze, Pos::new(column - 1, row), Pos::new(column, row)),
+            (1, 0) => Self::between(maze, Pos::new(column, row - 1), Pos::new(column, row)),
+            _ => Self::Wall,
+        }
+    }
+
+    /// Opening between two neighboring positions, once wrapped at the edges of toroidal mazes, if they are linked.
+    fn between(maze: &Maze, a: Pos, b: Pos) -> Self {
+        let (a, b) = (maze.wrap(a), maze.wrap(b));
+        if maze.is_inside(a) && maze.is_inside(b) && !maze.is_walled(a, b) {
+            Self::Opening(a, b)
+        } else {
+            Self::Wall
+        }
+    }
+
+    /// Cells shown by the tile, none for walls, one for cells and both sides of openings.
+    pub fn cells(self) -> impl Iterator<Item = Pos> {
+        let (a, b) = match self {
+            Self::Wall => (None, None),
+            Self::Cell(cell) => (Some(cell), None),
+            Self::Opening(a, b) => (Some(a), Some(b)),
+        };
+        a.into_iter().chain(b)
+    }
+}
+
+impl Maze {
+    /// Number of tiles of a map of the [`Maze`], horizontally and vertically (c.f. [`TileKind`]).
+    /// Panics if it is neither square nor toroidal (c.f. [`Maze::try_tiles`]).
+    pub fn tiles(&self) -> (isize, isize) {
+        match self.try_tiles() {
+            Ok(tiles) => tiles,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    /// Number of tiles of a map of the [`Maze`], horizontally and vertically,
+    /// fails with [`MazeError::UnsupportedTopology`] if it is neither square nor toroidal, as its cells are not laid out on a grid of tiles.
+    pub fn try_tiles(&self) -> Result<(isize, isize), MazeError> {
+        match self.topology() {
+            Topology::Square | Topology::Torus => {
+                let (width, height) = self.size();
+                Ok((width * 2 + 1, height * 2 + 1))
+            }
+            Topology::Hex | Topology::Diagonal => Err(MazeError::UnsupportedTopology),
+        }
+    }
+}
+
+#[cfg(all(feature = "std", feature = "generate"))]
+#[test]
+fn tile_kinds() {
+    use crate::{MazeGenerator, SimpleGenerator};
+
+    let maze = SimpleGenerator::new(4, 3).generate().unwrap();
+    assert_eq!(maze.tiles(), (9, 7));
+    let display = maze.display(None);
+    for (y, line) in display.lines().enumerate() {
+        for (x, character) in line.chars().enumerate() {
+            let kind = TileKind::of(&maze, Pos::new(x as isize, y as isize));
+            assert_eq!(kind == TileKind::Wall, character != ' ', "{x} {y}");
+            assert!(kind.cells().all(|cell| maze.is_inside(cell)));
+        }
+    }
+
+    // the passages wrapping around toroidal mazes open their outer walls, drawn dashed
+    let maze = SimpleGenerator::new(4, 3)
+        .topology(Topology::Torus)
+        .generate_seeded(1)
+        .unwrap();
+    let display = maze.display(None);
+    for (y, line) in display.lines().enumerate() {
+        for (x, character) in line.chars().enumerate() {
+            let kind = TileKind::of(&maze, Pos::new(x as isize, y as isize));
+            assert_eq!(
+                kind == TileKind::Wall,
+                !" ┄┆".contains(character),
+                "{x} {y}"
+            );
+        }
+    }
+
+    let maze = Maze::try_new_hex(4, 3, Pos::zero(), Pos::one()).unwrap();
+    assert_eq!(maze.try_tiles(), Err(MazeError::UnsupportedTopology));
+}
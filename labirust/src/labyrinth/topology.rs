@@ -0,0 +1,490 @@
+//! ## Topology
+//!
+//! This module contains the [`Topology`] of a [`Maze`], describing which cells neighbor each other.
+//! Hexagonal mazes store their cells in rows, odd rows being shifted half a cell to the east,
+//! and describe directions in axial coordinates (c.f. [`to_axial`]), where each [`HexDirection`] is a constant offset.
+//! Toroidal mazes wrap at their edges, the passages crossing an edge being drawn as dashed walls by [`Maze::display`],
+//! and diagonal passages are drawn across the corners they cross, as `╲`, `╱` or `╳`.
+
+#[cfg(feature = "std")]
+use std::{collections::HashMap, string::String};
+
+use alloc::vec::Vec;
+
+use smallvec::SmallVec;
+
+use crate::{path::direction_between, Direction, Maze, MazeError, Pos};
+
+/// Character of the horizontal walls crossed by a passage wrapping around a toroidal [`Maze`] in [`Maze::display`].
+pub(crate) const WRAP_HORIZONTAL: char = '┄';
+/// Character of the vertical walls crossed by a passage wrapping around a toroidal [`Maze`] in [`Maze::display`].
+pub(crate) const WRAP_VERTICAL: char = '┆';
+
+/// How the cells of a [`Maze`] neighbor each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Topology {
+    /// Square cells with four neighbors, one in each [`Direction`].
+    #[default]
+    Square,
+    /// Hexagonal cells with six neighbors, one in each [`HexDirection`].
+    Hex,
+    /// Square cells with four neighbors, the first and last columns being neighbors, as well as the first and last rows.
+    Torus,
+    /// Square cells with eight neighbors, one in each [`Direction`] and [`DiagonalDirection`].
+    Diagonal,
+}
+
+/// One of the four diagonal directions of the 2D-grid, `y` growing towards the south.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiagonalDirection {
+    NorthEast,
+    SouthEast,
+    SouthWest,
+    NorthWest,
+}
+
+impl DiagonalDirection {
+    /// Every diagonal direction, clockwise, starting from the [`DiagonalDirection::NorthEast`].
+    pub const ALL: [DiagonalDirection; 4] = [
+        DiagonalDirection::NorthEast,
+        DiagonalDirection::SouthEast,
+        DiagonalDirection::SouthWest,
+        DiagonalDirection::NorthWest,
+    ];
+
+    /// Vector pointing in this direction.
+    pub fn offset(self) -> Pos {
+        match self {
+            DiagonalDirection::NorthEast => Pos::new(1, -1),
+            DiagonalDirection::SouthEast => Pos::new(1, 1),
+            DiagonalDirection::SouthWest => Pos::new(-1, 1),
+            DiagonalDirection::NorthWest => Pos::new(-1, -1),
+        }
+    }
+
+    /// Bit flag of the direction, following the flags of [`Direction::bit`] and the start and end flags of a [`crate::Cell`].
+    pub fn bit(self) -> u16 {
+        1 << (8 + self as u16)
+    }
+
+    /// The direction pointing the other way.
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+}
+
+/// One of the six directions of a hexagonal grid, `y` growing towards the south.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HexDirection {
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+impl HexDirection {
+    /// Every direction, counter clockwise, starting from the [`HexDirection::East`].
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::NorthEast,
+        HexDirection::NorthWest,
+        HexDirection::West,
+        HexDirection::SouthWest,
+        HexDirection::SouthEast,
+    ];
+
+    /// Unit vector pointing in this direction, in axial coordinates.
+    pub fn axial_offset(self) -> Pos {
+        match self {
+            HexDirection::East => Pos::new(1, 0),
+            HexDirection::NorthEast => Pos::new(1, -1),
+            HexDirection::NorthWest => Pos::new(0, -1),
+            HexDirection::West => Pos::new(-1, 0),
+            HexDirection::SouthWest => Pos::new(-1, 1),
+            HexDirection::SouthEast => Pos::new(0, 1),
+        }
+    }
+
+    /// Bit flag of the direction, used to store sets of directions in a single byte.
+    pub fn bit(self) -> u8 {
+        1 << (self as u8)
+    }
+
+    /// The direction pointing the other way.
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 3) % 6]
+    }
+
+    /// Position of the neighbor of `position` in this direction.
+    pub fn neighbor(self, position: Pos) -> Pos {
+        from_axial(to_axial(position) + self.axial_offset())
+    }
+}
+
+/// Axial coordinates of a position of a hexagonal [`Maze`].
+pub fn to_axial(position: Pos) -> Pos {
+    let (x, y) = position.decompose();
+    Pos::new(x - y.div_euclid(2), y)
+}
+
+/// Position in a hexagonal [`Maze`] of axial coordinates.
+pub fn from_axial(axial: Pos) -> Pos {
+    let (q, r) = axial.decompose();
+    Pos::new(q + r.div_euclid(2), r)
+}
+
+/// Position within a toroidal [`Maze`] of `size` of any position, wrapping at its edges.
+fn wrap(position: Pos, (width, height): (isize, isize)) -> Pos {
+    Pos::new(
+        position.x().rem_euclid(width),
+        position.y().rem_euclid(height),
+    )
+}
+
+impl Topology {
+    pub(crate) fn is_square(&self) -> bool {
+        *self == Topology::Square
+    }
+
+    /// Neighbors of `position` in a [`Maze`] of `size`, inside it or not, along with the bit flag of the passage leading to each.
+    pub(crate) fn links(self, position: Pos, size: (isize, isize)) -> SmallVec<[(u16, Pos); 8]> {
+        let square = Direction::ALL
+            .into_iter()
+            .map(|direction| (u16::from(direction.bit()), position + direction));
+        match self {
+            Topology::Square => square.collect(),
+            Topology::Torus => square
+                .map(|(bit, neighbor)| (bit, wrap(neighbor, size)))
+                .collect(),
+            Topology::Hex => HexDirection::ALL
+                .into_iter()
+                .map(|direction| (u16::from(direction.bit()), direction.neighbor(position)))
+                .collect(),
+            Topology::Diagonal => square
+                .chain(
+                    DiagonalDirection::ALL
+                        .into_iter()
+                        .map(|direction| (direction.bit(), position + direction.offset())),
+                )
+                .collect(),
+        }
+    }
+
+    /// Neighbors of `position` in a [`Maze`] of `size`, inside it or not, clockwise around it.
+    pub(crate) fn around(self, position: Pos, size: (isize, isize)) -> SmallVec<[Pos; 8]> {
+        let links = self.links(position, size);
+        match self {
+            Topology::Square | Topology::Torus => links.into_iter().map(|(_, p)| p).collect(),
+            // hexagonal directions are listed counter clockwise
+            Topology::Hex => links.into_iter().rev().map(|(_, p)| p).collect(),
+            // each diagonal direction comes clockwise after one of the square ones, listed first
+            Topology::Diagonal => (0..4)
+                .flat_map(|index| [links[index].1, links[index + 4].1])
+                .collect(),
+        }
+    }
+
+    /// Bit flags of the passage between two neighbors in a [`Maze`] of `size`, from the first one then from the second one.
+    pub(crate) fn bits_between(
+        self,
+        position_a: Pos,
+        position_b: Pos,
+        size: (isize, isize),
+    ) -> Option<(u16, u16)> {
+        let widen = |(bit_a, bit_b): (u8, u8)| (u16::from(bit_a), u16::from(bit_b));
+        let square = |direction: Direction| widen((direction.bit(), direction.opposite().bit()));
+        match self {
+            Topology::Square => direction_between(position_a, position_b).map(square),
+            Topology::Torus => Direction::ALL
+                .into_iter()
+                .find(|&direction| wrap(position_a + direction, size) == position_b)
+                .map(square),
+            Topology::Hex => HexDirection::ALL
+                .into_iter()
+                .find(|direction| direction.neighbor(position_a) == position_b)
+                .map(|direction| widen((direction.bit(), direction.opposite().bit()))),
+            Topology::Diagonal => direction_between(position_a, position_b)
+                .map(square)
+                .or_else(|| {
+                    let offset = position_b - position_a;
+                    DiagonalDirection::ALL
+                        .into_iter()
+                        .find(|direction| direction.offset() == offset)
+                        .map(|direction| (direction.bit(), direction.opposite().bit()))
+                }),
+        }
+    }
+}
+
+impl Maze {
+    /// Fallible constructor of a [`Maze`] of the given [`Topology`] with every wall closed.
+    /// Fails when the size is not positive, or less than 3 in either dimension for toroidal mazes as opposite edges would already be neighbors,
+    /// or when the start or the end is out of bounds.
+    pub fn try_new_topology(
+        width: isize,
+        height: isize,
+        start: Pos,
+        end: Pos,
+        topology: Topology,
+    ) -> Result<Self, MazeError> {
+        if topology == Topology::Torus && (width < 3 || height < 3) {
+            return Err(MazeError::InvalidSize { width, height });
+        }
+        let mut result = Self::try_new(width, height, start, end, Vec::new())?;
+        result.topology = topology;
+        Ok(result)
+    }
+
+    /// Fallible constructor of a hexagonal [`Maze`] with every wall closed (c.f. [`Topology::Hex`] and [`Maze::try_new_topology`]).
+    pub fn try_new_hex(
+        width: isize,
+        height: isize,
+        start: Pos,
+        end: Pos,
+    ) -> Result<Self, MazeError> {
+        Self::try_new_topology(width, height, start, end, Topology::Hex)
+    }
+
+    /// Fallible constructor of a toroidal [`Maze`] with every wall closed (c.f. [`Topology::Torus`] and [`Maze::try_new_topology`]).
+    pub fn try_new_torus(
+        width: isize,
+        height: isize,
+        start: Pos,
+        end: Pos,
+    ) -> Result<Self, MazeError> {
+        Self::try_new_topology(width, height, start, end, Topology::Torus)
+    }
+
+    /// Position of any position once wrapped at the edges of a toroidal [`Maze`], unchanged for other topologies.
+    pub fn wrap(&self, position: Pos) -> Pos {
+        match self.topology {
+            Topology::Torus => wrap(position, self.size()),
+            Topology::Square | Topology::Hex | Topology::Diagonal => position,
+        }
+    }
+
+    /// How the cells of the [`Maze`] neighbor each other.
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Lowest number of steps between two positions allowed by the [`Topology`] of the [`Maze`], whatever its walls,
+    /// never overestimating the length of a path between them that does not go through portals.
+    pub fn min_distance(&self, from: Pos, to: Pos) -> usize {
+        let (dx, dy) = (to - from).decompose();
+        let (dx, dy) = (dx.unsigned_abs(), dy.unsigned_abs());
+        match self.topology {
+            Topology::Square => dx + dy,
+            Topology::Hex => {
+                let (dq, dr) = (to_axial(to) - to_axial(from)).decompose();
+                let dz = (dq + dr).unsigned_abs();
+                dq.unsigned_abs().max(dr.unsigned_abs()).max(dz)
+            }
+            Topology::Torus => {
+                let (width, height) = self.size();
+                dx.min(width as usize - dx) + dy.min(height as usize - dy)
+            }
+            Topology::Diagonal => dx.max(dy),
+        }
+    }
+
+    /// Lowest [`Maze::min_distance`] from `position` to any of the ends.
+    pub fn min_distance_to_end(&self, position: Pos) -> usize {
+        let distances = self.ends().map(|end| self.min_distance(position, end));
+        distances.min().unwrap_or(0)
+    }
+
+    /// Column and line of the character standing for `position` in [`Maze::display`].
+    #[cfg(feature = "std")]
+    pub(crate) fn display_coordinates(&self, position: Pos) -> (usize, usize) {
+        let (x, y) = (position.x() as usize, position.y() as usize);
+        match self.topology {
+            Topology::Square | Topology::Torus | Topology::Diagonal => (2 * x + 1, 2 * y + 1),
+            Topology::Hex => (4 * x + 2 * (y % 2), 2 * y),
+        }
+    }
+
+    /// Character of the corner at the top left of the cell at `position` in [`Maze::display`], crossed by the diagonal passages around it.
+    #[cfg(feature = "std")]
+    pub(crate) fn corner(&self, position: Pos) -> char {
+        if self.topology != Topology::Diagonal {
+            return '•';
+        }
+        let crossed = |from: Pos, direction: DiagonalDirection| {
+            let to = from + direction.offset();
+            self.is_inside(from) && self.is_inside(to) && !self.is_walled(from, to)
+        };
+        let north_west = position + DiagonalDirection::NorthWest.offset();
+        let south_west = position + Direction::West;
+        let falling = crossed(north_west, DiagonalDirection::SouthEast);
+        let rising = crossed(south_west, DiagonalDirection::NorthEast);
+        match (falling, rising) {
+            (true, true) => '╳',
+            (true, false) => '╲',
+            (false, true) => '╱',
+            (false, false) => '•',
+        }
+    }
+
+    /// Write the representation of a hexagonal [`Maze`] into `out`, replacing its content.
+    /// Cells are drawn as `·` on every other line, odd rows being shifted to the east, trailing spaces being left out,
+    /// with their open passages drawn as `───` to the east and `╱` or `╲` to the cells below.
+    #[cfg(feature = "std")]
+    pub(crate) fn display_hex_into(&self, overlay: Option<&HashMap<Pos, char>>, out: &mut String) {
+        out.clear();
+        let (width, height) = self.size();
+        let columns = 4 * width as usize + 1;
+        let mut lines = vec![vec![' '; columns]; 2 * height as usize - 1];
+        for (position, _) in self.iter() {
+            let (column, line) = self.display_coordinates(position);
+            lines[line][column] = overlay
+                .and_then(|overlay| overlay.get(&position).copied())
+                .or_else(|| {
+                    self.portals_from(position)
+                        .next()
+                        .map(|_| super::portal::PORTAL)
+                })
+                .or_else(|| {
+                    self.key_at(position)
+                        .map(|key| super::lock::glyph(key, false))
+                })
+                .or_else(|| self.shade(position))
+                .unwrap_or('·');
+            let passages = [
+                (HexDirection::East, (column + 1..column + 4, line), '─'),
+                (
+                    HexDirection::SouthEast,
+                    (column + 1..column + 2, line + 1),
+                    '╲',
+                ),
+                (
+                    HexDirection::SouthWest,
+                    (column.wrapping_sub(1)..column, line + 1),
+                    '╱',
+                ),
+            ];
+            for (direction, (columns, line), character) in passages {
+                let neighbor = direction.neighbor(position);
+                if self.is_inside(neighbor) && !self.is_walled(position, neighbor) {
+                    lines[line][columns].fill(character);
+                }
+            }
+        }
+        for (index, line) in lines.iter().enumerate() {
+            if index > 0 {
+                out.push('\n');
+            }
+            let length = line
+                .iter()
+                .rposition(|&c| c != ' ')
+                .map_or(0, |last| last + 1);
+            out.extend(&line[..length]);
+        }
+    }
+}
+
+#[test]
+fn torus() {
+    let (start, end) = (Pos::zero(), Pos::new(2, 2));
+    let mut maze = Maze::try_new_torus(3, 3, start, end).unwrap();
+    assert_eq!(maze.topology(), Topology::Torus);
+    assert_eq!(maze.wrap(Pos::new(-1, 3)), Pos::new(2, 0));
+    let (a, b) = (Pos::zero(), Pos::new(2, 0));
+    assert_eq!(maze.adjascent(a).len(), 4);
+    assert!(maze.adjascent(a).contains(&b));
+    let around = [(0, 2), (1, 0), (0, 1), (2, 0)].map(Pos::from);
+    assert_eq!(maze.around(a).as_slice(), &around);
+    maze.try_open_path(a, b).unwrap();
+    maze.try_open_path(b, end).unwrap();
+    assert_eq!(maze.paths_from(a).as_slice(), &[b]);
+    assert!(!maze.is_walled(b, a) && maze.is_walled(a, Pos::new(-1, 0)));
+    assert_eq!(maze.distance_field(a).distance(end), Some(2));
+    #[cfg(feature = "std")]
+    assert_eq!(
+        maze.display(None),
+        "•─•─•┄•\n┆ │ │ ┆\n•─•─•─•\n│ │ │ │\n•─•─•─•\n│ │ │ │\n•─•─•┄•"
+    );
+
+    let error = Maze::try_new_torus(2, 5, start, start).unwrap_err();
+    assert_eq!(
+        error,
+        MazeError::InvalidSize {
+            width: 2,
+            height: 5
+        }
+    );
+}
+
+#[test]
+fn diagonal() {
+    let (a, b, c, d) = (Pos::zero(), Pos::new(1, 0), Pos::new(0, 1), Pos::new(1, 1));
+    let mut maze = Maze::try_new_topology(2, 2, a, d, Topology::Diagonal).unwrap();
+    assert_eq!(maze.adjascent(a).as_slice(), &[b, c, d]);
+    let around = [
+        (1, 0),
+        (2, 0),
+        (2, 1),
+        (2, 2),
+        (1, 2),
+        (0, 2),
+        (0, 1),
+        (0, 0),
+    ];
+    assert_eq!(maze.around(d).as_slice(), &around.map(Pos::from));
+    maze.try_open_path(a, d).unwrap();
+    maze.try_open_path(b, c).unwrap();
+    assert!(!maze.is_walled(d, a) && maze.is_walled(a, b));
+    assert_eq!(maze.paths_from(c).as_slice(), &[b]);
+    assert_eq!(maze[a].degree(), 1);
+    assert_eq!(maze.distance_field(a).distance(d), Some(1));
+    #[cfg(feature = "std")]
+    assert_eq!(maze.display(None), "•─•─•\n│ │ │\n•─╳─•\n│ │ │\n•─•─•");
+    maze.try_close_path(c, b).unwrap();
+    #[cfg(feature = "std")]
+    assert_eq!(maze.display(None), "•─•─•\n│ │ │\n•─╲─•\n│ │ │\n•─•─•");
+}
+
+#[test]
+fn hex() {
+    // axial coordinates
+    let position = Pos::new(2, 3);
+    assert_eq!(from_axial(to_axial(position)), position);
+    let neighbors: Vec<_> = HexDirection::ALL
+        .into_iter()
+        .map(|direction| direction.neighbor(position))
+        .collect();
+    let expected = [(3, 3), (3, 2), (2, 2), (1, 3), (2, 4), (3, 4)];
+    assert_eq!(neighbors, expected.map(Pos::from));
+    let around = Topology::Hex.around(position, (5, 5));
+    let clockwise = [(3, 4), (2, 4), (1, 3), (2, 2), (3, 2), (3, 3)];
+    assert_eq!(around.as_slice(), &clockwise.map(Pos::from));
+    for direction in HexDirection::ALL {
+        assert_eq!(
+            direction.opposite().neighbor(direction.neighbor(position)),
+            position
+        );
+    }
+
+    // a zigzag between the two rows
+    //  a   b
+    //    c   d
+    let (a, b, c, d) = (Pos::zero(), Pos::new(1, 0), Pos::new(0, 1), Pos::new(1, 1));
+    let mut maze = Maze::try_new_hex(2, 2, a, d).unwrap();
+    assert_eq!(maze.topology(), Topology::Hex);
+    assert_eq!(maze.adjascent(a).as_slice(), &[b, c]);
+    assert_eq!(maze.adjascent(d).as_slice(), &[b, c]);
+    maze.try_open_path(a, c).unwrap();
+    maze.try_open_path(c, b).unwrap();
+    maze.try_open_path(b, d).unwrap();
+    assert_eq!(maze.try_open_path(a, d), Err(MazeError::NotAdjacent(a, d)));
+    assert!(maze.is_walled(a, b) && !maze.is_walled(b, c));
+    assert_eq!(maze.paths_from(b).as_slice(), &[c, d]);
+    assert_eq!(maze.distance_field(a).distance(d), Some(3));
+    #[cfg(feature = "std")]
+    assert_eq!(maze.display(None), "·   ·\n ╲ ╱ ╲\n  ·   ·");
+}
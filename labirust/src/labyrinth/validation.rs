@@ -0,0 +1,244 @@
+//! ## Validation
+//!
+//! This module contains the [`Validation`] report of a [`Maze`], listing the inconsistencies of mazes built by hand or deserialized
+//! before they reach the rest of the library, along with its connectivity.
+
+use alloc::{collections::VecDeque, vec::Vec};
+
+use crate::{Grid, Maze, MazeError, Pos};
+
+/// Report of the structure of a [`Maze`] (c.f. [`Maze::validate`]).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Validation {
+    mismatched_cells: Option<(isize, isize)>,
+    disconnected: Vec<Pos>,
+    out_of_bounds: Vec<(Pos, Pos)>,
+    one_sided: Vec<(Pos, Pos)>,
+    duplicated: Vec<(Pos, Pos)>,
+    unreachable_ends: Vec<Pos>,
+    perfect: bool,
+}
+
+impl Validation {
+    /// Size of the cells stored by the [`Maze`] when it differs from its own size, the rest of the report then being empty.
+    pub fn mismatched_cells(&self) -> Option<(isize, isize)> {
+        self.mismatched_cells
+    }
+
+    /// Positions that can not be reached from the start, in row-major order.
+    pub fn disconnected(&self) -> &[Pos] {
+        &self.disconnected
+    }
+
+    /// Passages and portals leading out of the [`Maze`], from their position inside it if any.
+    pub fn out_of_bounds(&self) -> &[(Pos, Pos)] {
+        &self.out_of_bounds
+    }
+
+    /// Passages open from their first position only.
+    pub fn one_sided(&self) -> &[(Pos, Pos)] {
+        &self.one_sided
+    }
+
+    /// Portals doubling a passage between adjacent cells.
+    pub fn duplicated(&self) -> &[(Pos, Pos)] {
+        &self.duplicated
+    }
+
+    /// Ends that can not be reached from the start.
+    pub fn unreachable_ends(&self) -> &[Pos] {
+        &self.unreachable_ends
+    }
+
+    /// Check if every position is linked to every other one by exactly one path, the passages forming a tree.
+    pub fn is_perfect(&self) -> bool {
+        self.perfect
+    }
+
+    /// Check if the [`Maze`] is consistent, its cells matching its size and its passages none leading outside of it, open on one side only or duplicated.
+    pub fn is_valid(&self) -> bool {
+        self.check().is_ok()
+    }
+
+    /// The first inconsistency of the [`Maze`] as an error, reachability aside.
+    pub fn check(&self) -> Result<(), MazeError> {
+        if let Some((width, height)) = self.mismatched_cells {
+            return Err(MazeError::InvalidSize { width, height });
+        }
+        if let Some(&(_, outside)) = self.out_of_bounds.first() {
+            return Err(MazeError::OutOfBounds(outside));
+        }
+        if let Some(&(position_a, position_b)) = self.one_sided.first() {
+            return Err(MazeError::OneSided(position_a, position_b));
+        }
+        if let Some(&(position_a, position_b)) = self.duplicated.first() {
+            return Err(MazeError::AlreadyOpen(position_a, position_b));
+        }
+        Ok(())
+    }
+}
+
+impl Maze {
+    /// Inspect the structure of the [`Maze`], without panicking whatever its content (c.f. [`Validation`]).
+    /// Locked doors are ignored, their passages counting as open.
+    pub fn validate(&self) -> Validation {
+        if self.cells.size() != self.size() || self.width <= 0 || self.height <= 0 {
+            return Validation {
+                mismatched_cells: Some(self.cells.size()),
+                disconnected: Vec::new(),
+                out_of_bounds: Vec::new(),
+                one_sided: Vec::new(),
+                duplicated: Vec::new(),
+                unreachable_ends: Vec::new(),
+                perfect: false,
+            };
+        }
+        let mut out_of_bounds = Vec::new();
+        let mut one_sided = Vec::new();
+        let mut duplicated = Vec::new();
+        let mut passages = 0;
+
+        for position in [self.start, self.end]
+            .into_iter()
+            .chain(self.extra_ends.iter().copied())
+        {
+            if self.get(position).is_none() {
+                out_of_bounds.push((position, position));
+            }
+        }
+        for (position, cell) in self.iter() {
+            for (bit, neighbor) in self.topology.links(position, self.size()) {
+                if cell.openings() & bit == 0 {
+                    continue;
+                }
+                let back = self
+                    .bits_between(position, neighbor)
+                    .filter(|_| self.is_inside(neighbor))
+                    .and_then(|(_, back)| Some((back, self.get(neighbor)?)));
+                match back {
+                    None => out_of_bounds.push((position, neighbor)),
+                    Some((back, cell)) if cell.openings() & back == 0 => {
+                        one_sided.push((position, neighbor))
+                    }
+                    Some(_) if position < neighbor => passages += 1,
+                    Some(_) => (),
+                }
+            }
+        }
+        for (position_a, position_b) in self.portals() {
+            let inside = |position| self.get(position).is_some();
+            if !inside(position_a) || !inside(position_b) {
+                out_of_bounds.push((position_a, position_b));
+                continue;
+            }
+            let doubled = self
+                .bits_between(position_a, position_b)
+                .is_some_and(|(bit, _)| self.openings(position_a) & bit != 0);
+            if doubled {
+                duplicated.push((position_a, position_b));
+            } else {
+                passages += 1;
+            }
+        }
+
+        let reached = self.reached_from_start();
+        let is_reached = |position| reached.get(position).copied().unwrap_or(false);
+        let disconnected: Vec<_> = reached
+            .iter()
+            .filter(|(_, &reached)| !reached)
+            .map(|(position, _)| position)
+            .collect();
+        let unreachable_ends = self.ends().filter(|&end| !is_reached(end)).collect();
+        let cells = (self.width * self.height) as usize;
+        let perfect = disconnected.is_empty()
+            && out_of_bounds.is_empty()
+            && one_sided.is_empty()
+            && duplicated.is_empty()
+            && passages + 1 == cells;
+
+        Validation {
+            mismatched_cells: None,
+            disconnected,
+            out_of_bounds,
+            one_sided,
+            duplicated,
+            unreachable_ends,
+            perfect,
+        }
+    }
+
+    /// Positions reachable from the start, following passages that stay inside the [`Maze`].
+    fn reached_from_start(&self) -> Grid<bool> {
+        let mut reached = Grid::new(self.width, self.height, false);
+        let mut queue = VecDeque::new();
+        if self.get(self.start).is_some() {
+            reached[self.start] = true;
+            queue.push_back(self.start);
+        }
+        while let Some(position) = queue.pop_front() {
+            let (openings, links) = (
+                self.openings(position),
+                self.topology.links(position, self.size()),
+            );
+            let walls = links
+                .into_iter()
+                .filter(|&(bit, _)| openings & bit != 0)
+                .map(|(_, neighbor)| neighbor);
+            for neighbor in walls.chain(self.portals_from(position)) {
+                if let Some(seen @ false) = reached.get_mut(neighbor) {
+                    *seen = true;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        reached
+    }
+}
+
+#[test]
+fn validate() {
+    use alloc::vec;
+
+    // a corridor going around a wall
+    //  a b
+    //  d c
+    let (a, b, c, d) = (Pos::zero(), Pos::new(1, 0), Pos::new(1, 1), Pos::new(0, 1));
+    let mut maze = Maze::new(2, 2, a, d, vec![(a, vec![b]), (b, vec![c]), (c, vec![d])]);
+    let report = maze.validate();
+    assert!(report.is_valid() && report.is_perfect());
+    assert!(report.disconnected().is_empty() && report.unreachable_ends().is_empty());
+
+    maze.add_portal(a, d);
+    let report = maze.validate();
+    assert!(report.is_valid() && !report.is_perfect());
+    maze.add_portal(a, b);
+    assert_eq!(maze.validate().duplicated(), [(a, b)]);
+    assert_eq!(maze.validate().check(), Err(MazeError::AlreadyOpen(a, b)));
+
+    let mut maze = Maze::new(2, 2, a, d, vec![(a, vec![b])]);
+    let report = maze.validate();
+    assert_eq!(report.disconnected(), [d, c]);
+    assert_eq!(report.unreachable_ends(), [d]);
+    assert!(report.is_valid() && !report.is_perfect());
+
+    // cells opened by hand, as a deserialized maze could be
+    let north = u16::from(crate::Direction::North.bit());
+    maze.cells[a].open(north);
+    maze.cells[d].open(north);
+    let report = maze.validate();
+    assert_eq!(report.out_of_bounds(), [(a, Pos::new(0, -1))]);
+    assert_eq!(report.one_sided(), [(d, a)]);
+    assert_eq!(report.check(), Err(MazeError::OutOfBounds(Pos::new(0, -1))));
+
+    // cells not matching the size of the maze
+    maze.width = 50;
+    let report = maze.validate();
+    assert_eq!(report.mismatched_cells(), Some((2, 2)));
+    assert_eq!(
+        report.check(),
+        Err(MazeError::InvalidSize {
+            width: 2,
+            height: 2
+        })
+    );
+}
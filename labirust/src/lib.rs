@@ -5,17 +5,99 @@
 //! * It exposes the [`Algorithm`] trait encapsulating the behavior of such an algorithm.
 //! * It also provides structures to generate [`Maze`] ([`generate`]) and execute said algorithms on them ([`Executor`]).
 //!
+//! ## Features
+//!
+//! * `std` (default): enables the headless [`Executor`], the SVG figures of [`Maze::to_svg`] and, with `generate`, the generators drawing from the thread-local random generator.
+//!   Without it, the crate is `no_std` and only requires `alloc`, generators then take their random generator as a parameter.
+//! * `generate` (default): enables the random generators, such as the [`SimpleGenerator`] and the [`InfiniteMaze`], through `rand`.
+//!   The [`MazeGenerator`] trait is always available.
+//! * `render-terminal`: enables [`Renderer::Terminal`], drawing each tick of an [`Executor`] in the terminal through `termion`,
+//!   which becomes the default renderer.
+//! * `bench`: enables the [`bench`] module, measuring generators, algorithms and the storage of the walls.
+//! * `evcxr`: shows [`Maze`]s and [`Execution`]s as inline SVG figures in Jupyter notebooks running the evcxr kernel,
+//!   through their `evcxr_display` method.
+//! * `image`: converts [`Maze`]s to and from the images of the `image` crate (c.f. [`Maze::to_rgba_image`]),
+//!   to save or load them as PNG or BMP, draws larger renderings of them (c.f. [`Maze::to_image`]),
+//!   and the [`ImageGenerator`] reading mazes from image files.
+//! * `pathfinding`: enables the [`pathfinding`] module, adapting mazes to the solvers of the `pathfinding` crate.
+//! * `plugins`: enables the [`plugins`] module, loading algorithms from shared libraries into an [`implementations::Registry`].
+//! * `remote`: enables the [`remote`] module, running algorithms implemented by other programs through a socket.
+//! * `rayon`: enables the [`ParallelGenerator`].
+//! * `log`: emits diagnostics about generation, execution ticks, invalid guesses and rendering through the `log` facade,
+//!   instead of printing over the terminal output.
+//! * `testing`: enables the [`testing`] module, with `proptest` strategies and invariant checks to property-test algorithms and generators.
+//! * `wasm`: enables the [`wasm`] bindings, to be built without the default features for `wasm32-unknown-unknown`.
+//! * `serde`: implements `Serialize` and `Deserialize` for the data types of the crate, such as [`Maze`], [`Pos`] or [`ExecutionReport`].
+//!
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[macro_use]
+mod logging;
 
 mod algorithm;
+#[cfg(feature = "bench")]
+pub mod bench;
+mod direction;
+mod error;
+#[cfg(feature = "evcxr")]
+mod evcxr;
 mod executor;
+mod grid;
 pub mod implementations;
 mod labyrinth;
+pub mod path;
+#[cfg(feature = "pathfinding")]
+pub mod pathfinding;
+#[cfg(feature = "plugins")]
+pub mod plugins;
 mod position;
+#[cfg(feature = "remote")]
+pub mod remote;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod visited;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
-pub use algorithm::Algorithm;
-pub use executor::{Context, Executor, Guess, Insight};
+pub use algorithm::{Algorithm, CloneableAlgorithm};
+pub use direction::Direction;
+pub use error::{Error, Result};
+pub use executor::{Anchor, Context, Execution, Guess, Insight, TraceFrame, Visibility};
+#[cfg(feature = "std")]
+pub use executor::{ExecutionReport, Executor, Renderer};
+pub use grid::{Grid, MazeMap};
+#[cfg(all(feature = "std", feature = "generate"))]
+pub use labyrinth::generator::Braider;
+#[cfg(feature = "image")]
+pub use labyrinth::generator::ImageGenerator;
+#[cfg(feature = "rayon")]
+pub use labyrinth::generator::ParallelGenerator;
+#[cfg(feature = "image")]
+pub use labyrinth::image::ImageStyle;
+pub use labyrinth::{
+    cell::{Cell, Cells},
+    distance::DistanceField,
+    generator::{GenerationError, MazeGenerator},
+    level::Pos3,
+    lock::KeyId,
+    stats::MazeStats,
+    tile::TileKind,
+    topology::{DiagonalDirection, HexDirection, Topology},
+    validation::Validation,
+    Maze, MazeError, Neighbors,
+};
+#[cfg(feature = "generate")]
 pub use labyrinth::{
-    generator::{MazeGenerator, SimpleGenerator},
-    Maze,
+    generator::{
+        braid, AldousBroderGenerator, CellSelection, GrowingTreeGenerator, HexGenerator,
+        HuntAndKillGenerator, ImperfectGenerator, KruskalGenerator, LayeredGenerator, Placement,
+        SeededGenerator, SimpleGenerator, WilsonGenerator,
+    },
+    infinite::InfiniteMaze,
 };
+pub use path::Path;
 pub use position::Pos;
+pub use visited::VisitedSet;
@@ -0,0 +1,34 @@
+//! ## Logging
+//!
+//! This module contains the macros emitting diagnostics through the [`log`](https://docs.rs/log) facade when the `log` feature is enabled.
+//! Without it, they compile to nothing while still type-checking their arguments.
+
+/// Emit a trace level event.
+macro_rules! trace {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "log")]
+        ::log::trace!($($arg)*);
+        #[cfg(not(feature = "log"))]
+        let _ = format_args!($($arg)*);
+    }};
+}
+
+/// Emit a debug level event.
+macro_rules! debug {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "log")]
+        ::log::debug!($($arg)*);
+        #[cfg(not(feature = "log"))]
+        let _ = format_args!($($arg)*);
+    }};
+}
+
+/// Emit a warning level event.
+macro_rules! warn {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "log")]
+        ::log::warn!($($arg)*);
+        #[cfg(not(feature = "log"))]
+        let _ = format_args!($($arg)*);
+    }};
+}
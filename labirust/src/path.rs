@@ -0,0 +1,108 @@
+//! ## Path
+//!
+//! This module contains primitives to manipulate sequences of positions, and the [`Path`] type wrapping them.
+
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+use crate::{Direction, Pos};
+
+/// Check that every two consecutive positions of `path` are adjacent on the grid.
+pub fn is_adjacent_chain(path: &[Pos]) -> bool {
+    path.windows(2)
+        .all(|pair| direction_between(pair[0], pair[1]).is_some())
+}
+
+/// Returns the [`Direction`] leading from `from` to `to`, `None` if they are not adjacent.
+pub fn direction_between(from: Pos, to: Pos) -> Option<Direction> {
+    let offset = to - from;
+    Direction::ALL.into_iter().find(|d| d.offset() == offset)
+}
+
+/// Returns a copy of `path` without stutters (repeated positions) nor backtracks (going back and forth between two positions).
+pub fn simplify(path: &[Pos]) -> Vec<Pos> {
+    let mut result: Vec<Pos> = Vec::with_capacity(path.len());
+    for &position in path {
+        let len = result.len();
+        if result.last() == Some(&position) {
+            continue;
+        } else if len >= 2 && result[len - 2] == position {
+            result.pop();
+        } else {
+            result.push(position);
+        }
+    }
+    result
+}
+
+/// A sequence of positions, usually leading from the start of a [`crate::Maze`] to some cell.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Path(Vec<Pos>);
+
+impl Path {
+    /// Constructor.
+    pub fn new(positions: Vec<Pos>) -> Self {
+        Self(positions)
+    }
+
+    /// The positions composing the [`Path`].
+    pub fn positions(&self) -> &[Pos] {
+        &self.0
+    }
+
+    /// Unwrap the [`Path`] into its positions.
+    pub fn into_positions(self) -> Vec<Pos> {
+        self.0
+    }
+
+    /// Check that every two consecutive positions of the [`Path`] are adjacent.
+    pub fn is_adjacent_chain(&self) -> bool {
+        is_adjacent_chain(&self.0)
+    }
+
+    /// Returns a copy of the [`Path`] without stutters nor backtracks.
+    pub fn simplify(&self) -> Self {
+        Self(simplify(&self.0))
+    }
+
+    /// Iterates over the [`Direction`] of each step of the [`Path`], `None` for steps between non-adjacent positions.
+    pub fn directions(&self) -> impl Iterator<Item = Option<Direction>> + '_ {
+        self.0
+            .windows(2)
+            .map(|pair| direction_between(pair[0], pair[1]))
+    }
+}
+
+impl From<Vec<Pos>> for Path {
+    fn from(positions: Vec<Pos>) -> Self {
+        Self::new(positions)
+    }
+}
+
+impl Deref for Path {
+    type Target = [Pos];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[test]
+fn helpers() {
+    let positions: Vec<Pos> = [(0, 0), (1, 0), (1, 0), (1, 1), (1, 0), (2, 0)]
+        .into_iter()
+        .map(Pos::from)
+        .collect();
+    let path = Path::new(positions);
+    assert!(!path.is_adjacent_chain());
+
+    let simplified = path.simplify();
+    assert!(simplified.is_adjacent_chain());
+    assert_eq!(
+        simplified.positions(),
+        &[(0, 0).into(), (1, 0).into(), (2, 0).into()]
+    );
+    assert!(simplified.directions().all(|d| d == Some(Direction::East)));
+    assert_eq!(direction_between(Pos::zero(), Pos::new(0, 2)), None);
+}
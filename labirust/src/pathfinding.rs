@@ -0,0 +1,125 @@
+//! ## Pathfinding
+//!
+//! This module adapts a [`Maze`] to the solvers of the [`pathfinding`](https://docs.rs/pathfinding) crate.
+//! It provides the successor and heuristic closures they expect, and wraps their results into [`Path`]s,
+//! giving reference solutions to validate [`crate::Algorithm`]s against.
+
+use ::pathfinding::prelude::{astar, bfs, dijkstra};
+use smallvec::SmallVec;
+
+use crate::{Maze, Neighbors, Path, Pos};
+
+/// Successors of a position in `maze`, as expected by [`pathfinding::prelude::bfs`].
+pub fn successors(maze: &Maze) -> impl Fn(&Pos) -> Neighbors + '_ {
+    |&position| maze.paths_from(position)
+}
+
+/// Successors of a position in `maze` with the cost of entering them (c.f. [`Maze::cost`]),
+/// as expected by [`pathfinding::prelude::dijkstra`] and [`pathfinding::prelude::astar`].
+pub fn weighted_successors(maze: &Maze) -> impl Fn(&Pos) -> SmallVec<[(Pos, usize); 8]> + '_ {
+    |&position| {
+        let costed = maze.paths_from_costed(position);
+        costed.map(|(p, cost)| (p, cost as usize)).collect()
+    }
+}
+
+/// Lowest distance from a position to the closest end of `maze` (c.f. [`Maze::min_distance_to_end`]) times the lowest cost of its cells (c.f. [`Maze::min_cost`]),
+/// an admissible heuristic for [`pathfinding::prelude::astar`] in mazes without portals.
+pub fn heuristic(maze: &Maze) -> impl Fn(&Pos) -> usize + '_ {
+    let min_cost = maze.min_cost() as usize;
+    move |&position| maze.min_distance_to_end(position) * min_cost
+}
+
+/// Shortest [`Path`] from the start to the end of `maze` found by a breadth-first search.
+pub fn solve_bfs(maze: &Maze) -> Option<Path> {
+    bfs(&maze.start(), successors(maze), |&p| maze.is_end(p)).map(Path::from)
+}
+
+/// Cheapest [`Path`] from the start to the end of `maze` found by Dijkstra's algorithm.
+pub fn solve_dijkstra(maze: &Maze) -> Option<Path> {
+    dijkstra(&maze.start(), weighted_successors(maze), |&p| {
+        maze.is_end(p)
+    })
+    .map(|(path, _)| Path::from(path))
+}
+
+/// Cheapest [`Path`] from the start to the end of `maze` found by the A* algorithm.
+pub fn solve_astar(maze: &Maze) -> Option<Path> {
+    astar(
+        &maze.start(),
+        weighted_successors(maze),
+        heuristic(maze),
+        |&p| maze.is_end(p),
+    )
+    .map(|(path, _)| Path::from(path))
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn reference_solvers() {
+    use crate::{
+        executor::drive, implementations::BreathFirst, ImperfectGenerator, MazeGenerator,
+        SimpleGenerator,
+    };
+
+    let maze = SimpleGenerator::new(30, 20).generate().unwrap();
+    let expected = maze
+        .distance_field(maze.start())
+        .distance(maze.end())
+        .unwrap()
+        + 1;
+    for path in [solve_bfs(&maze), solve_dijkstra(&maze), solve_astar(&maze)] {
+        let path = path.unwrap();
+        assert_eq!(path.len(), expected);
+        assert!(path.is_adjacent_chain());
+    }
+
+    // the bundled breath first algorithm finds a path as short
+    let mut last = Vec::new();
+    drive(
+        &maze,
+        &mut BreathFirst::new(),
+        Default::default(),
+        |_, guess| last = guess,
+    )
+    .unwrap();
+    assert_eq!(last.len(), expected);
+
+    // the closest of several ends, in a maze with loops
+    let mut looped = ImperfectGenerator::new(10, 8, 0.5).generate().unwrap();
+    looped.try_add_end(Pos::new(6, 2)).unwrap();
+    let field = looped.distance_field(looped.start());
+    let closest = looped.ends().filter_map(|end| field.distance(end)).min();
+    for path in [
+        solve_bfs(&looped),
+        solve_dijkstra(&looped),
+        solve_astar(&looped),
+    ] {
+        assert_eq!(Some(path.unwrap().len() - 1), closest);
+    }
+}
+
+#[test]
+fn weighted_solvers() {
+    use alloc::vec;
+
+    // a direct corridor through an expensive cell, and a detour around it
+    let [a, b, c] = [0, 1, 2].map(|x| Pos::new(x, 0));
+    let [d, e, f] = [0, 1, 2].map(|x| Pos::new(x, 1));
+    let paths = vec![
+        (a, vec![b, d]),
+        (b, vec![c, e]),
+        (c, vec![f]),
+        (d, vec![e]),
+        (e, vec![f]),
+    ];
+    let mut maze = Maze::new(3, 2, a, c, paths);
+    maze.try_set_cost(b, 10).unwrap();
+
+    assert_eq!(solve_bfs(&maze).unwrap().positions(), [a, b, c]);
+    for path in [solve_dijkstra(&maze), solve_astar(&maze)] {
+        let path = path.unwrap();
+        assert_eq!(path.positions(), [a, d, e, f, c]);
+        assert_eq!(maze.path_cost(path.positions()), 4);
+    }
+}
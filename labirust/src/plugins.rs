@@ -0,0 +1,56 @@
+//! ## Plugins
+//!
+//! This module loads [`Algorithm`](crate::Algorithm)s from shared libraries at runtime,
+//! so that third-party solvers can be used by an application without recompiling it.
+//!
+//! A plugin is a `cdylib` crate exporting a [`RegisterFn`] named `register`, adding its algorithms to a [`Registry`].
+//! As Rust has no stable ABI, it must be built with the same compiler, and the same version and features of labirust, as the application loading it.
+//!
+//! ```
+//! use labirust::{
+//!     implementations::{AlgorithmEntry, DepthFirst, Registry},
+//!     Algorithm,
+//! };
+//!
+//! #[no_mangle]
+//! pub fn register(registry: &mut Registry) {
+//!     let factory = || DepthFirst::new().boxed();
+//!     registry.register(AlgorithmEntry::new("plugged", "a depth first search from a plugin", factory));
+//! }
+//! ```
+
+use std::{ffi::OsStr, mem};
+
+use libloading::Library;
+
+use crate::implementations::Registry;
+
+/// Signature of the `register` function exported by plugins.
+pub type RegisterFn = fn(&mut Registry);
+
+impl Registry {
+    /// Load the plugin at `path` and call its `register` function on the [`Registry`].
+    /// The library stays loaded until the process exits, as the algorithms it registers run its code.
+    ///
+    /// # Safety
+    ///
+    /// Loading a library runs its initialization routines, and its `register` symbol is trusted to be a [`RegisterFn`]
+    /// built with the same compiler, version and features of labirust.
+    pub unsafe fn load(&mut self, path: impl AsRef<OsStr>) -> crate::Result<()> {
+        let library = Library::new(path)?;
+        let register = *library.get::<RegisterFn>(b"register")?;
+        mem::forget(library);
+        register(self);
+        Ok(())
+    }
+}
+
+#[test]
+fn missing() {
+    use crate::Error;
+
+    let mut registry = Registry::new();
+    let result = unsafe { registry.load("./missing-labirust-plugin.so") };
+    assert!(matches!(result, Err(Error::Plugin(_))));
+    assert_eq!(registry.entries().len(), Registry::new().entries().len());
+}
@@ -2,10 +2,11 @@
 //!
 //! This module contains the definition of the [`Pos`] type, used to represent positions in the rest of the library;
 
-use std::ops::{Add, Mul, Sub};
+use core::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 
 /// A discrete position on a 2D-grid.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pos(isize, isize);
 
 impl Pos {
@@ -31,7 +32,7 @@ impl Pos {
 
     /// Scale the vector by an integer value.
     pub fn scale(self, factor: isize) -> Self {
-        self * Self::sized(factor)
+        self * factor
     }
 
     /// Accessor of the x-coordinate.
@@ -48,6 +49,29 @@ impl Pos {
     pub fn decompose(self) -> (isize, isize) {
         (self.x(), self.y())
     }
+
+    /// Index of the position in a flat, row-major array of rows of `width` elements.
+    pub fn to_index(self, width: isize) -> usize {
+        (self.y() * width + self.x()) as usize
+    }
+
+    /// Position corresponding to an `index` in a flat, row-major array of rows of `width` elements.
+    /// Panics if `width` is not positive (c.f. [`Pos::try_from_index`]).
+    pub fn from_index(index: usize, width: isize) -> Self {
+        match Self::try_from_index(index, width) {
+            Some(result) => result,
+            None => panic!("rows of {width} elements can not hold any index"),
+        }
+    }
+
+    /// Position corresponding to an `index` in a flat, row-major array of rows of `width` elements, `None` if `width` is not positive.
+    pub fn try_from_index(index: usize, width: isize) -> Option<Self> {
+        if width <= 0 {
+            return None;
+        }
+        let index = index as isize;
+        Some(Self::new(index % width, index / width))
+    }
 }
 
 impl From<(isize, isize)> for Pos {
@@ -84,3 +108,59 @@ impl Mul for Pos {
         Self::new(x, y)
     }
 }
+
+impl Mul<isize> for Pos {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        let x = self.x() * rhs;
+        let y = self.y() * rhs;
+        Self::new(x, y)
+    }
+}
+
+impl Neg for Pos {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x(), -self.y())
+    }
+}
+
+impl AddAssign for Pos {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Pos {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+#[test]
+fn index_conversion() {
+    let width = 7;
+    for index in 0..(width * 5) as usize {
+        let position = Pos::from_index(index, width);
+        assert!(position.x() < width);
+        assert_eq!(position.to_index(width), index);
+    }
+    assert_eq!(Pos::new(3, 2).to_index(width), 17);
+    assert_eq!(Pos::try_from_index(17, width), Some(Pos::new(3, 2)));
+    assert_eq!(Pos::try_from_index(17, 0), None);
+    assert_eq!(Pos::try_from_index(0, -2), None);
+}
+
+#[test]
+fn arithmetic() {
+    let mut position = Pos::new(2, 3);
+    assert_eq!(-position, Pos::new(-2, -3));
+    assert_eq!(position * 3, Pos::new(6, 9));
+    assert_eq!(Pos::sized(4), Pos::new(4, 4));
+    position += Pos::new(1, -1);
+    assert_eq!(position, Pos::new(3, 2));
+    position -= Pos::one();
+    assert_eq!(position, Pos::new(2, 1));
+}
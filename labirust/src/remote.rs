@@ -0,0 +1,230 @@
+//! ## Remote
+//!
+//! This module contains the [`RemoteAlgorithm`], delegating the resolution of a [`crate::Maze`] to a program at the other end of a socket,
+//! so that solvers written in any language can be run by an [`crate::Executor`].
+//!
+//! The protocol exchanges JSON objects, one per line, positions being `[x, y]` arrays.
+//! * Before the first tick, the remote program receives `{"type": "maze", "width", "height", "start", "end"}`.
+//! * On each tick, it receives `{"type": "insight", "tick", "position", "paths"}`,
+//!   `paths` holding the positions reachable from `position`, the tail of the previous guess.
+//! * It answers each insight with `{"guess": [...]}`, a path from the start of the maze, or `{"guess": null}` to give up.
+//!
+//! Answers longer than [`MAX_LINE`] bytes are rejected, and connections made by [`RemoteAlgorithm::connect`]
+//! give up on answers taking longer than [`TIMEOUT`].
+
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Algorithm, Context, Error, Guess, Insight, Pos};
+
+/// Maximum length in bytes of a line answered by the remote program, newline included.
+pub const MAX_LINE: usize = 1 << 24;
+
+/// Time the remote program is given to answer, on connections made by [`RemoteAlgorithm::connect`].
+pub const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Messages sent to the remote program.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Request<'i> {
+    Maze {
+        width: isize,
+        height: isize,
+        start: Pos,
+        end: Pos,
+    },
+    Insight {
+        tick: usize,
+        position: Pos,
+        paths: &'i [Pos],
+    },
+}
+
+/// Answer of the remote program to an insight.
+#[derive(Deserialize)]
+struct Reply {
+    guess: Option<Vec<Pos>>,
+}
+
+/// An [`Algorithm`] forwarding each [`Insight`] to a remote program and following the guesses it answers.
+/// A failure to communicate is handled as the algorithm giving up, the cause being kept (c.f. [`RemoteAlgorithm::take_error`]).
+pub struct RemoteAlgorithm<S: Read + Write = TcpStream> {
+    stream: BufReader<S>,
+    tick: usize,
+    received: usize,
+    error: Option<Error>,
+}
+
+impl RemoteAlgorithm {
+    /// Connect to a remote program listening at `address`, waiting at most [`TIMEOUT`] for each of its answers.
+    pub fn connect(address: impl ToSocketAddrs) -> crate::Result<Self> {
+        let stream = TcpStream::connect(address)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        Ok(Self::new(stream))
+    }
+}
+
+impl<S: Read + Write> RemoteAlgorithm<S> {
+    /// Constructor, communicating with the remote program through `stream`, whose timeouts are left to the caller.
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream: BufReader::new(stream),
+            tick: 0,
+            received: 0,
+            error: None,
+        }
+    }
+
+    /// Take the error that interrupted the communication with the remote program, if any.
+    pub fn take_error(&mut self) -> Option<Error> {
+        self.error.take()
+    }
+
+    fn send(&mut self, request: &Request) -> crate::Result<()> {
+        let mut line = serde_json::to_string(request).expect("request serialization");
+        line.push('\n');
+        let stream = self.stream.get_mut();
+        stream.write_all(line.as_bytes())?;
+        stream.flush()?;
+        Ok(())
+    }
+
+    fn receive(&mut self) -> crate::Result<Reply> {
+        let mut line = String::new();
+        let read = (&mut self.stream)
+            .take(MAX_LINE as u64)
+            .read_line(&mut line)?;
+        if read == 0 {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+        self.received += 1;
+        let error = |message: String| Error::Parse {
+            line: self.received,
+            message,
+        };
+        if !line.ends_with('\n') && read == MAX_LINE {
+            return Err(error(format!("line longer than {MAX_LINE} bytes")));
+        }
+        serde_json::from_str(&line).map_err(|e| error(e.to_string()))
+    }
+
+    /// Exchange the messages of a tick, returns the guess of the remote program.
+    fn exchange(&mut self, insight: &Insight, ctx: &Context) -> crate::Result<Option<Vec<Pos>>> {
+        if self.tick == 0 {
+            let (width, height) = ctx.size();
+            let (start, end) = (ctx.start(), ctx.end());
+            self.send(&Request::Maze {
+                width,
+                height,
+                start,
+                end,
+            })?;
+        }
+        self.send(&Request::Insight {
+            tick: self.tick,
+            position: insight.position(),
+            paths: insight.paths(),
+        })?;
+        let Reply { guess } = self.receive()?;
+        self.tick += 1;
+        Ok(guess)
+    }
+}
+
+impl<S: Read + Write> Algorithm for RemoteAlgorithm<S> {
+    fn progress(&mut self, insight: &Insight, ctx: &mut Context) -> Guess {
+        match self.exchange(insight, ctx) {
+            Ok(Some(path)) => ctx.guess(path),
+            Ok(None) => ctx.give_up(),
+            Err(error) => {
+                warn!("remote algorithm failed at tick {}: {error}", self.tick);
+                self.error = Some(error);
+                ctx.give_up()
+            }
+        }
+    }
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn remote() {
+    use std::{net::TcpListener, thread};
+
+    use crate::{executor::drive, MazeGenerator, SimpleGenerator};
+
+    let maze = SimpleGenerator::new(6, 4).generate().unwrap();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    // a remote depth first search, keeping its own stack of paths
+    let solver = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut lines = BufReader::new(stream.try_clone().unwrap()).lines();
+        let mut stream = stream;
+        let message: serde_json::Value =
+            serde_json::from_str(&lines.next().unwrap().unwrap()).unwrap();
+        assert_eq!(message["type"], "maze");
+        let start: Pos = serde_json::from_value(message["start"].clone()).unwrap();
+        let (mut current, mut stack, mut seen) = (vec![start], Vec::new(), vec![start]);
+        for line in lines {
+            let message: serde_json::Value = serde_json::from_str(&line.unwrap()).unwrap();
+            let paths: Vec<Pos> = serde_json::from_value(message["paths"].clone()).unwrap();
+            for next in paths {
+                if seen.contains(&next) {
+                    continue;
+                }
+                seen.push(next);
+                let mut path = current.clone();
+                path.push(next);
+                stack.push(path);
+            }
+            let guess = stack.pop();
+            current = guess.clone().unwrap_or_default();
+            writeln!(stream, "{}", serde_json::json!({ "guess": guess })).unwrap();
+        }
+    });
+
+    let mut algorithm = RemoteAlgorithm::connect(address).unwrap();
+    let ticks = drive(&maze, &mut algorithm, Default::default(), |_, _| ()).unwrap();
+    assert!(ticks > 0);
+    assert!(algorithm.take_error().is_none());
+    drop(algorithm);
+    solver.join().unwrap();
+}
+
+#[cfg(feature = "generate")]
+#[test]
+fn remote_errors() {
+    use std::{net::TcpListener, thread};
+
+    use crate::{executor::drive, MazeGenerator, SimpleGenerator};
+
+    let maze = SimpleGenerator::new(6, 4).generate().unwrap();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    // a first answer that is not JSON, then a second one that never ends
+    let solver = thread::spawn(move || {
+        for answer in [b"guess\n".to_vec(), vec![b' '; MAX_LINE + 1]] {
+            let (mut stream, _) = listener.accept().unwrap();
+            let _ = stream.write_all(&answer);
+            let _ = stream.read_to_end(&mut Vec::new());
+        }
+    });
+
+    for expected in ["expected value", "line longer than"] {
+        let mut algorithm = RemoteAlgorithm::connect(address).unwrap();
+        let result = drive(&maze, &mut algorithm, Default::default(), |_, _| ());
+        assert!(result.is_err());
+        match algorithm.take_error() {
+            Some(Error::Parse { line: 1, message }) => assert!(message.contains(expected)),
+            error => panic!("unexpected error {error:?}"),
+        }
+    }
+    solver.join().unwrap();
+}
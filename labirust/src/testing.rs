@@ -0,0 +1,183 @@
+//! ## Testing
+//!
+//! This module contains utilities to property-test [`Algorithm`](crate::Algorithm)s and [`MazeGenerator`](crate::MazeGenerator)s:
+//! [`proptest`] strategies producing random valid [`Maze`]s, positions and paths,
+//! and helpers checking the invariants every [`Maze`] is expected to uphold.
+//!
+//! ```
+//! use labirust::{testing, MazeGenerator, SimpleGenerator};
+//! use proptest::prelude::*;
+//!
+//! proptest!(|((width, height) in testing::sizes(16, 16))| {
+//!     let maze = SimpleGenerator::new(width, height).generate().unwrap();
+//!     prop_assert!(testing::is_perfect(&maze));
+//! });
+//! ```
+
+use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{Maze, Path, Pos, SimpleGenerator, Topology};
+
+/// Strategy producing maze sizes from 1 by 1 up to `max_width` by `max_height`.
+pub fn sizes(max_width: isize, max_height: isize) -> impl Strategy<Value = (isize, isize)> {
+    (1..=max_width, 1..=max_height)
+}
+
+/// Strategy producing positions inside a `width` by `height` maze.
+pub fn positions(width: isize, height: isize) -> impl Strategy<Value = Pos> {
+    (0..width, 0..height).prop_map(Pos::from)
+}
+
+/// Strategy producing valid, perfect [`Maze`]s up to `max_width` by `max_height`, shrinking towards smaller mazes.
+pub fn mazes(max_width: isize, max_height: isize) -> impl Strategy<Value = Maze> {
+    (sizes(max_width, max_height), any::<u64>()).prop_map(|((width, height), seed)| {
+        let mut rng = StdRng::seed_from_u64(seed);
+        SimpleGenerator::new(width, height)
+            .generate_with(&mut rng)
+            .expect("valid size")
+    })
+}
+
+/// Strategy producing every [`Topology`], shrinking towards [`Topology::Square`].
+pub fn topologies() -> impl Strategy<Value = Topology> {
+    prop_oneof![
+        Just(Topology::Square),
+        Just(Topology::Torus),
+        Just(Topology::Hex),
+        Just(Topology::Diagonal),
+    ]
+}
+
+/// Strategy producing valid, perfect [`Maze`]s of `topology` up to `max_width` by `max_height`, shrinking towards smaller mazes.
+/// Toroidal mazes are at least 3 by 3 (c.f. [`Topology::Torus`]).
+pub fn mazes_of(
+    topology: Topology,
+    max_width: isize,
+    max_height: isize,
+) -> impl Strategy<Value = Maze> {
+    let min = match topology {
+        Topology::Torus => 3,
+        Topology::Square | Topology::Hex | Topology::Diagonal => 1,
+    };
+    (
+        min..=max_width.max(min),
+        min..=max_height.max(min),
+        any::<u64>(),
+    )
+        .prop_map(move |(width, height, seed)| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            SimpleGenerator::new(width, height)
+                .topology(topology)
+                .generate_with(&mut rng)
+                .expect("valid size")
+        })
+}
+
+/// Strategy producing [`Path`]s of up to `max_len` positions starting at the start of `maze` and only going through open passages.
+/// Paths are random walks, they may go back and forth.
+pub fn paths(maze: Maze, max_len: usize) -> impl Strategy<Value = Path> {
+    prop::collection::vec(any::<prop::sample::Index>(), 0..max_len).prop_map(move |choices| {
+        let mut result = vec![maze.start()];
+        for choice in choices {
+            let current = *result.last().expect("not empty");
+            let neighbors = maze.paths_from(current);
+            if neighbors.is_empty() {
+                break;
+            }
+            result.push(*choice.get(&neighbors));
+        }
+        Path::new(result)
+    })
+}
+
+impl Arbitrary for Pos {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Positions with coordinates between -1000 and 1000, c.f. [`positions`] for positions inside a maze.
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        (-1000..=1000isize, -1000..=1000isize)
+            .prop_map(Pos::from)
+            .boxed()
+    }
+}
+
+impl Arbitrary for Maze {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Mazes up to 32 by 32, c.f. [`mazes`].
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        mazes(32, 32).boxed()
+    }
+}
+
+/// Whether every cell of `maze` can be reached from its start.
+pub fn is_connected(maze: &Maze) -> bool {
+    let (width, height) = maze.size();
+    maze.distance_field(maze.start()).reached() == (width * height) as usize
+}
+
+/// Whether every passage of `maze` leads to a cell inside of it and can be taken both ways.
+pub fn is_symmetric(maze: &Maze) -> bool {
+    cells(maze).all(|position| {
+        maze.paths_from(position).iter().all(|&neighbor| {
+            maze.is_inside(neighbor) && maze.paths_from(neighbor).contains(&position)
+        })
+    })
+}
+
+/// Whether `maze` is symmetric, connected and without loops, there being a single path between any two cells.
+pub fn is_perfect(maze: &Maze) -> bool {
+    let (width, height) = maze.size();
+    let passages: usize = cells(maze).map(|p| maze.degree(p)).sum::<usize>() / 2;
+    is_symmetric(maze) && is_connected(maze) && passages == (width * height) as usize - 1
+}
+
+/// Whether `path` starts at the start of `maze` and only goes through open passages.
+pub fn is_valid_path(maze: &Maze, path: &[Pos]) -> bool {
+    path.first() == Some(&maze.start())
+        && path.iter().all(|&position| maze.is_inside(position))
+        && path
+            .windows(2)
+            .all(|pair| !maze.is_walled(pair[0], pair[1]))
+}
+
+fn cells(maze: &Maze) -> impl Iterator<Item = Pos> + '_ {
+    let (width, height) = maze.size();
+    (0..height).flat_map(move |y| (0..width).map(move |x| Pos::new(x, y)))
+}
+
+proptest! {
+    #[test]
+    fn generated_mazes(maze in any::<Maze>()) {
+        prop_assert!(is_perfect(&maze));
+    }
+
+    #[test]
+    fn random_walks((maze, path) in mazes(12, 12).prop_flat_map(|maze| (Just(maze.clone()), paths(maze, 40)))) {
+        prop_assert!(is_valid_path(&maze, path.positions()));
+    }
+
+    #[test]
+    fn algorithms_solve(
+        maze in topologies().prop_flat_map(|topology| mazes_of(topology, 16, 16)),
+        name in prop::sample::select(vec!["depth-first", "breath-first", "bidirectional", "wall-follower-right", "tremaux", "ida-star", "pledge", "jump-point", "wavefront", "beam", "fringe"]),
+    ) {
+        use crate::{executor::drive, implementations::by_name, Error, MazeError};
+
+        // a maze starting on its end is solved without any guess
+        let mut last = vec![maze.start()];
+        let result = drive(&maze, &mut by_name(name).unwrap(), Default::default(), |_, guess| last = guess);
+        // headings and straight runs only make sense on square mazes, that are not solved from the start
+        if maze.topology() != Topology::Square && ["pledge", "jump-point"].contains(&name) && !maze.is_end(maze.start()) {
+            prop_assert!(matches!(result, Err(Error::InvalidMaze(MazeError::UnsupportedTopology))));
+            return Ok(());
+        }
+        prop_assert!(result.is_ok(), "{name} on a {:?} maze: {result:?}", maze.topology());
+        prop_assert!(is_valid_path(&maze, &last));
+        prop_assert!(maze.is_end(*last.last().unwrap()));
+    }
+
+}
@@ -0,0 +1,131 @@
+//! ## Visited
+//!
+//! This module contains the definition of the [`VisitedSet`] type, a compact set of positions of a [`crate::Maze`].
+
+use alloc::{vec, vec::Vec};
+
+use crate::Pos;
+
+/// Set of positions of a grid, stored as one bit per cell.
+/// Much lighter than a [`std::collections::HashSet`] of positions when used to track visited cells of a [`crate::Maze`].
+#[derive(Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VisitedSet {
+    width: isize,
+    height: isize,
+    bits: Vec<u64>,
+}
+
+impl VisitedSet {
+    /// Constructor, for a grid of `width` by `height` cells.
+    pub fn new(width: isize, height: isize) -> Self {
+        let count = (width * height) as usize;
+        let bits = vec![0; count.div_ceil(64)];
+        Self {
+            width,
+            height,
+            bits,
+        }
+    }
+
+    fn is_inside(&self, position: Pos) -> bool {
+        let (x, y) = position.decompose();
+        x >= 0 && x < self.width && y >= 0 && y < self.height
+    }
+
+    /// Adds a position to the set, returns whether it was absent.
+    pub fn insert(&mut self, position: Pos) -> bool {
+        assert!(self.is_inside(position), "position out of bounds");
+        let index = position.to_index(self.width);
+        let (word, mask) = (index / 64, 1 << (index % 64));
+        let absent = self.bits[word] & mask == 0;
+        self.bits[word] |= mask;
+        absent
+    }
+
+    /// Removes a position from the set, returns whether it was present.
+    pub fn remove(&mut self, position: Pos) -> bool {
+        if !self.is_inside(position) {
+            return false;
+        }
+        let index = position.to_index(self.width);
+        let (word, mask) = (index / 64, 1 << (index % 64));
+        let present = self.bits[word] & mask != 0;
+        self.bits[word] &= !mask;
+        present
+    }
+
+    /// Check if the position is in the set, positions out of bounds never are.
+    pub fn contains(&self, position: Pos) -> bool {
+        if !self.is_inside(position) {
+            return false;
+        }
+        let index = position.to_index(self.width);
+        self.bits[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// Removes every position from the set.
+    pub fn clear(&mut self) {
+        self.bits.fill(0);
+    }
+
+    /// Number of positions in the set.
+    pub fn len(&self) -> usize {
+        self.bits.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Check if the set contains no position.
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|&w| w == 0)
+    }
+
+    /// Iterates over the positions of the set in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = Pos> + '_ {
+        let width = self.width;
+        self.bits
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &word)| {
+                (0..64)
+                    .filter(move |b| word & (1 << b) != 0)
+                    .map(move |b| i * 64 + b)
+            })
+            .map(move |index| Pos::from_index(index, width))
+    }
+}
+
+impl Clone for VisitedSet {
+    fn clone(&self) -> Self {
+        Self {
+            bits: self.bits.clone(),
+            ..*self
+        }
+    }
+
+    /// Reuses the storage of `self` when possible.
+    fn clone_from(&mut self, source: &Self) {
+        self.width = source.width;
+        self.height = source.height;
+        self.bits.clone_from(&source.bits);
+    }
+}
+
+#[test]
+fn visited_set() {
+    let mut set = VisitedSet::new(10, 10);
+    assert!(set.is_empty());
+    assert!(set.insert(Pos::new(3, 4)));
+    assert!(!set.insert(Pos::new(3, 4)));
+    assert!(set.insert(Pos::new(9, 9)));
+    assert!(set.contains(Pos::new(3, 4)));
+    assert!(!set.contains(Pos::new(4, 3)));
+    assert!(!set.contains(Pos::new(10, 0)));
+    assert_eq!(set.len(), 2);
+    assert_eq!(
+        set.iter().collect::<Vec<_>>(),
+        vec![Pos::new(3, 4), Pos::new(9, 9)]
+    );
+    assert!(set.remove(Pos::new(3, 4)));
+    set.clear();
+    assert!(set.is_empty());
+}
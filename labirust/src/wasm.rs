@@ -0,0 +1,106 @@
+//! ## Wasm
+//!
+//! This module contains the WebAssembly bindings of the crate, exposing maze generation and a step by step execution to JavaScript.
+//! Enable it without the default features, as the thread-local random generator, threads and the terminal are not available in browsers,
+//! from a `cdylib` crate re-exporting the bindings with `pub use labirust::wasm::*;`, then build that crate with `wasm-pack`.
+
+use alloc::{string::String, vec::Vec};
+
+use rand::{rngs::StdRng, SeedableRng};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::{implementations, Execution, Maze, Pos, SimpleGenerator};
+
+/// A [`Maze`] usable from JavaScript.
+#[wasm_bindgen]
+pub struct WasmMaze {
+    maze: Maze,
+}
+
+#[wasm_bindgen]
+impl WasmMaze {
+    /// Generate a maze with the [`SimpleGenerator`], the same `seed` always giving the same maze.
+    #[wasm_bindgen(constructor)]
+    pub fn generate(width: isize, height: isize, seed: u64) -> Result<WasmMaze, JsError> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let maze = SimpleGenerator::new(width, height)
+            .generate_with(&mut rng)
+            .map_err(|error| JsError::new(&alloc::format!("{error}")))?;
+        Ok(Self { maze })
+    }
+
+    pub fn width(&self) -> isize {
+        self.maze.width()
+    }
+
+    pub fn height(&self) -> isize {
+        self.maze.height()
+    }
+
+    /// The maze serialized as JSON.
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.maze).expect("maze serialization failed")
+    }
+}
+
+/// The resolution of a [`WasmMaze`] by one of the bundled [`crate::Algorithm`]s, progressing one tick per call to `step`.
+#[wasm_bindgen]
+pub struct WasmExecution {
+    execution: Execution,
+}
+
+/// State of a [`WasmExecution`] after a tick, as serialized for JavaScript.
+#[derive(Serialize)]
+struct WasmFrame<'e> {
+    tick: usize,
+    finished: bool,
+    path: &'e [Pos],
+    tried: Vec<Pos>,
+}
+
+#[wasm_bindgen]
+impl WasmExecution {
+    /// Prepare the resolution of `maze` by the algorithm registered under `algorithm`, c.f. [`implementations::registry`].
+    #[wasm_bindgen(constructor)]
+    pub fn new(maze: &WasmMaze, algorithm: &str) -> Result<WasmExecution, JsError> {
+        let algorithm = implementations::by_name(algorithm)
+            .map_err(|error| JsError::new(&alloc::format!("{error}")))?;
+        let execution = Execution::new(maze.maze.clone(), algorithm);
+        Ok(Self { execution })
+    }
+
+    /// Progress by one tick, returns whether the end of the maze has been reached.
+    pub fn step(&mut self) -> Result<bool, JsError> {
+        self.execution
+            .step()
+            .map_err(|error| JsError::new(&alloc::format!("{error}")))
+    }
+
+    /// The current frame serialized as JSON: the tick, whether the execution is finished, the last guessed path and the tried positions.
+    #[wasm_bindgen(js_name = frameJson)]
+    pub fn frame_json(&self) -> String {
+        let Self { execution } = self;
+        let frame = WasmFrame {
+            tick: execution.tick(),
+            finished: execution.is_finished(),
+            path: execution.path(),
+            tried: execution.tried().iter().collect(),
+        };
+        serde_json::to_string(&frame).expect("frame serialization failed")
+    }
+}
+
+#[test]
+fn execution() {
+    let maze = WasmMaze::generate(12, 6, 3).ok().unwrap();
+    assert_eq!(
+        maze.to_json(),
+        WasmMaze::generate(12, 6, 3).ok().unwrap().to_json()
+    );
+    let mut execution = WasmExecution::new(&maze, "breath-first").ok().unwrap();
+    while !execution.step().ok().unwrap() {}
+    let frame = execution.frame_json();
+    assert!(frame.contains("\"finished\":true"));
+}